@@ -0,0 +1,22 @@
+//! Lists every author on Hangar along with how many projects they have, most prolific first.
+//! Run with `cargo run --example authors --features blocking`.
+
+use hangar_api::{
+	api::AuthorsRequest,
+	blocking::BlockingClient,
+	object::{Pagination, SortDirection, UsersSort},
+};
+
+fn main() -> hangar_api::error::Result<()> {
+	let client = BlockingClient::new();
+	let request = AuthorsRequest::builder()
+		.sort(UsersSort::ProjectCount)
+		.direction(SortDirection::Descending)
+		.pagination(Pagination::page(0, Pagination::MAX_LIMIT))
+		.build();
+	for author in client.paginate(request) {
+		let author = author?;
+		println!("{} ({} projects)", author.name, author.project_count);
+	}
+	Ok(())
+}
@@ -0,0 +1,24 @@
+//! Fetches a project from the browser and logs its description.
+//! Build with `cargo build --example wasm --features reqwest --target wasm32-unknown-unknown`.
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+	use hangar_api::{api::ProjectRequest, client::HangarClient};
+
+	wasm_bindgen_futures::spawn_local(async {
+		let client = HangarClient::new();
+		let message = match client
+			.send(&ProjectRequest::builder().slug("ViaVersion").build())
+			.await
+		{
+			Ok(project) => project.description,
+			Err(err) => err.to_string(),
+		};
+		web_sys::console::log_1(&message.into());
+	});
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+	eprintln!("this example only runs on wasm32 targets");
+}
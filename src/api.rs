@@ -1,153 +1,3183 @@
-use constcat::concat;
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize, Serializer};
+use time::{format_description::well_known::Rfc3339, Date, OffsetDateTime};
 use typed_builder::TypedBuilder;
 
-use crate::object::*;
+use crate::auth::{ApiKey, AuthToken, JwtToken};
+use crate::error::{Error, ParseEnumError, Result};
+use crate::object::*;
+
+/// base url for normal api calls on the official Hangar instance
+pub const BASE_API_URL: &str = "https://hangar.papermc.io/api/v1";
+
+/// The http method a request is sent with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+	Get,
+	Post,
+	Delete,
+}
+
+impl HttpMethod {
+	/// Gets the method's name, like `GET`.
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Get => "GET",
+			Self::Post => "POST",
+			Self::Delete => "DELETE",
+		}
+	}
+}
+
+impl std::fmt::Display for HttpMethod {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+#[cfg(feature = "reqwest")]
+impl From<HttpMethod> for reqwest::Method {
+	fn from(value: HttpMethod) -> Self {
+		match value {
+			HttpMethod::Get => Self::GET,
+			HttpMethod::Post => Self::POST,
+			HttpMethod::Delete => Self::DELETE,
+		}
+	}
+}
+
+/// Trait implemented on all request structs.
+pub trait HangarRequest: Serialize {
+	/// The type Hangar responds with for this request.
+	type Response: DeserializeOwned;
+
+	/// The http method this request is sent with.
+	const METHOD: HttpMethod = HttpMethod::Get;
+
+	/// Whether responses to this request can be served from a client's cache.
+	/// Only read-only requests whose responses are the same for everyone should be cached.
+	const CACHEABLE: bool = false;
+
+	/// The permission Hangar requires to send this request, if it needs one.
+	/// Clients built with `HangarClientBuilder::check_permissions` check it before sending the request.
+	const REQUIRED_PERMISSION: Option<NamedPermission> = None;
+
+	/// Gets the path of this request, relative to the api's base url.
+	fn path(&self) -> String;
+
+	/// Gets the slug of the project `REQUIRED_PERMISSION` is needed in, or `None` if it's needed globally.
+	fn permission_project(&self) -> Option<&str> {
+		None
+	}
+
+	/// Gets the URL this request should be sent to on the official Hangar instance.
+	fn url(&self) -> String {
+		self.url_with_base(BASE_API_URL)
+	}
+
+	/// Gets the URL this request should be sent to on a Hangar instance with the given base url.
+	fn url_with_base(&self, base: &str) -> String {
+		format!("{}{}", base.trim_end_matches('/'), self.path())
+	}
+
+	/// Gets the query parameters of this request. Unset values are skipped and lists become repeated parameters.
+	/// Fails with `Error::InvalidRequest` if the request's fields can't be serialized as parameters.
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		serialize_query(self)
+	}
+
+	/// Checks the request is valid before it's sent, so mistakes fail without a round trip to Hangar.
+	fn validate(&self) -> Result<()> {
+		Ok(())
+	}
+
+	/// Gets the json body of this request, for requests which send one instead of query parameters.
+	fn body(&self) -> Option<Vec<u8>> {
+		None
+	}
+
+	/// Parses the raw response body into the response type.
+	/// Errors say where in the json they happened.
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		parse_json(body)
+	}
+
+	/// Gets the language this request asks Hangar to respond in, overriding the client's
+	/// `HangarClientBuilder::language`. `None` uses the client's language.
+	fn language(&self) -> Option<&str> {
+		None
+	}
+
+	/// Asks Hangar to respond to this request in the given language, see `WithLanguage`.
+	fn with_language(self, language: impl Into<String>) -> WithLanguage<Self>
+	where
+		Self: Sized,
+	{
+		WithLanguage {
+			request: self,
+			language: language.into(),
+		}
+	}
+
+	/// Adds query parameters this crate doesn't model yet, which are sent after the typed parameters.
+	/// See `WithExtraParams` for how they're merged.
+	fn with_extra_params<K, V>(
+		self,
+		params: impl IntoIterator<Item = (K, V)>,
+	) -> WithExtraParams<Self>
+	where
+		Self: Sized,
+		K: Into<String>,
+		V: Into<String>,
+	{
+		WithExtraParams {
+			request: self,
+			extra_params: params
+				.into_iter()
+				.map(|(key, value)| (key.into(), value.into()))
+				.collect(),
+		}
+	}
+
+	/// Gets everything needed to send this request to a Hangar instance with the given base url, for sending it
+	/// with something other than this crate's clients. Feed the response back to `decode_response` to parse it.
+	///
+	/// The headers include the ones this request needs, like its `Accept-Language` and `Content-Type`, but no
+	/// `User-Agent`. Use `RequestParts::authorize` to add the auth token. Fails like `query` if the query parameters
+	/// can't be serialized.
+	///
+	/// ```
+	/// use hangar_api::api::{HangarRequest, HttpMethod, ProjectRequest};
+	///
+	/// let parts = ProjectRequest::new("Some Project")
+	///     .to_parts("https://hangar.papermc.io/api/v1")
+	///     .unwrap();
+	/// assert_eq!(parts.method, HttpMethod::Get);
+	/// assert_eq!(parts.full_url(), "https://hangar.papermc.io/api/v1/projects/Some%20Project");
+	/// assert!(parts.body.is_none());
+	/// ```
+	fn to_parts(&self, base_url: &str) -> Result<RequestParts> {
+		let mut headers = Vec::new();
+		if let Some(language) = self.language() {
+			headers.push(("Accept-Language".to_string(), language.to_string()));
+		}
+		let body = self.body();
+		if body.is_some() {
+			headers.push(("Content-Type".to_string(), "application/json".to_string()));
+		}
+		Ok(RequestParts {
+			method: Self::METHOD,
+			url: self.url_with_base(base_url),
+			query: self.query()?,
+			headers,
+			body,
+		})
+	}
+
+	/// Gets a `curl` command sending this request to a Hangar instance with the given base url, e.g. to reproduce
+	/// a problem in a bug report. The auth token and any api key are replaced with placeholders, so the command
+	/// can be shared as it is.
+	fn to_curl(&self, base_url: &str, auth: Option<&AuthToken>) -> Result<String> {
+		curl_command(self, base_url, auth, true)
+	}
+
+	/// Gets a `curl` command like `to_curl`, but with the auth token and any api key included so it can be run.
+	fn to_curl_unredacted(&self, base_url: &str, auth: Option<&AuthToken>) -> Result<String> {
+		curl_command(self, base_url, auth, false)
+	}
+}
+
+/// Query parameters whose values are secret.
+pub(crate) const SECRET_PARAMS: &[&str] = &["apiKey"];
+
+/// A request as data, to be sent by anything which can send http requests, see `HangarRequest::to_parts`.
+#[derive(Clone)]
+pub struct RequestParts {
+	/// The http method of the request
+	pub method: HttpMethod,
+	/// The url of the request, without its query string
+	pub url: String,
+	/// The query parameters of the request, not yet percent-encoded
+	pub query: Vec<(String, String)>,
+	/// The headers of the request
+	pub headers: Vec<(String, String)>,
+	/// The json body of the request, if any
+	pub body: Option<Vec<u8>>,
+}
+
+/// Secrets like api keys and auth tokens are redacted from the debug output.
+impl std::fmt::Debug for RequestParts {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let query: Vec<_> = self
+			.query
+			.iter()
+			.map(|(name, value)| (name, redacted(name, value, SECRET_PARAMS)))
+			.collect();
+		let headers: Vec<_> = self
+			.headers
+			.iter()
+			.map(|(name, value)| (name, redacted(name, value, &["Authorization"])))
+			.collect();
+		f.debug_struct("RequestParts")
+			.field("method", &self.method)
+			.field("url", &self.url)
+			.field("query", &query)
+			.field("headers", &headers)
+			.field("body", &self.body)
+			.finish()
+	}
+}
+
+fn redacted<'a>(name: &str, value: &'a str, secrets: &[&str]) -> &'a str {
+	if secrets
+		.iter()
+		.any(|secret| secret.eq_ignore_ascii_case(name))
+	{
+		"<redacted>"
+	} else {
+		value
+	}
+}
+
+impl RequestParts {
+	/// Adds the `Authorization` header authenticating the request with a token, replacing any the request had.
+	pub fn authorize(&mut self, token: &AuthToken) {
+		self.headers
+			.retain(|(name, _)| !name.eq_ignore_ascii_case("Authorization"));
+		self.headers.push((
+			"Authorization".to_string(),
+			format!("HangarAuth {}", token.token.expose()),
+		));
+	}
+
+	/// Gets the url of the request with its percent-encoded query string.
+	pub fn full_url(&self) -> String {
+		with_query(&self.url, &self.query, false)
+	}
+}
+
+/// Appends a percent-encoded query string to a url, optionally redacting secret parameters.
+fn with_query(url: &str, query: &[(String, String)], redact: bool) -> String {
+	let query: Vec<String> = query
+		.iter()
+		.map(|(name, value)| {
+			let value = if redact && SECRET_PARAMS.contains(&name.as_str()) {
+				"REDACTED".to_string()
+			} else {
+				encode_segment(value)
+			};
+			format!("{}={value}", encode_segment(name))
+		})
+		.collect();
+	if query.is_empty() {
+		url.to_string()
+	} else {
+		format!("{url}?{}", query.join("&"))
+	}
+}
+
+/// Parses a raw response to a request sent without this crate's clients, see `HangarRequest::to_parts`.
+/// Error statuses become the same errors the clients give, except that rate limits don't know when they reset.
+pub fn decode_response<R: HangarRequest>(status: u16, body: &[u8]) -> Result<R::Response> {
+	match status {
+		200..=299 => R::parse_response(body),
+		304 => Err(Error::NotModified),
+		429 => Err(Error::RateLimited { retry_after: None }),
+		status => Err(Error::from_response(status, body)),
+	}
+}
+
+/// Builds the `curl` command for a request, see `HangarRequest::to_curl`.
+fn curl_command<R: HangarRequest + ?Sized>(
+	request: &R,
+	base_url: &str,
+	auth: Option<&AuthToken>,
+	redact: bool,
+) -> Result<String> {
+	/// Quotes an argument for posix shells.
+	fn quote(arg: &str) -> String {
+		format!("'{}'", arg.replace('\'', r"'\''"))
+	}
+
+	let parts = request.to_parts(base_url)?;
+	let url = with_query(&parts.url, &parts.query, redact);
+	let method = match R::METHOD {
+		HttpMethod::Get => "",
+		HttpMethod::Post => "-X POST ",
+		HttpMethod::Delete => "-X DELETE ",
+	};
+	let mut args = vec![format!("curl {method}{}", quote(&url))];
+	if let Some(auth) = auth {
+		let token = if redact {
+			"REDACTED"
+		} else {
+			auth.token.expose()
+		};
+		args.push(format!(
+			"-H {}",
+			quote(&format!("Authorization: HangarAuth {token}"))
+		));
+	}
+	for (name, value) in &parts.headers {
+		args.push(format!("-H {}", quote(&format!("{name}: {value}"))));
+	}
+	if let Some(body) = &parts.body {
+		args.push(format!(
+			"--data-raw {}",
+			quote(&String::from_utf8_lossy(body))
+		));
+	}
+	Ok(args.join(" \\\n  "))
+}
+
+/// Trait implemented on requests whose responses are split into pages.
+/// The limit and offset of a request's `Pagination` are always sent as query parameters:
+///
+/// ```
+/// use hangar_api::api::{HangarRequest, VersionsRequest};
+/// use hangar_api::object::Pagination;
+///
+/// let request = VersionsRequest::builder()
+///     .slug("Example")
+///     .pagination(Pagination::page(2, 10))
+///     .build();
+/// let query = request.query().unwrap();
+/// assert!(query.contains(&("limit".to_string(), "10".to_string())));
+/// assert!(query.contains(&("offset".to_string(), "20".to_string())));
+/// ```
+pub trait PaginatedRequest: HangarRequest {
+	/// The type of item in each page.
+	type Item;
+
+	/// Gets the pagination information of this request.
+	fn pagination_mut(&mut self) -> &mut Pagination;
+
+	/// Splits a response into its pagination information and the items of the page.
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>);
+
+	/// Makes this request return the items which could be deserialized alongside errors for those which couldn't,
+	/// rather than failing entirely. See `PartialRequest`.
+	fn partial(self) -> PartialRequest<Self>
+	where
+		Self: Sized,
+	{
+		PartialRequest(self)
+	}
+
+	/// Gets what identifies an item across pages, e.g. a project's id. Items can move between pages while
+	/// they're being fetched, like when sorting by downloads, so paginating skips items whose key was already seen
+	/// on an earlier page. `None` keeps every item.
+	fn item_key(_item: &Self::Item) -> Option<String> {
+		None
+	}
+
+	/// Moves this request on to the page after one it received, keeping its limit as the page size.
+	/// Returns whether there are more pages to fetch.
+	fn advance(&mut self, response: &PaginationResponse, items: usize) -> bool {
+		let page = self.pagination_mut();
+		page.offset += page.limit;
+		items > 0 && page.limit > 0 && page.offset < response.count
+	}
+}
+
+/// A request with extra query parameters appended to its own, for parameters Hangar supports before this crate
+/// does.
+///
+/// The typed parameters always take precedence: extra parameters with the same name as one the request already
+/// sends are dropped rather than overriding it. Extra parameters can still repeat each other.
+///
+/// ```
+/// use hangar_api::api::{HangarRequest, ProjectsRequest};
+/// use hangar_api::object::Pagination;
+///
+/// let request = ProjectsRequest::builder()
+///     .query(Some("worldedit".to_string()))
+///     .pagination(Pagination::page(0, 10))
+///     .build()
+///     .with_extra_params([("member", "EngineHub"), ("query", "ignored")]);
+/// let query = request.query().unwrap();
+/// assert!(query.contains(&("member".to_string(), "EngineHub".to_string())));
+/// assert!(query.contains(&("query".to_string(), "worldedit".to_string())));
+/// assert!(!query.contains(&("query".to_string(), "ignored".to_string())));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithExtraParams<R> {
+	/// The request being sent
+	pub request: R,
+	/// The extra query parameters, in the order they're sent
+	pub extra_params: Vec<(String, String)>,
+}
+
+impl<R: Serialize> Serialize for WithExtraParams<R> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		self.request.serialize(serializer)
+	}
+}
+
+impl<R: HangarRequest> HangarRequest for WithExtraParams<R> {
+	type Response = R::Response;
+	const METHOD: HttpMethod = R::METHOD;
+	const CACHEABLE: bool = R::CACHEABLE;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = R::REQUIRED_PERMISSION;
+
+	fn path(&self) -> String {
+		self.request.path()
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		self.request.permission_project()
+	}
+
+	fn language(&self) -> Option<&str> {
+		self.request.language()
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		let mut query = self.request.query()?;
+		let typed = query.len();
+		for (key, value) in &self.extra_params {
+			if query[..typed].iter().any(|(typed_key, _)| typed_key == key) {
+				debug!(param = %key, "dropping extra query parameter which is already set");
+				continue;
+			}
+			query.push((key.clone(), value.clone()));
+		}
+		Ok(query)
+	}
+
+	fn validate(&self) -> Result<()> {
+		self.request.validate()
+	}
+
+	fn body(&self) -> Option<Vec<u8>> {
+		self.request.body()
+	}
+
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		R::parse_response(body)
+	}
+}
+
+impl<R: PaginatedRequest> PaginatedRequest for WithExtraParams<R> {
+	type Item = R::Item;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		self.request.pagination_mut()
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		R::into_page(response)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		R::item_key(item)
+	}
+
+	fn advance(&mut self, response: &PaginationResponse, items: usize) -> bool {
+		self.request.advance(response, items)
+	}
+}
+
+/// Checks a language is a valid value for the `Accept-Language` header, like `de` or `de-DE, en;q=0.5`.
+pub(crate) fn validate_language(language: &str) -> Result<()> {
+	let valid = !language.trim().is_empty()
+		&& language
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || "-_,;=.* ".contains(c));
+	if valid {
+		Ok(())
+	} else {
+		Err(Error::InvalidRequest(format!(
+			"invalid language: {language}"
+		)))
+	}
+}
+
+/// A request sent with its own `Accept-Language` header, overriding the client's language.
+///
+/// Hangar doesn't localize any of its responses yet, so this currently changes nothing about them. Responses
+/// which could be localized are projects' descriptions and the contents of their pages.
+///
+/// ```
+/// use hangar_api::api::{HangarRequest, ProjectRequest};
+///
+/// let request = ProjectRequest::new("Example").with_language("de");
+/// assert_eq!(request.language(), Some("de"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithLanguage<R> {
+	/// The request being sent
+	pub request: R,
+	/// The language it's sent with, as a value for the `Accept-Language` header
+	pub language: String,
+}
+
+impl<R: Serialize> Serialize for WithLanguage<R> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		self.request.serialize(serializer)
+	}
+}
+
+impl<R: HangarRequest> HangarRequest for WithLanguage<R> {
+	type Response = R::Response;
+	const METHOD: HttpMethod = R::METHOD;
+	const CACHEABLE: bool = R::CACHEABLE;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = R::REQUIRED_PERMISSION;
+
+	fn path(&self) -> String {
+		self.request.path()
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		self.request.permission_project()
+	}
+
+	fn language(&self) -> Option<&str> {
+		Some(&self.language)
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		self.request.query()
+	}
+
+	fn validate(&self) -> Result<()> {
+		validate_language(&self.language)?;
+		self.request.validate()
+	}
+
+	fn body(&self) -> Option<Vec<u8>> {
+		self.request.body()
+	}
+
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		R::parse_response(body)
+	}
+}
+
+impl<R: PaginatedRequest> PaginatedRequest for WithLanguage<R> {
+	type Item = R::Item;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		self.request.pagination_mut()
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		R::into_page(response)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		R::item_key(item)
+	}
+
+	fn advance(&mut self, response: &PaginationResponse, items: usize) -> bool {
+		self.request.advance(response, items)
+	}
+}
+
+/// A paginated request which returns the items of its page which could be deserialized, instead of failing when
+/// any of them couldn't. Useful when one project Hangar sends doesn't match what this crate expects.
+#[derive(Debug, Clone)]
+pub struct PartialRequest<R>(pub R);
+
+impl<R: Serialize> Serialize for PartialRequest<R> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		self.0.serialize(serializer)
+	}
+}
+
+impl<R> HangarRequest for PartialRequest<R>
+where
+	R: PaginatedRequest,
+	R::Item: DeserializeOwned,
+{
+	type Response = PartialResponse<R::Item>;
+	const METHOD: HttpMethod = R::METHOD;
+	const CACHEABLE: bool = R::CACHEABLE;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = R::REQUIRED_PERMISSION;
+
+	fn path(&self) -> String {
+		self.0.path()
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		self.0.permission_project()
+	}
+
+	fn language(&self) -> Option<&str> {
+		self.0.language()
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		self.0.query()
+	}
+
+	fn validate(&self) -> Result<()> {
+		self.0.validate()
+	}
+
+	fn body(&self) -> Option<Vec<u8>> {
+		self.0.body()
+	}
+}
+
+/// The items of a page which could be deserialized, and errors for the ones which couldn't.
+#[derive(Debug, Clone)]
+pub struct PartialResponse<T> {
+	pub pagination: PaginationResponse,
+	/// The items which were deserialized, in order
+	pub items: Vec<T>,
+	/// The items which couldn't be deserialized
+	pub errors: Vec<ItemError>,
+}
+
+/// Each item is deserialized on its own, so one bad item doesn't fail the rest.
+impl<'de, T: DeserializeOwned> Deserialize<'de> for PartialResponse<T> {
+	fn deserialize<D: serde::Deserializer<'de>>(
+		deserializer: D,
+	) -> std::result::Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		struct RawPage {
+			pagination: PaginationResponse,
+			result: Vec<serde_json::Value>,
+		}
+
+		let page = RawPage::deserialize(deserializer)?;
+		let mut response = PartialResponse {
+			pagination: page.pagination,
+			items: Vec::new(),
+			errors: Vec::new(),
+		};
+		for (index, item) in page.result.into_iter().enumerate() {
+			match serde_path_to_error::deserialize(&item) {
+				Ok(parsed) => response.items.push(parsed),
+				Err(error) => {
+					let name = item_name(&item);
+					let mut error = Error::from(error);
+					if let Error::Deserialize { path, item, .. } = &mut error {
+						*path = format!("result[{index}].{path}");
+						item.clone_from(&name);
+					}
+					response.errors.push(ItemError { index, name, error });
+				}
+			}
+		}
+		Ok(response)
+	}
+}
+
+impl<T> PartialResponse<T> {
+	/// Checks whether every item was deserialized.
+	pub fn is_complete(&self) -> bool {
+		self.errors.is_empty()
+	}
+}
+
+/// An item of a page which couldn't be deserialized.
+#[derive(Debug, Clone)]
+pub struct ItemError {
+	/// Where the item is in the page
+	pub index: usize,
+	/// The slug of the project, or the name of the version or user, if it could be read
+	pub name: Option<String>,
+	/// Why the item couldn't be deserialized, with the path to the problem in the page
+	pub error: Error,
+}
+
+/// Gets the slug of a project, or the name of a version or user, from an item of a page.
+fn item_name(item: &serde_json::Value) -> Option<String> {
+	item.pointer("/namespace/slug")
+		.or_else(|| item.get("name"))
+		.and_then(|name| name.as_str())
+		.map(str::to_string)
+}
+
+/// Deserializes a json body, noting where in the json any error happened.
+/// If the error was in an item of a page, the item's slug or name is noted too.
+fn parse_json<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+	let deserializer = &mut serde_json::Deserializer::from_slice(body);
+	let mut error = match serde_path_to_error::deserialize(deserializer) {
+		Ok(parsed) => return Ok(parsed),
+		Err(error) => Error::from(error),
+	};
+	if let Error::Deserialize { path, item, .. } = &mut error {
+		let index = path
+			.strip_prefix("result[")
+			.and_then(|rest| rest.split_once(']'))
+			.and_then(|(index, _)| index.parse::<usize>().ok());
+		if let Some(index) = index {
+			*item = serde_json::from_slice::<serde_json::Value>(body)
+				.ok()
+				.and_then(|page| page.get("result")?.get(index).and_then(item_name));
+		}
+	}
+	Err(error)
+}
+
+/// Serializes a request's fields as query parameters, skipping unset values and repeating lists.
+/// Nested structs like `Pagination` are flattened into the top level here whether or not they're marked with
+/// `#[serde(flatten)]`, so their fields are always sent rather than leaving the server to use its defaults.
+fn serialize_query<T: Serialize + ?Sized>(value: &T) -> Result<Vec<(String, String)>> {
+	let value = serde_json::to_value(value).map_err(|err| {
+		Error::InvalidRequest(format!(
+			"the query parameters couldn't be serialized: {err}"
+		))
+	})?;
+	let map = match value {
+		serde_json::Value::Object(map) => map,
+		serde_json::Value::Null => return Ok(Vec::new()),
+		value => {
+			return Err(Error::InvalidRequest(format!(
+				"the query parameters have to be a struct, not `{value}`"
+			)))
+		}
+	};
+	let mut query = Vec::new();
+	push_query_map(&mut query, map);
+	Ok(query)
+}
+
+/// A field requests can be sorted by.
+trait SortField: Copy {
+	/// The direction to sort by this field in when a request doesn't say.
+	fn default_sort_direction(self) -> SortDirection;
+
+	/// Gets the value Hangar expects for sorting by this field in a direction.
+	fn sort_value(self, direction: SortDirection) -> String;
+}
+
+impl SortField for ProjectsSort {
+	fn default_sort_direction(self) -> SortDirection {
+		self.default_direction()
+	}
+
+	fn sort_value(self, direction: SortDirection) -> String {
+		self.query_value(direction)
+	}
+}
+
+/// Versions are sorted newest first, like Hangar sorts them without a sort.
+impl SortField for VersionsSort {
+	fn default_sort_direction(self) -> SortDirection {
+		SortDirection::Descending
+	}
+
+	fn sort_value(self, direction: SortDirection) -> String {
+		self.query_value(direction)
+	}
+}
+
+impl SortField for UsersSort {
+	fn default_sort_direction(self) -> SortDirection {
+		SortDirection::Ascending
+	}
+
+	fn sort_value(self, direction: SortDirection) -> String {
+		self.query_value(direction)
+	}
+}
+
+/// Serializes a request's fields as query parameters like `serialize_query`, adding its sort. Hangar takes the sort
+/// field and direction combined into a single `sort` parameter, like `-views` for the most viewed first.
+fn query_with_sort<S: SortField>(
+	request: &impl Serialize,
+	sort: Option<S>,
+	direction: Option<SortDirection>,
+) -> Result<Vec<(String, String)>> {
+	let mut query = serialize_query(request)?;
+	if let Some(sort) = sort {
+		let direction = direction.unwrap_or(sort.default_sort_direction());
+		query.push(("sort".to_string(), sort.sort_value(direction)));
+	}
+	Ok(query)
+}
+
+fn push_query_map(
+	query: &mut Vec<(String, String)>,
+	map: serde_json::Map<String, serde_json::Value>,
+) {
+	for (key, value) in map {
+		let values = match value {
+			serde_json::Value::Array(values) => values,
+			value => vec![value],
+		};
+		for value in values {
+			match value {
+				serde_json::Value::Null => {}
+				serde_json::Value::String(value) => query.push((key.clone(), value)),
+				serde_json::Value::Object(map) => push_query_map(query, map),
+				value => query.push((key.clone(), value.to_string())),
+			}
+		}
+	}
+}
+
+/// Gets what identifies a project across pages. Projects can be renamed while paginating, so they're told apart by
+/// id, falling back to their namespace for responses without one.
+fn project_key(id: Option<i64>, namespace: &Namespace) -> String {
+	id.map_or_else(|| namespace.key(), |id| format!("#{id}"))
+}
+
+/// Serializes a date as the start of the day in utc, since Hangar expects full timestamps for date ranges.
+fn serialize_date<S: Serializer>(
+	date: &Date,
+	serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+	let timestamp = date
+		.midnight()
+		.assume_utc()
+		.format(&Rfc3339)
+		.map_err(serde::ser::Error::custom)?;
+	serializer.serialize_str(&timestamp)
+}
+
+/// Checks a date range is in order.
+fn validate_date_range(from_date: Date, to_date: Date) -> Result<()> {
+	if from_date > to_date {
+		return Err(Error::InvalidRequest(format!(
+			"from date {from_date} is after to date {to_date}"
+		)));
+	}
+	Ok(())
+}
+
+/// Percent-encodes a value for use as a single segment of a url path.
+pub(crate) fn encode_segment(value: &str) -> String {
+	let mut encoded = String::with_capacity(value.len());
+	for byte in value.bytes() {
+		match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+				encoded.push(byte as char)
+			}
+			byte => encoded.push_str(&format!("%{byte:02X}")),
+		}
+	}
+	encoded
+}
+
+/// Parses a plain text response body. The text is kept exactly as sent, including its line endings, so a body
+/// which isn't valid utf-8 is an error rather than having its invalid bytes replaced.
+fn parse_text(body: &[u8]) -> Result<String> {
+	String::from_utf8(body.to_vec()).map_err(|err| {
+		Error::from(<serde_json::Error as serde::de::Error>::custom(format!(
+			"response body isn't valid utf-8: {err}"
+		)))
+	})
+}
+
+/// Parses the markdown body of a page.
+fn parse_page(body: &[u8]) -> Result<Page> {
+	Ok(Page {
+		contents: parse_text(body)?,
+	})
+}
+
+/// Searches all the projects on Hangar, or for a single user. Requires the `view_public_info` permission.
+#[derive(Debug, Clone, Default, Serialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+#[builder(field_defaults(default, setter(into)))]
+pub struct ProjectsRequest {
+	/// Whether to prioritize the project with an exact name match if present
+	pub prioritize_exact_match: Option<bool>,
+	/// Pagination information
+	#[builder(!default)]
+	#[serde(flatten)]
+	pub pagination: Pagination,
+	/// Used to sort the result
+	#[serde(skip)]
+	pub sort: Option<ProjectsSort>,
+	/// The direction to sort in, defaulting to descending for everything but `ProjectsSort::Slug`
+	#[serde(skip)]
+	pub direction: Option<SortDirection>,
+	/// Categories to filter for, matching projects in any of them
+	#[serde(rename = "category")]
+	pub categories: Vec<Category>,
+	/// Platforms to filter for, matching projects supporting any of them
+	#[serde(rename = "platform")]
+	pub platforms: Vec<Platform>,
+	/// The author of the project
+	pub owner: Option<String>,
+	/// The query to use when searching. Hangar has no filter for keywords, but matches them with the query.
+	pub query: Option<String>,
+	/// A license type to filter for. Strings are checked against the types this crate knows when the request is
+	/// validated, see `LicenseFilter`.
+	pub license: Option<LicenseFilter>,
+	/// A platform version to filter for
+	pub version: Option<String>,
+	/// A tag to filter for
+	pub tag: Option<String>,
+	/// The member of the project
+	pub member: Option<String>,
+}
+
+/// A license type to filter projects by.
+///
+/// Strings convert into a known license type when they name one, like `mit` or the SPDX identifier `Apache-2.0`,
+/// and are rejected by `ProjectsRequest::validate` otherwise, since Hangar returns no projects for license types it
+/// doesn't have. Use `Unchecked` to send a type this crate doesn't know about yet.
+///
+/// ```
+/// use hangar_api::api::{HangarRequest, LicenseFilter, ProjectsRequest};
+/// use hangar_api::object::{LicenseType, Pagination};
+///
+/// let request = |license: LicenseFilter| {
+///     ProjectsRequest::builder()
+///         .pagination(Pagination::default())
+///         .license(license)
+///         .build()
+/// };
+/// assert_eq!(LicenseFilter::from("apache-2.0"), LicenseFilter::Known(LicenseType::Apache2));
+/// assert!(request("MTI".into()).validate().is_err());
+/// assert!(request(LicenseFilter::Unchecked("EUPL".into())).validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseFilter {
+	/// A license type this crate knows
+	Known(LicenseType),
+	/// A license type sent as is, without being checked
+	Unchecked(String),
+	/// A string which isn't a license type this crate knows, which fails validation
+	Invalid(ParseEnumError),
+}
+
+impl From<LicenseType> for LicenseFilter {
+	fn from(value: LicenseType) -> Self {
+		Self::Known(value)
+	}
+}
+
+impl From<&str> for LicenseFilter {
+	fn from(value: &str) -> Self {
+		match value.parse() {
+			Ok(license) => Self::Known(license),
+			Err(err) => Self::Invalid(err),
+		}
+	}
+}
+
+impl From<String> for LicenseFilter {
+	fn from(value: String) -> Self {
+		value.as_str().into()
+	}
+}
+
+impl LicenseFilter {
+	/// Checks that the license type is known or was explicitly left unchecked.
+	pub fn validate(&self) -> Result<()> {
+		match self {
+			Self::Invalid(err) => Err(Error::InvalidRequest(format!(
+				"{err}. Use `LicenseFilter::Unchecked` to filter for it anyway"
+			))),
+			Self::Known(_) | Self::Unchecked(_) => Ok(()),
+		}
+	}
+}
+
+impl Serialize for LicenseFilter {
+	fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		match self {
+			Self::Known(license) => serializer.collect_str(license),
+			Self::Unchecked(license) => serializer.serialize_str(license),
+			Self::Invalid(err) => serializer.serialize_str(&err.value),
+		}
+	}
+}
+
+/// Presets for common searches, which are the easiest place to start from. Adjust them with `with` or by setting
+/// their fields.
+impl ProjectsRequest {
+	/// Creates a request for the most downloaded projects in a category, up to `limit` of them per page.
+	///
+	/// ```
+	/// use hangar_api::api::{HangarRequest, ProjectsRequest};
+	/// use hangar_api::object::Category;
+	///
+	/// let request = ProjectsRequest::top_downloads(Category::AdminTools, 10);
+	/// assert_eq!(
+	///     request.query().unwrap(),
+	///     [("category", "admin_tools"), ("limit", "10"), ("offset", "0"), ("sort", "-downloads")]
+	///         .map(|(name, value)| (name.to_string(), value.to_string())),
+	/// );
+	/// ```
+	pub fn top_downloads(category: Category, limit: i64) -> Self {
+		Self {
+			pagination: Pagination::page(0, limit),
+			sort: Some(ProjectsSort::Downloads),
+			categories: vec![category],
+			..Self::default()
+		}
+	}
+
+	/// Creates a request for the most recently updated projects supporting a platform version, like `1.21`, up to
+	/// `limit` of them per page.
+	///
+	/// ```
+	/// use hangar_api::api::{HangarRequest, ProjectsRequest};
+	/// use hangar_api::object::Platform;
+	///
+	/// let request = ProjectsRequest::recently_updated(Platform::Paper, "1.21", 25);
+	/// assert_eq!(
+	///     request.query().unwrap(),
+	///     [
+	///         ("limit", "25"),
+	///         ("offset", "0"),
+	///         ("platform", "PAPER"),
+	///         ("version", "1.21"),
+	///         ("sort", "-updated"),
+	///     ]
+	///     .map(|(name, value)| (name.to_string(), value.to_string())),
+	/// );
+	/// ```
+	pub fn recently_updated(platform: Platform, version: impl Into<String>, limit: i64) -> Self {
+		Self {
+			pagination: Pagination::page(0, limit),
+			sort: Some(ProjectsSort::Updated),
+			platforms: vec![platform],
+			version: Some(version.into()),
+			..Self::default()
+		}
+	}
+
+	/// Creates a request for every project owned by a user or organization, by slug, with pages as large as
+	/// Hangar allows. Use `HangarClient::projects_for_user` to include the projects of organizations a user is in.
+	///
+	/// ```
+	/// use hangar_api::api::{HangarRequest, ProjectsRequest};
+	///
+	/// let request = ProjectsRequest::by_owner("EngineHub").with(|request| request.query = Some("edit".into()));
+	/// assert_eq!(
+	///     request.query().unwrap(),
+	///     [("limit", "25"), ("offset", "0"), ("owner", "EngineHub"), ("query", "edit"), ("sort", "slug")]
+	///         .map(|(name, value)| (name.to_string(), value.to_string())),
+	/// );
+	/// ```
+	pub fn by_owner(owner: impl Into<String>) -> Self {
+		Self {
+			pagination: Pagination::page(0, Pagination::MAX_LIMIT),
+			sort: Some(ProjectsSort::Slug),
+			owner: Some(owner.into()),
+			..Self::default()
+		}
+	}
+
+	/// Creates a request searching for projects by name, with Hangar putting a project named exactly that first if
+	/// there is one. Up to 10 projects are returned, see `ProjectMatch` for telling whether the first one is the
+	/// project named.
+	pub fn named(name: impl Into<String>) -> Self {
+		Self {
+			prioritize_exact_match: Some(true),
+			pagination: Pagination::page(0, 10),
+			query: Some(name.into()),
+			..Self::default()
+		}
+	}
+
+	/// Creates a request for projects similar to another one, e.g. for a "you might also like" section. These are the
+	/// most downloaded projects in the same category which match the project's first keyword, which is usually the
+	/// one describing it best, or just the most downloaded projects in the category if it has no keywords. The
+	/// project itself is likely to be among them, see `Paginated::without`.
+	pub fn similar_to(project: &Project, pagination: Pagination) -> Self {
+		Self {
+			pagination,
+			sort: Some(ProjectsSort::Downloads),
+			categories: vec![project.category],
+			query: project.settings.keywords.first().cloned(),
+			..Self::default()
+		}
+	}
+
+	/// Adjusts the request, e.g. one of the presets.
+	pub fn with(mut self, adjust: impl FnOnce(&mut Self)) -> Self {
+		adjust(&mut self);
+		self
+	}
+}
+
+/// What searching for a project by name turned up, see `HangarClient::find_project`.
+#[derive(Debug, Clone)]
+pub enum ProjectMatch {
+	/// A project is named exactly that, see `Project::is_named`
+	Exact(Box<Project>),
+	/// No project is named exactly that, but these came up searching for it, most relevant first
+	Ambiguous(Vec<Project>),
+	/// Nothing came up searching for it
+	NotFound,
+}
+
+impl ProjectMatch {
+	/// Picks the project named exactly `name` from the results of searching for it with `ProjectsRequest::named`.
+	/// Hangar puts an exact match first, but the first result is only taken if it really is named that.
+	pub fn from_results(name: &str, mut projects: Vec<Project>) -> Self {
+		match projects.first() {
+			None => Self::NotFound,
+			Some(first) if first.is_named(name) => Self::Exact(Box::new(projects.swap_remove(0))),
+			Some(_) => Self::Ambiguous(projects),
+		}
+	}
+
+	/// Gets the project if it was an exact match.
+	pub fn exact(self) -> Option<Project> {
+		match self {
+			Self::Exact(project) => Some(*project),
+			Self::Ambiguous(_) | Self::NotFound => None,
+		}
+	}
+}
+
+/// What to look through for projects and versions waiting on moderation, for staff tooling. Hangar's api has no
+/// moderation queue, so `HangarClient::moderation_queue` builds one from searching for the most recently updated
+/// projects and filtering them, rather than this being a `HangarRequest`.
+///
+/// This can only find what the search shows the user, which includes projects waiting for approval only for users
+/// allowed to see hidden projects, and only the `scan` most recently updated projects are looked at, so items
+/// waiting on an older project can be missed. Looking at versions sends a request per project scanned.
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct ModerationQueueRequest {
+	/// How many of the most recently updated projects to look through
+	#[builder(default = 100)]
+	pub scan: usize,
+	/// Whether to look through each scanned project's newest versions for unreviewed ones too
+	#[builder(default)]
+	pub versions: bool,
+}
+
+impl ModerationQueueRequest {
+	/// Creates the request for the projects to look through.
+	pub fn scan_request(&self) -> ProjectsRequest {
+		ProjectsRequest {
+			pagination: Pagination::page(0, Pagination::MAX_LIMIT),
+			sort: Some(ProjectsSort::Updated),
+			..ProjectsRequest::default()
+		}
+	}
+}
+
+/// A version waiting for review, submitted by its `author` when it was created.
+#[derive(Debug, Clone)]
+pub struct PendingVersion {
+	/// The project the version belongs to
+	pub project: Namespace,
+	pub version: Version,
+}
+
+/// The projects and versions waiting on moderation, see `ModerationQueueRequest`.
+#[derive(Debug, Clone, Default)]
+pub struct ModerationQueue {
+	/// Projects waiting for approval, submitted by their owner and oldest update first
+	pub projects: Vec<Project>,
+	/// Unreviewed versions, oldest first
+	pub versions: Vec<PendingVersion>,
+	/// How many projects were looked through
+	pub scanned: usize,
+}
+
+impl ModerationQueue {
+	/// Picks out what's waiting on moderation from the projects looked through and the versions of each, if they
+	/// were fetched.
+	pub fn from_scan(projects: Vec<Project>, versions: Vec<(Namespace, Vec<Version>)>) -> Self {
+		let scanned = projects.len();
+		let mut projects: Vec<Project> = projects
+			.into_iter()
+			.filter(|project| project.visibility == Visibility::NeedsApproval)
+			.collect();
+		projects.sort_by_key(|project| project.last_updated);
+		let mut versions: Vec<PendingVersion> = versions
+			.into_iter()
+			.flat_map(|(project, versions)| {
+				versions
+					.into_iter()
+					.filter(|version| version.review_state == ReviewState::Unreviewed)
+					.map(move |version| PendingVersion {
+						project: project.clone(),
+						version,
+					})
+			})
+			.collect();
+		versions.sort_by_key(|pending| pending.version.created_at);
+		Self {
+			projects,
+			versions,
+			scanned,
+		}
+	}
+
+	/// Checks whether nothing is waiting on moderation.
+	pub fn is_empty(&self) -> bool {
+		self.projects.is_empty() && self.versions.is_empty()
+	}
+}
+
+impl ProjectsRequest {
+	/// Creates a request for how many projects match this one in a single category, replacing its categories.
+	/// Only one project is returned, the count is in the response's pagination.
+	pub fn category_count_request(&self, category: Category) -> Self {
+		Self {
+			categories: vec![category],
+			..self.count_request()
+		}
+	}
+
+	/// Creates a request for how many projects match this one on a single platform, replacing its platforms.
+	/// Only one project is returned, the count is in the response's pagination.
+	pub fn platform_count_request(&self, platform: Platform) -> Self {
+		Self {
+			platforms: vec![platform],
+			..self.count_request()
+		}
+	}
+
+	fn count_request(&self) -> Self {
+		Self {
+			pagination: Pagination::page(0, 1),
+			sort: None,
+			direction: None,
+			..self.clone()
+		}
+	}
+}
+
+impl HangarRequest for ProjectsRequest {
+	type Response = ProjectsResponse;
+	const CACHEABLE: bool = true;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		"/projects".to_string()
+	}
+
+	fn validate(&self) -> Result<()> {
+		self.pagination.validate()?;
+		self.license
+			.as_ref()
+			.map_or(Ok(()), LicenseFilter::validate)
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		query_with_sort(self, self.sort, self.direction)
+	}
+}
+
+impl PaginatedRequest for ProjectsRequest {
+	type Item = Project;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(project_key(item.id, &item.namespace))
+	}
+}
+
+/// A page of items returned by a paginated endpoint.
+///
+/// A search or listing which matches nothing is a valid, empty page rather than an error. `Error::NotFound` only
+/// ever means what the request is about doesn't exist, like the project whose versions are listed, so the two
+/// can be told apart:
+///
+/// ```
+/// use hangar_api::api::ProjectsResponse;
+/// use hangar_api::error::Error;
+///
+/// let response: ProjectsResponse = serde_json::from_str(
+///     r#"{"pagination": {"limit": 25, "offset": 0, "count": 0}, "result": []}"#,
+/// )
+/// .unwrap();
+/// assert!(response.is_empty());
+/// assert!(!response.has_matches());
+///
+/// // a 404 is an error, never an empty page
+/// assert!(matches!(Error::from_response(404, b""), Error::NotFound));
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Paginated<T> {
+	pub pagination: PaginationResponse,
+	pub result: Vec<T>,
+}
+
+impl<T> Paginated<T> {
+	/// Gets how many items are on this page.
+	pub fn len(&self) -> usize {
+		self.result.len()
+	}
+
+	/// Checks whether this page has no items, either because nothing matched or because it's past the last page.
+	/// Use `has_matches` to tell those apart.
+	pub fn is_empty(&self) -> bool {
+		self.result.is_empty()
+	}
+
+	/// Checks whether anything matched the request at all, on this page or any other.
+	pub fn has_matches(&self) -> bool {
+		self.pagination.count > 0
+	}
+
+	/// Iterates over the items on this page.
+	pub fn iter(&self) -> std::slice::Iter<'_, T> {
+		self.result.iter()
+	}
+
+	/// Checks whether there are more items after this page.
+	pub fn has_next(&self) -> bool {
+		self.next_pagination().is_some()
+	}
+
+	/// Gets the pagination for the page after this one, see `PaginationResponse::to_next`.
+	pub fn next_pagination(&self) -> Option<Pagination> {
+		self.pagination.to_next()
+	}
+}
+
+impl<T> IntoIterator for Paginated<T> {
+	type Item = T;
+	type IntoIter = std::vec::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.result.into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a Paginated<T> {
+	type Item = &'a T;
+	type IntoIter = std::slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.result.iter()
+	}
+}
+
+pub type ProjectsResponse = Paginated<Project>;
+
+impl Paginated<Project> {
+	/// Iterates over the projects which are available to everyone, see `Visibility::is_available`.
+	pub fn available(&self) -> impl Iterator<Item = &Project> {
+		self.result.iter().filter(|project| project.is_available())
+	}
+
+	/// Removes a project from the page, e.g. the one a search for similar projects started from. The count of
+	/// matches goes down by one if it was on the page.
+	pub fn without(mut self, project: &Project) -> Self {
+		let key = project.namespace.key();
+		let len = self.result.len();
+		self.result.retain(|other| other.namespace.key() != key);
+		if self.result.len() < len {
+			self.pagination.count = (self.pagination.count - 1).max(0);
+		}
+		self
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct PaginationResponse {
+	/// The maximum amount of items to return
+	pub limit: i64,
+	/// Where to start searching
+	pub offset: i64,
+	/// The total number of records
+	pub count: i64,
+}
+
+/// Carries the limit and offset of a response over, e.g. to send the same page again.
+impl From<&PaginationResponse> for Pagination {
+	fn from(value: &PaginationResponse) -> Self {
+		Self {
+			limit: value.limit,
+			offset: value.offset,
+		}
+	}
+}
+
+impl PaginationResponse {
+	/// Gets how many pages there are in total, or 0 if the limit isn't positive.
+	pub fn total_pages(&self) -> i64 {
+		if self.limit <= 0 {
+			return 0;
+		}
+		(self.count + self.limit - 1) / self.limit
+	}
+
+	/// Gets the pagination for the page after this one with the same limit, or `None` if this is the last page.
+	pub fn to_next(&self) -> Option<Pagination> {
+		let offset = self.offset.saturating_add(self.limit);
+		(self.limit > 0 && offset < self.count).then_some(Pagination {
+			limit: self.limit,
+			offset,
+		})
+	}
+
+	/// Gets the number of this page, starting from 0 like `Pagination::page`.
+	pub fn current_page(&self) -> i64 {
+		if self.limit <= 0 {
+			return 0;
+		}
+		self.offset / self.limit
+	}
+}
+
+/// Returns info on a specific project. Requires the `view_public_info` permission.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct ProjectRequest {
+	/// The slug of the project to return
+	#[serde(skip)]
+	pub slug: Slug,
+}
+
+impl ProjectRequest {
+	/// Creates a request for the project with the given slug.
+	///
+	/// ```
+	/// use hangar_api::api::{HangarRequest, ProjectRequest};
+	///
+	/// let request = ProjectRequest::new("ViaVersion");
+	/// assert_eq!(request.path(), "/projects/ViaVersion");
+	/// ```
+	pub fn new(slug: impl Into<Slug>) -> Self {
+		Self { slug: slug.into() }
+	}
+}
+
+impl HangarRequest for ProjectRequest {
+	type Response = Project;
+	const CACHEABLE: bool = true;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/projects/{}", encode_segment(&self.slug))
+	}
+}
+
+/// Returns a page of a project. Requires visibility of the page, and gives `Error::NotFound` if there's no page at the path.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct PageRequest {
+	/// The slug of the project to return the page for
+	#[serde(skip)]
+	pub slug: String,
+	/// The path of the page
+	pub path: String,
+}
+
+impl PageRequest {
+	/// Creates a request for the page at a path in a project.
+	pub fn new(slug: impl Into<String>, path: impl Into<String>) -> Self {
+		Self {
+			slug: slug.into(),
+			path: path.into(),
+		}
+	}
+}
+
+impl HangarRequest for PageRequest {
+	type Response = Page;
+	const CACHEABLE: bool = true;
+
+	fn path(&self) -> String {
+		format!("/pages/page/{}", encode_segment(&self.slug))
+	}
+
+	/// Pages are returned as raw markdown rather than json.
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		parse_page(body)
+	}
+}
+
+/// Returns the main page of a project. Requires visibility of the page.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct MainPageRequest {
+	/// The slug of the project to return the main page for
+	#[serde(skip)]
+	pub slug: String,
+}
+
+impl HangarRequest for MainPageRequest {
+	type Response = Page;
+	const CACHEABLE: bool = true;
+
+	fn path(&self) -> String {
+		format!("/pages/main/{}", encode_segment(&self.slug))
+	}
+
+	/// Pages are returned as raw markdown rather than json.
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		parse_page(body)
+	}
+}
+
+/// Returns all versions of a project. Requires the `view_public_info` permission in the project or owning organization.
+/// Sorting applies across pages, so paginating a request sorted ascending goes from the oldest version onwards:
+///
+/// ```
+/// use hangar_api::api::{HangarRequest, VersionsRequest};
+/// use hangar_api::object::{Pagination, SortDirection, VersionsSort};
+///
+/// let request = VersionsRequest::builder()
+///     .slug("Example")
+///     .pagination(Pagination::page(0, 25))
+///     .sort(VersionsSort::CreatedAt)
+///     .direction(SortDirection::Ascending)
+///     .build();
+/// assert!(request.query().unwrap().contains(&("sort".to_string(), "createdAt".to_string())));
+/// ```
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+#[serde(rename_all = "camelCase")]
+pub struct VersionsRequest {
+	/// The slug of the project to return versions for
+	#[builder(!default)]
+	#[serde(skip)]
+	pub slug: String,
+	#[builder(!default)]
+	#[serde(flatten)]
+	pub pagination: Pagination,
+	/// Whether to include hidden-by-default channels in the result, defaults to try
+	pub include_hidden_channels: Option<bool>,
+	/// A name of a version channel to filter for, either a `CommonChannel` or any other name
+	#[builder(setter(strip_option(fallback = channel_opt)))]
+	pub channel: Option<String>,
+	/// A platform name to filter for
+	pub platform: Option<Platform>,
+	/// A platform version to filter for
+	pub platform_version: Option<String>,
+	/// Used to sort the result, which Hangar otherwise sorts newest first
+	#[serde(skip)]
+	pub sort: Option<VersionsSort>,
+	/// The direction to sort in, defaulting to descending
+	#[serde(skip)]
+	pub direction: Option<SortDirection>,
+}
+
+impl HangarRequest for VersionsRequest {
+	type Response = VersionsResponse;
+	const CACHEABLE: bool = true;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/projects/{}/versions", encode_segment(&self.slug))
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		query_with_sort(self, self.sort, self.direction)
+	}
+}
+
+impl PaginatedRequest for VersionsRequest {
+	type Item = Version;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(item.name.clone())
+	}
+}
+
+pub type VersionsResponse = Paginated<Version>;
+
+impl Paginated<Version> {
+	/// Iterates over the versions which are available to everyone, see `Visibility::is_available`.
+	pub fn available(&self) -> impl Iterator<Item = &Version> {
+		self.result.iter().filter(|version| version.is_available())
+	}
+
+	/// Orders the versions like a project's page does, with pinned versions first and the rest after them, each
+	/// newest first. See `Version::is_effectively_pinned` for which versions are pinned.
+	pub fn pinned_first(&self) -> Vec<&Version> {
+		let mut versions: Vec<&Version> = self.result.iter().collect();
+		versions.sort_by(|a, b| {
+			b.is_effectively_pinned()
+				.cmp(&a.is_effectively_pinned())
+				.then_with(|| b.created_at.cmp(&a.created_at))
+		});
+		versions
+	}
+
+	/// Gets the versions published after a time, e.g. when an update checker last looked, in the order Hangar sent
+	/// them. Versions published at exactly that time were already there, so they're left out.
+	/// Only this page is checked. In Hangar's default newest first order, if every version on it is newer, the
+	/// next page may have more.
+	pub fn published_after(&self, time: OffsetDateTime) -> Vec<&Version> {
+		self.result
+			.iter()
+			.filter(|version| version.created_at > time)
+			.collect()
+	}
+}
+
+/// Returns a specific version of a project. Requires the `view_public_info` permission in the project or owning organization.
+#[derive(Debug, Serialize, TypedBuilder)]
+pub struct VersionRequest {
+	/// The slug of the project to return the version for
+	#[serde(skip)]
+	pub slug: String,
+	/// The name of the version to return
+	#[serde(skip)]
+	pub name: String,
+}
+
+impl VersionRequest {
+	/// Creates a request for the version of a project with the given name.
+	pub fn new(slug: impl Into<String>, name: impl Into<String>) -> Self {
+		Self {
+			slug: slug.into(),
+			name: name.into(),
+		}
+	}
+}
+
+impl HangarRequest for VersionRequest {
+	type Response = Version;
+	const CACHEABLE: bool = true;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!(
+			"/projects/{}/versions/{}",
+			encode_segment(&self.slug),
+			encode_segment(&self.name)
+		)
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+}
+
+/// Returns the review history of a version of a project, oldest first. Requires the `reviewer` permission.
+/// This endpoint isn't part of Hangar's documented api, so its path and response are assumed from how Hangar lists
+/// versions and may not match what a Hangar instance serves.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct VersionReviewRequest {
+	/// The slug of the project the version belongs to
+	#[serde(skip)]
+	pub slug: String,
+	/// The name of the version to return the reviews of
+	#[serde(skip)]
+	pub name: String,
+}
+
+impl HangarRequest for VersionReviewRequest {
+	type Response = ReviewHistory;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::Reviewer);
+
+	fn path(&self) -> String {
+		format!(
+			"/projects/{}/versions/{}/reviews",
+			encode_segment(&self.slug),
+			encode_segment(&self.name)
+		)
+	}
+}
+
+/// Downloads a version of a project for a specific platform. Requires the `view_public_info` permission in the project or owning organization.
+/// External downloads are redirected to their external url.
+#[derive(Debug, Serialize, TypedBuilder)]
+pub struct VersionDownloadRequest {
+	/// The slug of the project the version belongs to
+	#[serde(skip)]
+	pub slug: String,
+	/// The name of the version to download
+	#[serde(skip)]
+	pub name: String,
+	/// The platform to download the version for
+	#[serde(skip)]
+	pub platform: Platform,
+}
+
+impl HangarRequest for VersionDownloadRequest {
+	type Response = Vec<u8>;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!(
+			"/projects/{}/versions/{}/{}/download",
+			encode_segment(&self.slug),
+			encode_segment(&self.name),
+			self.platform.to_string().to_uppercase()
+		)
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	/// Downloads are returned as the raw file.
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		Ok(body.to_vec())
+	}
+}
+
+/// Returns the members of a project. Requires the `view_public_info` permission in the project or owning organization.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct MembersRequest {
+	/// The slug of the project to return members for
+	#[serde(skip)]
+	pub slug: String,
+	#[serde(flatten)]
+	pub pagination: Pagination,
+}
+
+impl HangarRequest for MembersRequest {
+	type Response = MembersResponse;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/projects/{}/members", encode_segment(&self.slug))
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
+}
+
+impl PaginatedRequest for MembersRequest {
+	type Item = ProjectMember;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(item.user.clone())
+	}
+}
+
+pub type MembersResponse = Paginated<ProjectMember>;
+
+/// Returns the users who have starred a project. Requires the `view_public_info` permission in the project or owning organization.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct ProjectStargazersRequest {
+	/// The slug of the project to return stargazers for
+	#[serde(skip)]
+	pub slug: String,
+	#[serde(flatten)]
+	pub pagination: Pagination,
+}
+
+impl HangarRequest for ProjectStargazersRequest {
+	type Response = UsersResponse;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/projects/{}/stargazers", encode_segment(&self.slug))
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
+}
+
+impl PaginatedRequest for ProjectStargazersRequest {
+	type Item = User;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(item.name.clone())
+	}
+}
+
+/// Returns the users who are watching a project, who get notified of new versions. Requires the `view_public_info`
+/// permission in the project or owning organization.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct ProjectWatchersRequest {
+	/// The slug of the project to return watchers for
+	#[serde(skip)]
+	pub slug: String,
+	#[serde(flatten)]
+	pub pagination: Pagination,
+}
+
+impl HangarRequest for ProjectWatchersRequest {
+	type Response = UsersResponse;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/projects/{}/watchers", encode_segment(&self.slug))
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
+}
+
+impl PaginatedRequest for ProjectWatchersRequest {
+	type Item = User;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(item.name.clone())
+	}
+}
+
+/// Returns info on a specific user. Requires the `view_public_info` permission.
+/// The name is percent-encoded, so any characters can be used.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct UserRequest {
+	/// The name of the user to return
+	#[serde(skip)]
+	pub name: String,
+}
+
+impl HangarRequest for UserRequest {
+	type Response = User;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/users/{}", encode_segment(&self.name))
+	}
+}
+
+/// Searches all the users on Hangar. Requires the `view_public_info` permission.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+pub struct UsersRequest {
+	/// The search query, or all users if not set
+	pub query: Option<String>,
+	/// Used to sort the result
+	#[serde(skip)]
+	pub sort: Option<UsersSort>,
+	/// The direction to sort in, defaulting to ascending
+	#[serde(skip)]
+	pub direction: Option<SortDirection>,
+	/// Pagination information
+	#[builder(!default)]
+	#[serde(flatten)]
+	pub pagination: Pagination,
+}
+
+impl HangarRequest for UsersRequest {
+	type Response = UsersResponse;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		"/users".to_string()
+	}
+
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		query_with_sort(self, self.sort, self.direction)
+	}
+}
+
+impl PaginatedRequest for UsersRequest {
+	type Item = User;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(item.name.clone())
+	}
+}
+
+pub type UsersResponse = Paginated<User>;
+
+/// Searches the users on Hangar who have published at least one project. Requires the `view_public_info` permission.
+/// See the `authors` example for fetching every author with `paginate`.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+pub struct AuthorsRequest {
+	/// The search query, or all authors if not set
+	pub query: Option<String>,
+	/// Used to sort the result
+	#[serde(skip)]
+	pub sort: Option<UsersSort>,
+	/// The direction to sort in, defaulting to ascending
+	#[serde(skip)]
+	pub direction: Option<SortDirection>,
+	/// Pagination information
+	#[builder(!default)]
+	#[serde(flatten)]
+	pub pagination: Pagination,
+}
+
+impl HangarRequest for AuthorsRequest {
+	type Response = UsersResponse;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		"/authors".to_string()
+	}
+
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		query_with_sort(self, self.sort, self.direction)
+	}
+}
+
+impl PaginatedRequest for AuthorsRequest {
+	type Item = User;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(item.name.clone())
+	}
+}
+
+/// Searches the staff members of Hangar. Requires the `view_public_info` permission.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+pub struct StaffRequest {
+	/// The search query, or all staff members if not set
+	pub query: Option<String>,
+	/// Used to sort the result
+	#[serde(skip)]
+	pub sort: Option<UsersSort>,
+	/// The direction to sort in, defaulting to ascending
+	#[serde(skip)]
+	pub direction: Option<SortDirection>,
+	/// Pagination information
+	#[builder(!default)]
+	#[serde(flatten)]
+	pub pagination: Pagination,
+}
+
+impl HangarRequest for StaffRequest {
+	type Response = UsersResponse;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		"/staff".to_string()
+	}
+
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		query_with_sort(self, self.sort, self.direction)
+	}
+}
+
+impl PaginatedRequest for StaffRequest {
+	type Item = User;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(item.name.clone())
+	}
+}
+
+/// Returns the projects a user has starred. Requires the `view_public_info` permission.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+pub struct UserStarredRequest {
+	/// The name of the user to return starred projects for
+	#[builder(!default)]
+	#[serde(skip)]
+	pub user: String,
+	/// Used to sort the result
+	#[serde(skip)]
+	pub sort: Option<ProjectsSort>,
+	/// The direction to sort in, defaulting to descending for everything but `ProjectsSort::Slug`
+	#[serde(skip)]
+	pub direction: Option<SortDirection>,
+	/// Pagination information
+	#[builder(!default)]
+	#[serde(flatten)]
+	pub pagination: Pagination,
+}
+
+impl HangarRequest for UserStarredRequest {
+	type Response = CompactProjectsResponse;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/users/{}/starred", encode_segment(&self.user))
+	}
+
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
+
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		query_with_sort(self, self.sort, self.direction)
+	}
+}
+
+impl PaginatedRequest for UserStarredRequest {
+	type Item = ProjectCompact;
+
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(project_key(item.id, &item.namespace))
+	}
+}
+
+/// Returns the projects a user is watching. Requires the `view_public_info` permission.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+pub struct UserWatchingRequest {
+	/// The name of the user to return watched projects for
+	#[builder(!default)]
+	#[serde(skip)]
+	pub user: String,
+	/// Used to sort the result
+	#[serde(skip)]
+	pub sort: Option<ProjectsSort>,
+	/// The direction to sort in, defaulting to descending for everything but `ProjectsSort::Slug`
+	#[serde(skip)]
+	pub direction: Option<SortDirection>,
+	/// Pagination information
+	#[builder(!default)]
+	#[serde(flatten)]
+	pub pagination: Pagination,
+}
+
+impl HangarRequest for UserWatchingRequest {
+	type Response = CompactProjectsResponse;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/users/{}/watching", encode_segment(&self.user))
+	}
 
-/// base url for normal api calls
-const BASE_API_URL: &str = "https://hangar.papermc.io/api/v1";
+	fn validate(&self) -> Result<()> {
+		Ok(self.pagination.validate()?)
+	}
 
-/// Trait implemented on all request structs.
-pub trait HangarRequest {
-	/// Gets the URL this request should be sent to.
-	fn url(&self) -> String;
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		query_with_sort(self, self.sort, self.direction)
+	}
 }
 
-/// Searches all the projects on Hangar, or for a single user. Requires the `view_public_info` permission.
-#[derive(Debug, Default, Serialize, TypedBuilder)]
-#[serde(rename_all = "camelCase")]
-#[builder(field_defaults(default, setter(into)))]
-pub struct ProjectsRequest {
-	/// Whether to prioritize the project with an exact name match if present
-	prioritize_exact_match: Option<bool>,
-	/// Pagination information
-	#[builder(!default)]
-	#[serde(flatten)]
-	pagination: Pagination,
-	/// Used to sort the result
-	sort: Option<ProjectsSort>,
-	/// A category to filter for
-	category: Option<Category>,
-	/// A platform to filter for
-	platform: Option<Platform>,
-	/// The author of the project
-	owner: Option<String>,
-	/// The query to use when searching
-	query: Option<String>,
-	/// A license to filter for
-	license: Option<String>,
-	/// A platform version to filter for
-	version: Option<String>,
-	/// A tag to filter for
-	tag: Option<String>,
-	/// The member of the project
-	member: Option<String>,
-}
+impl PaginatedRequest for UserWatchingRequest {
+	type Item = ProjectCompact;
 
-impl HangarRequest for ProjectsRequest {
-	fn url(&self) -> String {
-		concat!(BASE_API_URL, "/projects").to_string()
+	fn pagination_mut(&mut self) -> &mut Pagination {
+		&mut self.pagination
+	}
+
+	fn into_page(response: Self::Response) -> (PaginationResponse, Vec<Self::Item>) {
+		(response.pagination, response.result)
+	}
+
+	fn item_key(item: &Self::Item) -> Option<String> {
+		Some(project_key(item.id, &item.namespace))
 	}
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ProjectsResponse {
-	pub pagination: PaginationResponse,
-	pub result: Vec<Project>,
+/// Returns the projects a user has pinned to their profile, in the order they're shown. Requires the `view_public_info` permission.
+/// Unlike the other user lists this isn't paginated.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct UserPinnedRequest {
+	/// The name of the user to return pinned projects for
+	#[serde(skip)]
+	pub user: String,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct PaginationResponse {
-	/// The maximum amount of items to return
-	pub limit: i64,
-	/// Where to start searching
-	pub offset: i64,
-	/// The total number of records
-	pub count: i64,
+impl HangarRequest for UserPinnedRequest {
+	type Response = Vec<ProjectCompact>;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/users/{}/pinned", encode_segment(&self.user))
+	}
 }
 
-/// Returns info on a specific project. Requires the `view_public_info` permission.
+/// A page of compact projects, shared by the starred and watching endpoints.
+pub type CompactProjectsResponse = Paginated<ProjectCompact>;
+
+/// Returns the name of the latest version of a project in its default release channel. Requires the `view_public_info` permission in the project or owning organization.
 #[derive(Debug, Serialize, TypedBuilder)]
 #[builder(field_defaults(setter(into)))]
-pub struct ProjectRequest {
-	/// The slug of the project to return
+pub struct LatestReleaseRequest {
+	/// The slug of the project to return the latest release for
 	#[serde(skip)]
 	pub slug: String,
 }
 
-impl HangarRequest for ProjectRequest {
-	fn url(&self) -> String {
-		format!("{}/projects/{}", BASE_API_URL, self.slug)
+impl LatestReleaseRequest {
+	/// Creates a request for the full version returned by this request, which only gives its name.
+	pub fn version_request(&self, name: impl Into<String>) -> VersionRequest {
+		VersionRequest::new(self.slug.clone(), name)
+	}
+}
+
+impl HangarRequest for LatestReleaseRequest {
+	type Response = String;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/projects/{}/latestrelease", encode_segment(&self.slug))
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	/// The version name is returned as plain text rather than json.
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		parse_text(body)
 	}
 }
 
-/// Returns a page of a project. Requires visibility of the page.
+/// Returns the name of the latest version of a project in a specific channel. Requires the `view_public_info` permission in the project or owning organization.
 #[derive(Debug, Serialize, TypedBuilder)]
 #[builder(field_defaults(setter(into)))]
-pub struct PageRequest {
-	/// The slug of the project to return the page for
+pub struct LatestVersionRequest {
+	/// The slug of the project to return the latest version for
 	#[serde(skip)]
 	pub slug: String,
-	/// The path of the page
-	pub path: String,
+	/// The name of the channel to return the latest version for
+	pub channel: String,
 }
 
-impl HangarRequest for PageRequest {
-	fn url(&self) -> String {
-		format!("{}/pages/page/{}", BASE_API_URL, self.slug)
+impl LatestVersionRequest {
+	/// Creates a request for the full version returned by this request, which only gives its name.
+	pub fn version_request(&self, name: impl Into<String>) -> VersionRequest {
+		VersionRequest::new(self.slug.clone(), name)
 	}
 }
 
-/// Returns all versions of a project. Requires the `view_public_info` permission in the project or owning organization.
-#[derive(Debug, Serialize, TypedBuilder)]
+impl HangarRequest for LatestVersionRequest {
+	type Response = String;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::ViewPublicInfo);
+
+	fn path(&self) -> String {
+		format!("/projects/{}/latest", encode_segment(&self.slug))
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	/// The version name is returned as plain text rather than json.
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		parse_text(body)
+	}
+}
+
+/// Returns the permissions the authenticated user has, either globally or in a project or organization.
+#[derive(Debug, Default, Serialize, TypedBuilder)]
 #[builder(field_defaults(default, setter(into)))]
+pub struct PermissionsRequest {
+	/// The slug of the project to check permissions in, or global permissions if neither this nor the organization
+	/// is set
+	pub project: Option<String>,
+	/// The name of the organization to check permissions in
+	pub organization: Option<String>,
+}
+
+impl HangarRequest for PermissionsRequest {
+	type Response = PermissionsResponse;
+
+	fn path(&self) -> String {
+		"/permissions".to_string()
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct VersionsRequest {
-	/// The slug of the project to return versions for
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct PermissionsResponse {
+	/// What the permissions apply to
+	#[serde(rename = "type")]
+	pub permission_type: PermissionType,
+	/// The permissions the user has
+	pub permissions: Vec<NamedPermission>,
+	/// The permissions as a binary string, with a bit set for each permission
+	pub permission_bin_string: String,
+}
+
+/// Checks whether the authenticated user has all of the given permissions, either globally or in a project or
+/// organization.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+pub struct HasAllPermissionsRequest {
+	/// The permissions to check for
+	#[builder(!default)]
+	pub permissions: Vec<NamedPermission>,
+	/// The slug of the project to check permissions in, or global permissions if neither this nor the organization
+	/// is set
+	pub project: Option<String>,
+	/// The name of the organization to check permissions in
+	pub organization: Option<String>,
+}
+
+impl HangarRequest for HasAllPermissionsRequest {
+	type Response = PermissionCheck;
+
+	fn path(&self) -> String {
+		"/permissions/hasAll".to_string()
+	}
+}
+
+/// Checks whether the authenticated user has any of the given permissions, either globally or in a project or
+/// organization.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(default, setter(into)))]
+pub struct HasAnyPermissionsRequest {
+	/// The permissions to check for
 	#[builder(!default)]
+	pub permissions: Vec<NamedPermission>,
+	/// The slug of the project to check permissions in, or global permissions if neither this nor the organization
+	/// is set
+	pub project: Option<String>,
+	/// The name of the organization to check permissions in
+	pub organization: Option<String>,
+}
+
+impl HangarRequest for HasAnyPermissionsRequest {
+	type Response = PermissionCheck;
+
+	fn path(&self) -> String {
+		"/permissions/hasAny".to_string()
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct PermissionCheck {
+	/// What the permissions were checked in
+	#[serde(rename = "type")]
+	pub permission_type: PermissionType,
+	/// Whether the check passed
+	pub result: bool,
+}
+
+/// Exchanges an api key for a JWT used to authenticate other requests.
+/// Clients do this for you with `authenticate`, storing the token for their following requests.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticateRequest {
+	/// The api key to authenticate with
+	pub api_key: ApiKey,
+}
+
+impl HangarRequest for AuthenticateRequest {
+	type Response = AuthenticateResponse;
+	const METHOD: HttpMethod = HttpMethod::Post;
+
+	fn path(&self) -> String {
+		"/authenticate".to_string()
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct AuthenticateResponse {
+	/// The JWT to send with requests
+	pub token: JwtToken,
+	/// Seconds until the token expires
+	pub expires_in: i64,
+}
+
+/// Returns the api keys of the authenticated user. Requires the `edit_api_keys` permission.
+#[derive(Debug, Default, Serialize)]
+pub struct KeysRequest {}
+
+impl HangarRequest for KeysRequest {
+	type Response = Vec<ApiKeyInfo>;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::EditApiKeys);
+
+	fn path(&self) -> String {
+		"/keys".to_string()
+	}
+}
+
+/// Creates an api key for the authenticated user. Requires the `edit_api_keys` permission.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct CreateKeyRequest {
+	/// The name of the key
+	pub name: String,
+	/// The permissions the key has, which must be a subset of the user's permissions
+	pub permissions: Vec<NamedPermission>,
+}
+
+impl HangarRequest for CreateKeyRequest {
+	type Response = CreatedApiKey;
+	const METHOD: HttpMethod = HttpMethod::Post;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::EditApiKeys);
+
+	fn path(&self) -> String {
+		"/keys".to_string()
+	}
+
+	/// The key is sent as a json body rather than query parameters.
+	fn query(&self) -> Result<Vec<(String, String)>> {
+		Ok(Vec::new())
+	}
+
+	fn body(&self) -> Option<Vec<u8>> {
+		serde_json::to_vec(self).ok()
+	}
+
+	/// The new key is returned as plain text rather than json.
+	fn parse_response(body: &[u8]) -> Result<Self::Response> {
+		Ok(CreatedApiKey {
+			key: ApiKey::new(parse_text(body)?),
+		})
+	}
+}
+
+/// A newly created api key.
+/// This is the only time Hangar gives out the full key, so it must be saved now. Listing keys later only gives
+/// their identifiers.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct CreatedApiKey {
+	/// The full api key
+	pub key: ApiKey,
+}
+
+/// Deletes an api key of the authenticated user. Requires the `edit_api_keys` permission.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct DeleteKeyRequest {
+	/// The name of the key to delete
+	pub name: String,
+}
+
+impl HangarRequest for DeleteKeyRequest {
+	type Response = ();
+	const METHOD: HttpMethod = HttpMethod::Delete;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::EditApiKeys);
+
+	fn path(&self) -> String {
+		"/keys".to_string()
+	}
+
+	/// Nothing is returned on success.
+	fn parse_response(_body: &[u8]) -> Result<Self::Response> {
+		Ok(())
+	}
+}
+
+/// Returns the daily views and downloads of a project within a date range. Requires the `is_subject_member`
+/// permission in the project or owning organization.
+#[derive(Debug, Serialize, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatsRequest {
+	/// The slug of the project to return stats for
 	#[serde(skip)]
 	pub slug: String,
-	#[builder(!default)]
-	#[serde(flatten)]
-	pub pagination: Pagination,
-	/// Whether to include hidden-by-default channels in the result, defaults to try
-	pub include_hidden_channels: Option<bool>,
-	/// A name of a version channel to filter for
-	pub channel: Option<String>,
-	/// A platform name to filter for
-	pub platform: Option<Platform>,
-	/// A platform version to filter for
-	pub platform_version: Option<String>,
+	/// The first day to return stats for
+	#[serde(serialize_with = "serialize_date")]
+	pub from_date: Date,
+	/// The last day to return stats for
+	#[serde(serialize_with = "serialize_date")]
+	pub to_date: Date,
 }
 
-impl HangarRequest for VersionsRequest {
-	fn url(&self) -> String {
-		format!("{}/projects/{}/versions", BASE_API_URL, self.slug)
+impl ProjectStatsRequest {
+	/// Creates a request for the stats of a project, failing if the date range is out of order.
+	/// Requests made with the builder are checked the same way when they're sent.
+	pub fn new(slug: impl Into<String>, from_date: Date, to_date: Date) -> Result<Self> {
+		validate_date_range(from_date, to_date)?;
+		Ok(Self {
+			slug: slug.into(),
+			from_date,
+			to_date,
+		})
 	}
 }
 
-#[derive(Debug, Deserialize)]
-pub struct VersionsResponse {
-	pub pagination: PaginationResponse,
-	pub result: Vec<Version>,
+impl HangarRequest for ProjectStatsRequest {
+	type Response = BTreeMap<Date, DayProjectStats>;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::IsSubjectMember);
+
+	fn path(&self) -> String {
+		format!("/projects/{}/stats", encode_segment(&self.slug))
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	fn validate(&self) -> Result<()> {
+		validate_date_range(self.from_date, self.to_date)
+	}
 }
 
-/// Returns a specific version of a project. Requires the `view_public_info` permission in the project or owning organization.
+/// Returns the daily downloads of a version on each platform within a date range. Requires the `is_subject_member`
+/// permission in the project or owning organization.
 #[derive(Debug, Serialize, TypedBuilder)]
-pub struct VersionRequest {
-	/// The slug of the project to return the version for
+#[builder(field_defaults(setter(into)))]
+#[serde(rename_all = "camelCase")]
+pub struct VersionStatsRequest {
+	/// The slug of the project the version belongs to
 	#[serde(skip)]
 	pub slug: String,
-	/// The name of the version to return
+	/// The name of the version to return stats for
 	#[serde(skip)]
 	pub name: String,
+	/// The first day to return stats for
+	#[serde(serialize_with = "serialize_date")]
+	pub from_date: Date,
+	/// The last day to return stats for
+	#[serde(serialize_with = "serialize_date")]
+	pub to_date: Date,
 }
 
-impl HangarRequest for VersionRequest {
-	fn url(&self) -> String {
+impl VersionStatsRequest {
+	/// Creates a request for the stats of a version, failing if the date range is out of order.
+	/// Requests made with the builder are checked the same way when they're sent.
+	pub fn new(
+		slug: impl Into<String>,
+		name: impl Into<String>,
+		from_date: Date,
+		to_date: Date,
+	) -> Result<Self> {
+		validate_date_range(from_date, to_date)?;
+		Ok(Self {
+			slug: slug.into(),
+			name: name.into(),
+			from_date,
+			to_date,
+		})
+	}
+}
+
+impl HangarRequest for VersionStatsRequest {
+	type Response = BTreeMap<Date, DayVersionStats>;
+	const REQUIRED_PERMISSION: Option<NamedPermission> = Some(NamedPermission::IsSubjectMember);
+
+	fn path(&self) -> String {
 		format!(
-			"{}/projects/{}/versions/{}",
-			BASE_API_URL, self.slug, self.name
+			"/projects/{}/versions/{}/stats",
+			encode_segment(&self.slug),
+			encode_segment(&self.name)
+		)
+	}
+
+	fn permission_project(&self) -> Option<&str> {
+		Some(&self.slug)
+	}
+
+	fn validate(&self) -> Result<()> {
+		validate_date_range(self.from_date, self.to_date)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn requests_declare_the_permissions_they_need() {
+		assert_eq!(
+			ProjectRequest::REQUIRED_PERMISSION,
+			Some(NamedPermission::ViewPublicInfo)
+		);
+		assert_eq!(
+			VersionReviewRequest::REQUIRED_PERMISSION,
+			Some(NamedPermission::Reviewer)
+		);
+		assert_eq!(
+			KeysRequest::REQUIRED_PERMISSION,
+			Some(NamedPermission::EditApiKeys)
+		);
+		assert_eq!(
+			ProjectStatsRequest::REQUIRED_PERMISSION,
+			Some(NamedPermission::IsSubjectMember)
+		);
+		assert_eq!(PermissionsRequest::REQUIRED_PERMISSION, None);
+		assert_eq!(AuthenticateRequest::REQUIRED_PERMISSION, None);
+	}
+
+	#[test]
+	fn project_permissions_are_checked_in_the_project() {
+		let day = day(1);
+		let stats = ProjectStatsRequest::new("Maintenance", day, day).unwrap();
+		assert_eq!(stats.permission_project(), Some("Maintenance"));
+		assert_eq!(KeysRequest::default().permission_project(), None);
+	}
+
+	#[test]
+	fn text_responses_are_kept_byte_for_byte() {
+		let page = MainPageRequest::parse_response(b"# Title\r\n\r\nBody\n").unwrap();
+		assert_eq!(page.contents, "# Title\r\n\r\nBody\n");
+	}
+
+	#[test]
+	fn invalid_utf8_text_is_a_deserialize_error() {
+		let err = MainPageRequest::parse_response(b"caf\xe9").unwrap_err();
+		assert!(matches!(err, Error::Deserialize { .. }), "{err:?}");
+	}
+
+	fn day(day: u8) -> Date {
+		Date::from_calendar_date(2024, time::Month::June, day).unwrap()
+	}
+
+	#[test]
+	fn project_stats_dates_are_sent_as_the_start_of_the_day() {
+		let request = ProjectStatsRequest::new("Test", day(1), day(30)).unwrap();
+		assert_eq!(
+			request.query().unwrap(),
+			[
+				("fromDate".to_string(), "2024-06-01T00:00:00Z".to_string()),
+				("toDate".to_string(), "2024-06-30T00:00:00Z".to_string()),
+			]
+		);
+		assert_eq!(request.path(), "/projects/Test/stats");
+	}
+
+	#[test]
+	fn queries_which_cant_be_serialized_are_invalid_requests() {
+		// rfc3339 only has four digit years
+		let request = ProjectStatsRequest::new("Test", Date::MIN, Date::MIN).unwrap();
+		for err in [
+			request.query().unwrap_err(),
+			request.to_parts(BASE_API_URL).unwrap_err(),
+			request.to_curl(BASE_API_URL, None).unwrap_err(),
+		] {
+			match err {
+				Error::InvalidRequest(message) => {
+					assert!(message.contains("couldn't be serialized"), "{message}")
+				}
+				err => panic!("expected InvalidRequest, got {err:?}"),
+			}
+		}
+	}
+
+	#[test]
+	fn project_stats_date_ranges_must_be_in_order() {
+		assert!(ProjectStatsRequest::new("Test", day(1), day(1)).is_ok());
+		assert!(matches!(
+			ProjectStatsRequest::new("Test", day(2), day(1)),
+			Err(Error::InvalidRequest(_))
+		));
+		let request = ProjectStatsRequest::builder()
+			.slug("Test")
+			.from_date(day(2))
+			.to_date(day(1))
+			.build();
+		assert!(request.validate().is_err());
+	}
+
+	#[test]
+	fn users_requests_combine_the_sort_and_direction() {
+		let request = UsersRequest::builder()
+			.query("kenny".to_string())
+			.sort(UsersSort::ProjectCount)
+			.direction(SortDirection::Descending)
+			.pagination(Pagination::default())
+			.build();
+		let mut query = request.query().unwrap();
+		query.sort();
+		assert_eq!(
+			query,
+			[
+				("limit".to_string(), "25".to_string()),
+				("offset".to_string(), "0".to_string()),
+				("query".to_string(), "kenny".to_string()),
+				("sort".to_string(), "-projectCount".to_string()),
+			]
+		);
+		let request = UsersRequest::builder()
+			.sort(UsersSort::Name)
+			.pagination(Pagination::default())
+			.build();
+		assert!(request
+			.query()
+			.unwrap()
+			.contains(&("sort".to_string(), "name".to_string())));
+		assert!(!request
+			.query()
+			.unwrap()
+			.iter()
+			.any(|(name, _)| name == "query"));
+	}
+
+	fn versions_sort(sort: Option<VersionsSort>, direction: Option<SortDirection>) -> Vec<String> {
+		let request = VersionsRequest::builder()
+			.slug("Maintenance")
+			.pagination(Pagination::default())
+			.sort(sort)
+			.direction(direction)
+			.build();
+		request
+			.query()
+			.unwrap()
+			.into_iter()
+			.filter(|(key, _)| key == "sort")
+			.map(|(_, value)| value)
+			.collect()
+	}
+
+	#[test]
+	fn versions_requests_combine_the_sort_and_direction() {
+		assert!(versions_sort(None, None).is_empty());
+		assert!(versions_sort(None, Some(SortDirection::Ascending)).is_empty());
+		assert_eq!(
+			versions_sort(Some(VersionsSort::CreatedAt), None),
+			["-createdAt"]
+		);
+		assert_eq!(
+			versions_sort(
+				Some(VersionsSort::CreatedAt),
+				Some(SortDirection::Descending)
+			),
+			["-createdAt"]
+		);
+		assert_eq!(
+			versions_sort(
+				Some(VersionsSort::CreatedAt),
+				Some(SortDirection::Ascending)
+			),
+			["createdAt"]
+		);
+	}
+
+	#[test]
+	fn versions_sort_is_sent_alongside_the_filters() {
+		let request = VersionsRequest::builder()
+			.slug("Maintenance")
+			.pagination(Pagination::default())
+			.channel("Release")
+			.platform(Platform::Paper)
+			.sort(VersionsSort::CreatedAt)
+			.direction(SortDirection::Ascending)
+			.build();
+		let mut query = request.query().unwrap();
+		query.sort();
+		assert_eq!(
+			query,
+			[
+				("channel".to_string(), "Release".to_string()),
+				("limit".to_string(), "25".to_string()),
+				("offset".to_string(), "0".to_string()),
+				("platform".to_string(), "PAPER".to_string()),
+				("sort".to_string(), "createdAt".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn projects_are_told_apart_by_id_across_pages() {
+		let mut project: Project =
+			serde_json::from_str(include_str!("../tests/fixtures/project.json")).unwrap();
+		let key = ProjectsRequest::item_key(&project);
+		project.namespace.slug = "Renamed".into();
+		assert_eq!(ProjectsRequest::item_key(&project), key);
+		project.id = None;
+		assert_eq!(
+			ProjectsRequest::item_key(&project).as_deref(),
+			Some("kennytv/Renamed")
+		);
+	}
+
+	#[test]
+	fn parts_percent_encode_the_path_and_query() {
+		let request = ProjectsRequest::builder()
+			.query("motd & icons/ü".to_string())
+			.pagination(Pagination::default())
+			.build();
+		let parts = request.to_parts(BASE_API_URL).unwrap();
+		assert_eq!(parts.method, HttpMethod::Get);
+		assert!(parts.body.is_none());
+		assert_eq!(
+			parts.full_url(),
+			"https://hangar.papermc.io/api/v1/projects?limit=25&offset=0&query=motd%20%26%20icons%2F%C3%BC"
+		);
+		let parts = VersionRequest::new("Some Project", "1.0/beta")
+			.to_parts(BASE_API_URL)
+			.unwrap();
+		assert_eq!(
+			parts.url,
+			"https://hangar.papermc.io/api/v1/projects/Some%20Project/versions/1.0%2Fbeta"
+		);
+	}
+
+	#[test]
+	fn parts_with_bodies_say_they_are_json() {
+		let request = CreateKeyRequest::builder()
+			.name("ci")
+			.permissions(vec![NamedPermission::CreateVersion])
+			.build();
+		let parts = request.to_parts(BASE_API_URL).unwrap();
+		assert_eq!(parts.method, HttpMethod::Post);
+		assert!(parts.query.is_empty());
+		assert_eq!(
+			parts.headers,
+			[("Content-Type".to_string(), "application/json".to_string())]
+		);
+		assert_eq!(
+			parts.body.as_deref(),
+			Some(&br#"{"name":"ci","permissions":["create_version"]}"#[..])
+		);
+	}
+
+	#[test]
+	fn decoding_error_statuses_gives_the_clients_errors() {
+		assert!(matches!(
+			decode_response::<ProjectRequest>(304, b""),
+			Err(Error::NotModified)
+		));
+		assert!(matches!(
+			decode_response::<ProjectRequest>(429, b""),
+			Err(Error::RateLimited { retry_after: None })
+		));
+		assert!(matches!(
+			decode_response::<ProjectRequest>(404, b""),
+			Err(Error::NotFound)
+		));
+		assert_eq!(
+			decode_response::<LatestReleaseRequest>(200, b"4.2.1").unwrap(),
+			"4.2.1"
+		);
+	}
+
+	fn sorted_query(request: &impl HangarRequest) -> Vec<(String, String)> {
+		let mut query = request.query().unwrap();
+		query.sort();
+		query
+	}
+
+	fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+		pairs
+			.iter()
+			.map(|(name, value)| (name.to_string(), value.to_string()))
+			.collect()
+	}
+
+	#[test]
+	fn search_presets_send_their_sort_and_filters() {
+		assert_eq!(
+			sorted_query(&ProjectsRequest::top_downloads(Category::Chat, 5)),
+			pairs(&[
+				("category", "chat"),
+				("limit", "5"),
+				("offset", "0"),
+				("sort", "-downloads"),
+			])
+		);
+		assert_eq!(
+			sorted_query(&ProjectsRequest::recently_updated(
+				Platform::Velocity,
+				"3.3",
+				10
+			)),
+			pairs(&[
+				("limit", "10"),
+				("offset", "0"),
+				("platform", "VELOCITY"),
+				("sort", "-updated"),
+				("version", "3.3"),
+			])
+		);
+		assert_eq!(
+			sorted_query(&ProjectsRequest::by_owner("kennytv")),
+			pairs(&[
+				("limit", "25"),
+				("offset", "0"),
+				("owner", "kennytv"),
+				("sort", "slug"),
+			])
+		);
+	}
+
+	#[test]
+	fn search_presets_can_be_adjusted() {
+		let request = ProjectsRequest::top_downloads(Category::AdminTools, 10).with(|request| {
+			request.direction = Some(SortDirection::Ascending);
+			request.platforms.push(Platform::Paper);
+			request.pagination = Pagination::page(2, 10);
+		});
+		assert_eq!(
+			sorted_query(&request),
+			pairs(&[
+				("category", "admin_tools"),
+				("limit", "10"),
+				("offset", "20"),
+				("platform", "PAPER"),
+				("sort", "downloads"),
+			])
+		);
+	}
+
+	#[test]
+	fn search_presets_are_validated_like_other_requests() {
+		assert!(ProjectsRequest::by_owner("kennytv").validate().is_ok());
+		assert!(ProjectsRequest::top_downloads(Category::Chat, 25)
+			.validate()
+			.is_ok());
+		assert!(ProjectsRequest::top_downloads(Category::Chat, 26)
+			.validate()
+			.is_err());
+		assert!(
+			ProjectsRequest::recently_updated(Platform::Paper, "1.21", 0)
+				.validate()
+				.is_err()
+		);
+	}
+
+	#[test]
+	fn starred_projects_are_listed_by_user() {
+		let request = UserStarredRequest::builder()
+			.user("kenny tv")
+			.sort(ProjectsSort::Stars)
+			.pagination(Pagination::page(1, 10))
+			.build();
+		assert_eq!(request.path(), "/users/kenny%20tv/starred");
+		let mut query = request.query().unwrap();
+		query.sort();
+		assert_eq!(
+			query,
+			[
+				("limit".to_string(), "10".to_string()),
+				("offset".to_string(), "10".to_string()),
+				("sort".to_string(), "-stars".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn watched_projects_are_listed_by_user() {
+		let request = UserWatchingRequest::builder()
+			.user("kennytv")
+			.pagination(Pagination::default())
+			.build();
+		assert_eq!(request.path(), "/users/kennytv/watching");
+		assert!(!request
+			.query()
+			.unwrap()
+			.iter()
+			.any(|(name, _)| name == "sort"));
+	}
+
+	fn page(limit: i64, offset: i64, count: i64) -> PaginationResponse {
+		PaginationResponse {
+			limit,
+			offset,
+			count,
+		}
+	}
+
+	#[test]
+	fn next_pages_advance_the_offset_by_the_limit() {
+		let next = page(25, 0, 60).to_next().unwrap();
+		assert_eq!((next.limit, next.offset), (25, 25));
+		let next = page(25, 25, 60).to_next().unwrap();
+		assert_eq!((next.limit, next.offset), (25, 50));
+		assert!(page(25, 50, 60).to_next().is_none());
+		assert!(page(25, 0, 25).to_next().is_none());
+		assert!(page(0, 0, 10).to_next().is_none());
+	}
+
+	#[test]
+	fn response_pagination_carries_over_to_requests() {
+		let pagination = Pagination::from(&page(10, 30, 100));
+		assert_eq!((pagination.limit, pagination.offset), (10, 30));
+		let response = page(10, 30, 95);
+		assert_eq!(response.current_page(), 3);
+		assert_eq!(response.total_pages(), 10);
+	}
+
+	/// Gets the `limit` and `offset` a request sends.
+	fn pagination_query(request: &impl HangarRequest) -> Vec<(String, String)> {
+		request
+			.query()
+			.unwrap()
+			.into_iter()
+			.filter(|(key, _)| key == "limit" || key == "offset")
+			.collect()
+	}
+
+	#[test]
+	fn every_paginated_request_sends_its_limit_and_offset() {
+		let pagination = || Pagination::page(3, 7);
+		let expected = [
+			("limit".to_string(), "7".to_string()),
+			("offset".to_string(), "21".to_string()),
+		];
+		let queries = [
+			(
+				"projects",
+				pagination_query(&ProjectsRequest::builder().pagination(pagination()).build()),
+			),
+			(
+				"versions",
+				pagination_query(
+					&VersionsRequest::builder()
+						.slug("Maintenance")
+						.pagination(pagination())
+						.build(),
+				),
+			),
+			(
+				"members",
+				pagination_query(
+					&MembersRequest::builder()
+						.slug("Maintenance")
+						.pagination(pagination())
+						.build(),
+				),
+			),
+			(
+				"stargazers",
+				pagination_query(
+					&ProjectStargazersRequest::builder()
+						.slug("Maintenance")
+						.pagination(pagination())
+						.build(),
+				),
+			),
+			(
+				"watchers",
+				pagination_query(
+					&ProjectWatchersRequest::builder()
+						.slug("Maintenance")
+						.pagination(pagination())
+						.build(),
+				),
+			),
+			(
+				"users",
+				pagination_query(&UsersRequest::builder().pagination(pagination()).build()),
+			),
+			(
+				"authors",
+				pagination_query(&AuthorsRequest::builder().pagination(pagination()).build()),
+			),
+			(
+				"staff",
+				pagination_query(&StaffRequest::builder().pagination(pagination()).build()),
+			),
+			(
+				"starred",
+				pagination_query(
+					&UserStarredRequest::builder()
+						.user("kennytv")
+						.pagination(pagination())
+						.build(),
+				),
+			),
+			(
+				"watching",
+				pagination_query(
+					&UserWatchingRequest::builder()
+						.user("kennytv")
+						.pagination(pagination())
+						.build(),
+				),
+			),
+		];
+		for (name, query) in queries {
+			assert_eq!(query, expected, "{name}");
+		}
+	}
+
+	#[test]
+	fn default_pagination_is_sent_too() {
+		let request = ProjectsRequest::default();
+		assert_eq!(
+			pagination_query(&request),
+			[
+				("limit".to_string(), "25".to_string()),
+				("offset".to_string(), "0".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn empty_searches_are_not_missing_pages() {
+		let nothing: ProjectsResponse =
+			serde_json::from_str(r#"{"pagination":{"limit":25,"offset":0,"count":0},"result":[]}"#)
+				.unwrap();
+		assert!(nothing.is_empty());
+		assert!(!nothing.has_matches());
+		let past_the_end: ProjectsResponse = serde_json::from_str(
+			r#"{"pagination":{"limit":25,"offset":50,"count":30},"result":[]}"#,
+		)
+		.unwrap();
+		assert!(past_the_end.is_empty());
+		assert!(past_the_end.has_matches());
+	}
+
+	fn checked_permissions() -> Vec<NamedPermission> {
+		vec![NamedPermission::CreateVersion, NamedPermission::EditVersion]
+	}
+
+	#[test]
+	fn global_permission_checks_repeat_the_permissions() {
+		let all = HasAllPermissionsRequest::builder()
+			.permissions(checked_permissions())
+			.build();
+		let any = HasAnyPermissionsRequest::builder()
+			.permissions(checked_permissions())
+			.build();
+		let expected = pairs(&[
+			("permissions", "create_version"),
+			("permissions", "edit_version"),
+		]);
+		assert_eq!(all.query().unwrap(), expected);
+		assert_eq!(any.query().unwrap(), expected);
+		assert_eq!(all.path(), "/permissions/hasAll");
+		assert_eq!(any.path(), "/permissions/hasAny");
+	}
+
+	#[test]
+	fn scoped_permission_checks_send_the_scope() {
+		let all = HasAllPermissionsRequest::builder()
+			.permissions(checked_permissions())
+			.project("Maintenance".to_string())
+			.build();
+		assert_eq!(
+			sorted_query(&all),
+			pairs(&[
+				("permissions", "create_version"),
+				("permissions", "edit_version"),
+				("project", "Maintenance"),
+			])
+		);
+		let any = HasAnyPermissionsRequest::builder()
+			.permissions(vec![NamedPermission::Unknown(
+				"upload_screenshots".to_string(),
+			)])
+			.organization("PaperMC".to_string())
+			.build();
+		assert_eq!(
+			sorted_query(&any),
+			pairs(&[
+				("organization", "PaperMC"),
+				("permissions", "upload_screenshots"),
+			])
+		);
+		let permissions = PermissionsRequest::builder()
+			.project("Maintenance".to_string())
+			.build();
+		assert_eq!(
+			permissions.query().unwrap(),
+			pairs(&[("project", "Maintenance")])
+		);
+		assert!(PermissionsRequest::default().query().unwrap().is_empty());
+	}
+
+	#[test]
+	fn permission_checks_say_what_they_were_checked_in() {
+		for (body, permission_type, result) in [
+			(
+				r#"{"type":"global","result":true}"#,
+				PermissionType::Global,
+				true,
+			),
+			(
+				r#"{"type":"project","result":false}"#,
+				PermissionType::Project,
+				false,
+			),
+			(
+				r#"{"type":"organization","result":true}"#,
+				PermissionType::Organization,
+				true,
+			),
+			(
+				r#"{"type":"team","result":true}"#,
+				PermissionType::Unknown,
+				true,
+			),
+		] {
+			for check in [
+				HasAllPermissionsRequest::parse_response(body.as_bytes()).unwrap(),
+				HasAnyPermissionsRequest::parse_response(body.as_bytes()).unwrap(),
+			] {
+				assert_eq!(check.permission_type, permission_type, "{body}");
+				assert_eq!(check.result, result, "{body}");
+			}
+		}
+	}
+
+	#[test]
+	fn global_permissions_are_typed() {
+		let permissions = PermissionsRequest::parse_response(
+			br#"{"type":"global","permissions":["view_public_info"],"permissionBinString":"1"}"#,
 		)
+		.unwrap();
+		assert_eq!(permissions.permission_type, PermissionType::Global);
+		assert_eq!(permissions.permissions, [NamedPermission::ViewPublicInfo]);
 	}
 }
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+use crate::api::AuthenticateResponse;
+
+/// An api key, which is exchanged for a JWT to authenticate with Hangar.
+/// The key is kept out of debug output and has no `Display` impl, so use `expose` when it's really needed. With the
+/// `zeroize` feature it's wiped from memory when dropped.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ApiKey(String);
+
+impl ApiKey {
+	/// Wraps an api key.
+	pub fn new(key: impl Into<String>) -> Self {
+		Self(key.into())
+	}
+
+	/// Gets the api key itself.
+	pub fn expose(&self) -> &str {
+		&self.0
+	}
+}
+
+impl std::fmt::Debug for ApiKey {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "ApiKey(****)")
+	}
+}
+
+impl From<String> for ApiKey {
+	fn from(value: String) -> Self {
+		Self(value)
+	}
+}
+
+impl From<&str> for ApiKey {
+	fn from(value: &str) -> Self {
+		Self(value.to_string())
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ApiKey {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(&mut self.0);
+	}
+}
+
+/// A JWT sent with requests to authenticate them.
+/// Like `ApiKey` it's kept out of debug output, has no `Display` impl, and is zeroized on drop with the `zeroize`
+/// feature.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct JwtToken(String);
+
+impl JwtToken {
+	/// Wraps a JWT.
+	pub fn new(token: impl Into<String>) -> Self {
+		Self(token.into())
+	}
+
+	/// Gets the JWT itself.
+	pub fn expose(&self) -> &str {
+		&self.0
+	}
+}
+
+impl std::fmt::Debug for JwtToken {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "JwtToken(****)")
+	}
+}
+
+impl From<String> for JwtToken {
+	fn from(value: String) -> Self {
+		Self(value)
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for JwtToken {
+	fn drop(&mut self) {
+		zeroize::Zeroize::zeroize(&mut self.0);
+	}
+}
+
+/// A JWT used to authenticate requests, obtained by exchanging an api key.
+#[derive(Debug, Clone)]
+pub struct AuthToken {
+	/// The JWT itself
+	pub token: JwtToken,
+	/// When the token expires
+	pub expires_at: OffsetDateTime,
+}
+
+impl AuthToken {
+	/// Whether the token has expired and needs to be replaced by authenticating again.
+	pub fn is_expired(&self) -> bool {
+		self.expires_within(Duration::ZERO)
+	}
+
+	/// Whether the token expires within the given duration from now.
+	pub fn expires_within(&self, duration: Duration) -> bool {
+		self.expires_at <= OffsetDateTime::now_utc() + duration
+	}
+}
+
+impl From<AuthenticateResponse> for AuthToken {
+	fn from(value: AuthenticateResponse) -> Self {
+		Self {
+			token: value.token,
+			expires_at: OffsetDateTime::now_utc() + Duration::seconds(value.expires_in),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn secrets_are_redacted_from_debug_output() {
+		let key = ApiKey::new("9b3e5c1a-0000-4000-8000-000000000000.hangar-secret");
+		let token = AuthToken {
+			token: JwtToken::new("eyJhbGciOiJIUzI1NiJ9.e30.c2lnbmF0dXJl"),
+			expires_at: OffsetDateTime::UNIX_EPOCH,
+		};
+		assert_eq!(format!("{key:?}"), "ApiKey(****)");
+		assert_eq!(format!("{:?}", token.token), "JwtToken(****)");
+		let debug = format!("{token:?}");
+		assert!(debug.contains("JwtToken(****)"), "{debug}");
+		assert!(!debug.contains("eyJhbGciOiJIUzI1NiJ9"), "{debug}");
+	}
+
+	#[test]
+	fn secrets_can_be_exposed() {
+		assert_eq!(ApiKey::from("secret").expose(), "secret");
+		assert_eq!(JwtToken::from("token".to_string()).expose(), "token");
+	}
+
+	#[test]
+	fn api_keys_serialize_as_the_key() {
+		let key = ApiKey::new("secret");
+		assert_eq!(serde_json::to_string(&key).unwrap(), r#""secret""#);
+		assert_eq!(serde_json::from_str::<ApiKey>(r#""secret""#).unwrap(), key);
+	}
+}
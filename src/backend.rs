@@ -0,0 +1,441 @@
+use std::future::Future;
+
+use reqwest::{header::HeaderMap, Method};
+
+use crate::api::SECRET_PARAMS;
+use crate::error::Result;
+
+/// A request to be executed by an `HttpBackend`.
+/// Secrets like api keys and auth tokens are redacted from its debug output.
+#[derive(Clone)]
+pub struct HttpRequest {
+	/// The http method of the request
+	pub method: Method,
+	/// The url of the request, without its query string
+	pub url: String,
+	/// The query parameters of the request
+	pub query: Vec<(String, String)>,
+	/// The headers of the request
+	pub headers: Vec<(String, String)>,
+	/// The body of the request, if any
+	pub body: Option<Vec<u8>>,
+}
+
+/// Headers whose values are secret.
+const SECRET_HEADERS: &[&str] = &["Authorization"];
+
+impl std::fmt::Debug for HttpRequest {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		fn redacted<'a>(
+			pairs: &'a [(String, String)],
+			secrets: &[&str],
+		) -> Vec<(&'a str, &'a str)> {
+			pairs
+				.iter()
+				.map(|(name, value)| {
+					if secrets
+						.iter()
+						.any(|secret| secret.eq_ignore_ascii_case(name))
+					{
+						(name.as_str(), "<redacted>")
+					} else {
+						(name.as_str(), value.as_str())
+					}
+				})
+				.collect()
+		}
+
+		f.debug_struct("HttpRequest")
+			.field("method", &self.method)
+			.field("url", &self.url)
+			.field("query", &redacted(&self.query, SECRET_PARAMS))
+			.field("headers", &redacted(&self.headers, SECRET_HEADERS))
+			.field("body", &self.body)
+			.finish()
+	}
+}
+
+impl HttpRequest {
+	/// Whether the query of this request contains a secret, in which case the url must be kept out of errors.
+	pub(crate) fn has_secret_query(&self) -> bool {
+		self.query
+			.iter()
+			.any(|(name, _)| SECRET_PARAMS.contains(&name.as_str()))
+	}
+
+	/// Gets the url of this request with its query parameters sorted, identifying it regardless of parameter order.
+	pub(crate) fn key(&self) -> String {
+		url_key(&self.url, &self.query)
+	}
+
+	/// Gets the value of a header of this request, matching its name ignoring ascii case.
+	pub(crate) fn header(&self, name: &str) -> Option<&str> {
+		self.headers
+			.iter()
+			.find(|(header, _)| header.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.as_str())
+	}
+
+	/// Gets a key identifying the response this request gets, which along with the url depends on the language
+	/// it asks for and who it's sent as. Credentials are only included as a hash so the key can be stored.
+	pub(crate) fn response_key(&self) -> String {
+		let mut key = self.key();
+		if let Some(language) = self.header("Accept-Language") {
+			key.push_str(" lang=");
+			key.push_str(language);
+		}
+		if let Some(auth) = self.header("Authorization") {
+			key.push_str(&format!(" auth={}", credential_hash(auth)));
+		}
+		key
+	}
+}
+
+/// Hashes a credential for use in keys, so requests sent as different users aren't mixed up without the
+/// credential itself ending up in them.
+pub(crate) fn credential_hash(credential: &str) -> String {
+	format!("{:016x}", stable_hash(credential))
+}
+
+/// Gets a url with sorted query parameters appended, identifying it regardless of parameter order.
+pub(crate) fn url_key(url: &str, query: &[(String, String)]) -> String {
+	let mut query = query.to_vec();
+	query.sort();
+	match reqwest::Url::parse_with_params(url, &query) {
+		Ok(url) => url.to_string(),
+		Err(_) => url.to_string(),
+	}
+}
+
+/// Hashes a string with 64-bit FNV-1a, which unlike the standard library's hasher stays the same between builds,
+/// for naming files after keys.
+pub(crate) fn stable_hash(key: &str) -> u64 {
+	key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+		(hash ^ byte as u64).wrapping_mul(0x100000001b3)
+	})
+}
+
+/// Removes the url from an error if it contains a secret.
+pub(crate) fn redact_url(err: reqwest::Error, secret: bool) -> reqwest::Error {
+	if secret {
+		err.without_url()
+	} else {
+		err
+	}
+}
+
+/// A response returned by an `HttpBackend`.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+	/// The http status code of the response
+	pub status: u16,
+	/// The final url of the response, after any redirects
+	pub url: String,
+	/// The headers of the response
+	pub headers: HeaderMap,
+	/// The body of the response
+	pub body: Vec<u8>,
+}
+
+/// `Send` on native targets. On wasm futures can't be sent between threads, so it's implemented for everything.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send> MaybeSend for T {}
+/// `Send` on native targets. On wasm futures can't be sent between threads, so it's implemented for everything.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSend for T {}
+
+/// `Sync` on native targets, implemented for everything on wasm.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSync: Sync {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Sync> MaybeSync for T {}
+/// `Sync` on native targets, implemented for everything on wasm.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSync {}
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSync for T {}
+
+/// The http layer used by `HangarClient` to execute requests.
+/// Implement this to use your own http client, or to serve canned responses in tests.
+pub trait HttpBackend: MaybeSend + MaybeSync {
+	/// Executes a request, returning its response regardless of status.
+	fn execute(
+		&self,
+		request: HttpRequest,
+	) -> impl Future<Output = Result<HttpResponse>> + MaybeSend;
+
+	/// Executes a request like `execute`, calling `progress` with how many bytes of the body were received so far
+	/// and how many there are in total, if known, as the body comes in.
+	///
+	/// Backends which don't stream bodies can leave this as is, which calls `progress` once the whole body was
+	/// received.
+	fn execute_with_progress<F: FnMut(u64, Option<u64>) + MaybeSend>(
+		&self,
+		request: HttpRequest,
+		mut progress: F,
+	) -> impl Future<Output = Result<HttpResponse>> + MaybeSend {
+		async move {
+			let response = self.execute(request).await?;
+			let len = response.body.len() as u64;
+			progress(len, Some(len));
+			Ok(response)
+		}
+	}
+}
+
+/// The default backend, executing requests with reqwest.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestBackend {
+	client: reqwest::Client,
+}
+
+impl ReqwestBackend {
+	/// Creates a backend using an existing reqwest client.
+	pub fn new(client: reqwest::Client) -> Self {
+		Self { client }
+	}
+}
+
+impl From<reqwest::Client> for ReqwestBackend {
+	fn from(value: reqwest::Client) -> Self {
+		Self::new(value)
+	}
+}
+
+impl ReqwestBackend {
+	/// Sends a request, giving the response before its body was received.
+	async fn send(&self, request: HttpRequest) -> reqwest::Result<reqwest::Response> {
+		let mut builder = self
+			.client
+			.request(request.method, &request.url)
+			.query(&request.query);
+		for (name, value) in &request.headers {
+			builder = builder.header(name, value);
+		}
+		if let Some(body) = request.body {
+			builder = builder.body(body);
+		}
+		builder.send().await
+	}
+}
+
+impl HttpBackend for ReqwestBackend {
+	async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+		let secret = request.has_secret_query();
+		let mut response = self
+			.send(request)
+			.await
+			.map_err(|err| redact_url(err, secret))?;
+		let status = response.status().as_u16();
+		let url = response.url().to_string();
+		let headers = std::mem::take(response.headers_mut());
+		let body = response
+			.bytes()
+			.await
+			.map_err(|err| redact_url(err, secret))?
+			.to_vec();
+		Ok(HttpResponse {
+			status,
+			url,
+			headers,
+			body,
+		})
+	}
+
+	/// Bodies aren't streamed on wasm, where progress is only reported once the whole body was received.
+	#[cfg(not(target_arch = "wasm32"))]
+	async fn execute_with_progress<F: FnMut(u64, Option<u64>) + MaybeSend>(
+		&self,
+		request: HttpRequest,
+		mut progress: F,
+	) -> Result<HttpResponse> {
+		let secret = request.has_secret_query();
+		let mut response = self
+			.send(request)
+			.await
+			.map_err(|err| redact_url(err, secret))?;
+		let status = response.status().as_u16();
+		let url = response.url().to_string();
+		let total = response.content_length();
+		let headers = std::mem::take(response.headers_mut());
+		let mut body = Vec::with_capacity(total.unwrap_or(0).min(64 * 1024 * 1024) as usize);
+		progress(0, total);
+		while let Some(chunk) = response
+			.chunk()
+			.await
+			.map_err(|err| redact_url(err, secret))?
+		{
+			body.extend_from_slice(&chunk);
+			progress(body.len() as u64, total);
+		}
+		Ok(HttpResponse {
+			status,
+			url,
+			headers,
+			body,
+		})
+	}
+}
+
+/// A url and its sorted query parameters.
+#[cfg(feature = "test-util")]
+type MockKey = (String, Vec<(String, String)>);
+
+/// A canned response of a `MockBackend`.
+#[cfg(feature = "test-util")]
+#[derive(Debug, Clone)]
+struct MockResponse {
+	status: u16,
+	headers: HeaderMap,
+	body: Vec<u8>,
+}
+
+/// An in-memory backend serving canned responses, for testing code which uses the client without any network.
+/// Responses are matched by url and query parameters in any order first, then by url alone. Unmatched requests get a 404.
+/// Responses added for the same url are served in the order they were added, with the last one served from then on,
+/// e.g. to fail a few times before succeeding.
+#[cfg(feature = "test-util")]
+#[derive(Debug, Default)]
+pub struct MockBackend {
+	responses: std::sync::Mutex<std::collections::HashMap<MockKey, Vec<MockResponse>>>,
+	requests: std::sync::Mutex<Vec<HttpRequest>>,
+}
+
+#[cfg(feature = "test-util")]
+impl MockBackend {
+	/// Creates a backend with no responses.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a response for the given url, which may include a query string.
+	pub fn respond(self, url: &str, status: u16, body: impl Into<Vec<u8>>) -> Self {
+		self.respond_with_headers(url, status, &[], body)
+	}
+
+	/// Adds a response with headers for the given url, like `respond`.
+	///
+	/// # Panics
+	///
+	/// Panics if a header name or value is invalid.
+	pub fn respond_with_headers(
+		self,
+		url: &str,
+		status: u16,
+		headers: &[(&str, &str)],
+		body: impl Into<Vec<u8>>,
+	) -> Self {
+		let (url, query) = match url.split_once('?') {
+			Some((url, query)) => (
+				url,
+				reqwest::Url::parse(&format!("http://localhost/?{query}"))
+					.map(|url| url.query_pairs().into_owned().collect())
+					.unwrap_or_default(),
+			),
+			None => (url, Vec::new()),
+		};
+		let mut header_map = HeaderMap::new();
+		for (name, value) in headers {
+			header_map.append(
+				reqwest::header::HeaderName::try_from(*name).expect("invalid header name"),
+				reqwest::header::HeaderValue::try_from(*value).expect("invalid header value"),
+			);
+		}
+		self.responses
+			.lock()
+			.expect("lock poisoned")
+			.entry(Self::key(url, query))
+			.or_default()
+			.push(MockResponse {
+				status,
+				headers: header_map,
+				body: body.into(),
+			});
+		self
+	}
+
+	/// Gets every request executed so far.
+	pub fn requests(&self) -> Vec<HttpRequest> {
+		self.requests.lock().expect("lock poisoned").clone()
+	}
+
+	fn key(url: &str, mut query: Vec<(String, String)>) -> MockKey {
+		query.sort();
+		(url.to_string(), query)
+	}
+
+	/// Takes the next response for a request, leaving the last one for the url in place.
+	fn next_response(&self, request: &HttpRequest) -> Option<MockResponse> {
+		let mut responses = self.responses.lock().expect("lock poisoned");
+		let key = Self::key(&request.url, request.query.clone());
+		let key = if responses.contains_key(&key) {
+			key
+		} else {
+			Self::key(&request.url, Vec::new())
+		};
+		let queue = responses.get_mut(&key)?;
+		if queue.len() > 1 {
+			Some(queue.remove(0))
+		} else {
+			queue.first().cloned()
+		}
+	}
+}
+
+#[cfg(feature = "test-util")]
+impl HttpBackend for MockBackend {
+	async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+		let response = self.next_response(&request).unwrap_or(MockResponse {
+			status: 404,
+			headers: HeaderMap::new(),
+			body: Vec::new(),
+		});
+		let url = request.url.clone();
+		self.requests.lock().expect("lock poisoned").push(request);
+		Ok(HttpResponse {
+			status: response.status,
+			url,
+			headers: response.headers,
+			body: response.body,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn request(headers: &[(&str, &str)]) -> HttpRequest {
+		HttpRequest {
+			method: Method::GET,
+			url: "https://hangar.papermc.io/api/v1/projects/Test".to_string(),
+			query: Vec::new(),
+			headers: headers
+				.iter()
+				.map(|(name, value)| (name.to_string(), value.to_string()))
+				.collect(),
+			body: None,
+		}
+	}
+
+	#[test]
+	fn response_keys_depend_on_language_and_credentials() {
+		let anonymous = request(&[]).response_key();
+		let german = request(&[("Accept-Language", "de")]).response_key();
+		let alice = request(&[("Authorization", "HangarAuth alice")]).response_key();
+		let bob = request(&[("authorization", "HangarAuth bob")]).response_key();
+		assert_eq!(anonymous, request(&[]).key());
+		assert_eq!(german, format!("{anonymous} lang=de"));
+		assert_ne!(alice, anonymous);
+		assert_ne!(alice, bob);
+		assert_eq!(
+			alice,
+			request(&[("Authorization", "HangarAuth alice")]).response_key()
+		);
+		assert!(!alice.contains("alice"));
+	}
+}
@@ -0,0 +1,652 @@
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, Mutex},
+};
+
+use reqwest::Method;
+use web_time::Instant;
+
+use crate::api::{
+	AuthenticateRequest, HangarRequest, LatestReleaseRequest, LatestVersionRequest,
+	ModerationQueue, ModerationQueueRequest, PaginatedRequest, PermissionsRequest, ProjectMatch,
+	ProjectRequest, ProjectsRequest, ProjectsResponse, VersionsRequest, VersionsResponse,
+};
+use crate::auth::{ApiKey, AuthToken};
+use crate::backend::{redact_url, HttpRequest, HttpResponse};
+use crate::client::{
+	add_channel, api_key_error, channels_request, check_response, download_size, download_url,
+	exists_result, head_unsupported, is_promotable, leaderboard_request, merge_user_projects,
+	mirror_request, page_size, parse_envelope, promoted_request, rank_by_downloads, refresh_error,
+	require_permission, retain_unseen, sort_channels, user_projects_requests, verify_download,
+	Attempts, ClientConfig, DownloadSource, DownloadedFile, HangarClientBuilder, MirrorStrategy,
+	MirroredDownload, RefreshedProject, ResponseEnvelope, MAX_LEADERBOARD,
+};
+use crate::diff::StatsSnapshot;
+use crate::error::{Error, Result};
+use crate::handle::BlockingProjectHandle;
+use crate::object::{
+	ByPlatform, Category, Channel, NamedPermission, Pagination, Platform, Project, ProjectStats,
+	ProjectsSort, Slug, Version,
+};
+use crate::rate_limit::RateLimit;
+use crate::resolve::{DependencyReport, ResolveOptions, Resolver};
+
+/// Blocking client for sending requests to Hangar, mirroring `HangarClient` without needing an async runtime.
+/// Requests are built, retried, and mapped to errors exactly like the async client.
+#[derive(Debug, Clone)]
+pub struct BlockingClient {
+	client: reqwest::blocking::Client,
+	config: ClientConfig,
+	/// Held while refreshing the auth token so concurrent requests only refresh it once
+	refresh_lock: Arc<Mutex<()>>,
+}
+
+impl Default for BlockingClient {
+	fn default() -> Self {
+		Self::builder()
+			.build_blocking()
+			.expect("the default client configuration is valid")
+	}
+}
+
+impl BlockingClient {
+	/// Creates a new client for the official Hangar instance.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new client for the official Hangar instance which authenticates with an api key, getting a new
+	/// token whenever the current one is about to expire.
+	pub fn with_api_key(api_key: impl Into<ApiKey>) -> Self {
+		Self::builder()
+			.api_key(api_key)
+			.build_blocking()
+			.expect("the default client configuration is valid")
+	}
+
+	/// Creates a builder for configuring a client. Finish it with `HangarClientBuilder::build_blocking`.
+	pub fn builder() -> HangarClientBuilder {
+		HangarClientBuilder::default()
+	}
+
+	pub(crate) fn from_parts(client: reqwest::blocking::Client, config: ClientConfig) -> Self {
+		Self {
+			client,
+			config,
+			refresh_lock: Arc::default(),
+		}
+	}
+
+	/// Gets the base url requests are sent to.
+	pub fn base_url(&self) -> &str {
+		&self.config.base_url
+	}
+
+	/// Exchanges an api key for a JWT, which is then sent with every following request to Hangar.
+	/// The token expires after a while, after which this needs to be called again.
+	pub fn authenticate(&self, api_key: impl Into<ApiKey>) -> Result<AuthToken> {
+		let request = AuthenticateRequest::builder().api_key(api_key).build();
+		let response = self
+			.execute(self.config.http_request(&request)?)
+			.map_err(api_key_error)?;
+		Ok(self
+			.config
+			.store_session(AuthenticateRequest::parse_response(&response.body)?))
+	}
+
+	/// Gets the token currently used to authenticate requests.
+	pub fn auth_token(&self) -> Option<AuthToken> {
+		self.config.auth_token()
+	}
+
+	/// Sets or clears the token used to authenticate requests.
+	pub fn set_auth_token(&self, token: Option<AuthToken>) {
+		self.config.set_auth_token(token);
+	}
+
+	/// Gets the rate limit Hangar reported in the most recent response, shared between clones of the client.
+	pub fn rate_limit(&self) -> Option<RateLimit> {
+		self.config.rate_limit()
+	}
+
+	/// Sends a request to Hangar, returning its parsed response.
+	pub fn send<R: HangarRequest>(&self, request: &R) -> Result<R::Response> {
+		Ok(self.send_with_meta(request)?.body)
+	}
+
+	/// Sends a request to Hangar, returning its parsed response alongside metadata about the response.
+	pub fn send_with_meta<R: HangarRequest>(
+		&self,
+		request: &R,
+	) -> Result<ResponseEnvelope<R::Response>> {
+		self.fetch(request, true)
+	}
+
+	/// Sends a request to Hangar without checking the cache, storing the fresh response in it.
+	pub fn send_uncached<R: HangarRequest>(&self, request: &R) -> Result<R::Response> {
+		Ok(self.fetch(request, false)?.body)
+	}
+
+	/// Checks whether what a request asks for exists without downloading it, see `HangarClient::exists`.
+	pub fn exists<R: HangarRequest>(&self, request: &R) -> Result<bool> {
+		let mut http = self.config.head_request(request)?;
+		self.refresh_auth()?;
+		self.check_permission(request)?;
+		let result = match self.execute(http.clone()) {
+			Err(err) if head_unsupported(&err) => {
+				http.method = Method::GET;
+				self.execute(http)
+			}
+			result => result,
+		};
+		exists_result(result)
+	}
+
+	/// Removes the cached response to a request, if there is one.
+	pub fn invalidate<R: HangarRequest>(&self, request: &R) {
+		self.config.invalidate(request);
+	}
+
+	/// Removes every cached response.
+	pub fn clear_cache(&self) {
+		self.config.clear_cache();
+	}
+
+	/// Removes every cached response about a project, see `HangarClient::invalidate_project`.
+	pub fn invalidate_project(&self, slug: impl Into<Slug>) {
+		self.config.invalidate_project(&slug.into());
+	}
+
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument(
+			name = "hangar_request",
+			level = "debug",
+			skip_all,
+			fields(
+				endpoint = crate::client::endpoint_name::<R>(),
+				path = %request.path(),
+				offset = crate::client::pagination_offset(&request.query().unwrap_or_default()),
+				attempt = tracing::field::Empty,
+			)
+		)
+	)]
+	fn fetch<R: HangarRequest>(
+		&self,
+		request: &R,
+		use_cache: bool,
+	) -> Result<ResponseEnvelope<R::Response>> {
+		request.validate()?;
+		let cache_key = self.config.cache_key(request);
+		if let Some(response) = self.config.cached(cache_key.as_deref(), use_cache) {
+			return parse_envelope::<R>(response);
+		}
+		self.refresh_auth()?;
+		self.check_permission(request)?;
+		let mut http = self.config.http_request(request)?;
+		let revalidation = self.config.revalidation(&mut http);
+		let response = self.execute(http)?;
+		self.config.finish::<R>(cache_key, revalidation, response)
+	}
+
+	/// Iterates over every item matched by a paginated request, fetching pages as needed.
+	/// The request's limit is used as the page size, starting from its offset.
+	pub fn paginate<'a, R: PaginatedRequest + 'a>(
+		&'a self,
+		request: R,
+	) -> impl Iterator<Item = Result<R::Item>> + 'a {
+		let mut request = Some(request);
+		let mut items = Vec::new().into_iter();
+		let mut seen = HashSet::new();
+		std::iter::from_fn(move || loop {
+			if let Some(item) = items.next() {
+				return Some(Ok(item));
+			}
+			let mut current = request.take()?;
+			let (pagination, mut page) = match self.send(&current) {
+				Ok(response) => R::into_page(response),
+				Err(err) => return Some(Err(err)),
+			};
+			if current.advance(&pagination, page.len()) {
+				request = Some(current);
+			}
+			retain_unseen::<R>(&mut page, &mut seen);
+			items = page.into_iter();
+		})
+	}
+
+	/// Gets up to `n` items matched by a paginated request, see `HangarClient::take`.
+	pub fn take<R: PaginatedRequest>(&self, mut request: R, n: usize) -> Result<Vec<R::Item>> {
+		if n == 0 {
+			return Ok(Vec::new());
+		}
+		request.pagination_mut().limit = page_size(n);
+		self.paginate(request).take(n).collect()
+	}
+
+	/// Gets the `n` most downloaded projects, optionally in a single category, see `HangarClient::leaderboard`.
+	pub fn leaderboard(&self, category: Option<Category>, n: usize) -> Result<Vec<Project>> {
+		let projects = self.take(leaderboard_request(category), n.min(MAX_LEADERBOARD))?;
+		Ok(rank_by_downloads(projects))
+	}
+
+	/// Lists the projects and versions waiting on moderation, see `HangarClient::moderation_queue`. The versions of
+	/// the scanned projects are fetched one project after the other.
+	pub fn moderation_queue(&self, request: &ModerationQueueRequest) -> Result<ModerationQueue> {
+		let permissions = self.send(&PermissionsRequest::default())?;
+		require_permission(&permissions, NamedPermission::Reviewer)?;
+		let projects = self.take(request.scan_request(), request.scan)?;
+		let mut versions = Vec::new();
+		if request.versions {
+			for project in &projects {
+				let response = self.send(&channels_request(&project.namespace.slug))?;
+				versions.push((project.namespace.clone(), response.result));
+			}
+		}
+		Ok(ModerationQueue::from_scan(projects, versions))
+	}
+
+	/// Gets a page of projects similar to `project`, see `HangarClient::similar`.
+	pub fn similar(&self, project: &Project, pagination: Pagination) -> Result<ProjectsResponse> {
+		let response = self.send(&ProjectsRequest::similar_to(project, pagination))?;
+		Ok(response.without(project))
+	}
+
+	/// Searches for a project by name, see `HangarClient::find_project`.
+	pub fn find_project(&self, name: &str) -> Result<ProjectMatch> {
+		let response = self.send(&ProjectsRequest::named(name))?;
+		Ok(ProjectMatch::from_results(name, response.result))
+	}
+
+	/// Gets the project named exactly `name`, see `HangarClient::find_project_exact`.
+	pub fn find_project_exact(&self, name: &str) -> Result<Option<Project>> {
+		Ok(self.find_project(name)?.exact())
+	}
+
+	/// Gets a handle for looking up things about a project without repeating its slug, see
+	/// `HangarClient::project`.
+	pub fn project(&self, slug: impl Into<String>) -> BlockingProjectHandle<'_> {
+		BlockingProjectHandle::new(self, slug.into())
+	}
+
+	/// Gets a project along with a page of its versions, sending the requests one after the other.
+	pub fn project_with_versions(
+		&self,
+		slug: impl Into<String>,
+		pagination: Pagination,
+	) -> Result<(Project, VersionsResponse)> {
+		let slug = slug.into();
+		let project = ProjectRequest::new(slug.clone());
+		let versions = VersionsRequest::builder()
+			.slug(slug)
+			.pagination(pagination)
+			.build();
+		Ok((self.send(&project)?, self.send(&versions)?))
+	}
+
+	/// Gets the latest version of a project in its default release channel, looking up its name and then fetching
+	/// the full version. Gives `Error::NotFound` if the project has never released a version.
+	pub fn latest_release_version(&self, slug: impl Into<String>) -> Result<Version> {
+		let request = LatestReleaseRequest::builder().slug(slug).build();
+		let name = self.send(&request)?;
+		self.send(&request.version_request(name))
+	}
+
+	/// Gets the latest version of a project in a channel, looking up its name and then fetching the full version.
+	pub fn latest_version(
+		&self,
+		slug: impl Into<String>,
+		channel: impl Into<String>,
+	) -> Result<Version> {
+		let request = LatestVersionRequest::builder()
+			.slug(slug)
+			.channel(channel)
+			.build();
+		let name = self.send(&request)?;
+		self.send(&request.version_request(name))
+	}
+
+	/// Resolves the plugin dependencies of a version on a platform, following the dependencies of each Hangar
+	/// project found. Dependencies are looked up by name and resolved to the newest available version with a download
+	/// for the platform, while external dependencies are listed with their url without being followed.
+	pub fn resolve_dependencies(
+		&self,
+		version: &Version,
+		platform: Platform,
+		options: ResolveOptions,
+	) -> Result<DependencyReport> {
+		let mut resolver = Resolver::new(version, platform, options);
+		while let Some((dependency, depth)) = resolver.next() {
+			let projects = self.send(&Resolver::project_request(&dependency))?;
+			let Some(slug) = resolver.find_project(&dependency, projects) else {
+				continue;
+			};
+			let versions = self.send(&resolver.versions_request(&slug))?;
+			resolver.resolve(dependency, depth, slug, versions);
+		}
+		Ok(resolver.finish())
+	}
+
+	/// Counts how many projects matching a search are in each category, sending one request per category.
+	pub fn category_counts(&self, request: &ProjectsRequest) -> Result<HashMap<Category, i64>> {
+		Category::ALL
+			.into_iter()
+			.map(|category| {
+				let request = request.category_count_request(category);
+				Ok((category, self.send(&request)?.pagination.count))
+			})
+			.collect()
+	}
+
+	/// Counts how many projects matching a search support each platform, sending one request per platform.
+	pub fn platform_counts(&self, request: &ProjectsRequest) -> Result<HashMap<Platform, i64>> {
+		Platform::ALL
+			.into_iter()
+			.map(|platform| {
+				let request = request.platform_count_request(platform);
+				Ok((platform, self.send(&request)?.pagination.count))
+			})
+			.collect()
+	}
+
+	/// Gets the version to recommend downloading for each platform, see `HangarClient::promoted_versions`.
+	/// The platforms are looked up one after another.
+	pub fn promoted_versions(&self, slug: impl Into<String>) -> Result<ByPlatform<Version>> {
+		let slug = slug.into();
+		let promoted = |platform| {
+			for version in self.paginate(promoted_request(&slug, platform)) {
+				let version = version?;
+				if is_promotable(&version, platform) {
+					return Ok(Some(version));
+				}
+			}
+			Ok::<_, Error>(None)
+		};
+		Ok(ByPlatform {
+			paper: promoted(Platform::Paper)?,
+			waterfall: promoted(Platform::Waterfall)?,
+			velocity: promoted(Platform::Velocity)?,
+		})
+	}
+
+	/// Gets the channels a project has published versions in, see `HangarClient::project_channels`.
+	pub fn project_channels(&self, slug: impl Into<String>) -> Result<Vec<Channel>> {
+		let mut channels = Vec::new();
+		for version in self.paginate(channels_request(&slug.into())) {
+			add_channel(&mut channels, version?.channel);
+		}
+		Ok(sort_channels(channels))
+	}
+
+	/// Gets every project a user maintains, including the projects of organizations they're in, see
+	/// `HangarClient::projects_for_user`.
+	pub fn projects_for_user(
+		&self,
+		username: impl Into<String>,
+		sort: ProjectsSort,
+	) -> Result<Vec<Project>> {
+		let (member, owned) = user_projects_requests(&username.into(), sort);
+		let member = self.paginate(member).collect::<Result<_>>()?;
+		let owned = self.paginate(owned).collect::<Result<_>>()?;
+		Ok(merge_user_projects(member, owned, sort))
+	}
+
+	/// Fetches a project again by the slug it was seen with before, see `HangarClient::refresh_project`.
+	pub fn refresh_project(&self, slug: impl Into<String>) -> Result<RefreshedProject> {
+		let slug = slug.into();
+		let project = self.send_uncached(&ProjectRequest::new(&slug));
+		RefreshedProject::from_result(slug, project)
+	}
+
+	/// Fetches several projects again like `refresh_project`, one after another, giving a result for each slug in
+	/// order.
+	pub fn refresh_projects(
+		&self,
+		slugs: impl IntoIterator<Item = impl Into<String>>,
+	) -> Vec<Result<RefreshedProject>> {
+		slugs
+			.into_iter()
+			.map(|slug| self.refresh_project(slug))
+			.collect()
+	}
+
+	/// Gets the stats of a project, like its download and star counts.
+	pub fn project_stats(&self, slug: impl Into<Slug>) -> Result<ProjectStats> {
+		let request = ProjectRequest::new(slug);
+		Ok(self.send(&request)?.stats)
+	}
+
+	/// Gets the stats of a project as a snapshot taken now, see `StatsSnapshot`.
+	pub fn project_stats_snapshot(&self, slug: impl Into<Slug>) -> Result<StatsSnapshot> {
+		Ok(StatsSnapshot::now(self.project_stats(slug)?))
+	}
+
+	/// Gets how many users have starred a project, without paging through its stargazers.
+	pub fn star_count(&self, slug: impl Into<Slug>) -> Result<i64> {
+		Ok(self.project_stats(slug)?.stars)
+	}
+
+	/// Gets how many users are watching a project, without paging through its watchers.
+	pub fn watcher_count(&self, slug: impl Into<Slug>) -> Result<i64> {
+		Ok(self.project_stats(slug)?.watchers)
+	}
+
+	/// Downloads the file of a version for the given platform.
+	/// External downloads are fetched from their external url rather than from Hangar.
+	pub fn download(&self, version: &Version, platform: Platform) -> Result<Vec<u8>> {
+		Ok(self.download_with_meta(version, platform)?.bytes)
+	}
+
+	/// Downloads the file of a version along with where it was downloaded from, see
+	/// `HangarClient::download_with_meta`.
+	pub fn download_with_meta(
+		&self,
+		version: &Version,
+		platform: Platform,
+	) -> Result<DownloadedFile> {
+		let url = download_url(version, platform)?;
+		self.refresh_auth()?;
+		let response = self.execute(self.config.request(Method::GET, url, Vec::new()))?;
+		Ok(response.into())
+	}
+
+	/// Downloads the file of a version of a project, falling back to other sources if one fails, see
+	/// `HangarClient::download_mirrored`.
+	pub fn download_mirrored(
+		&self,
+		slug: impl Into<Slug>,
+		version: &Version,
+		platform: Platform,
+		strategy: MirrorStrategy,
+	) -> Result<MirroredDownload> {
+		let slug = slug.into();
+		let mut failures: Vec<(DownloadSource, Error)> = Vec::new();
+		for &source in strategy.sources() {
+			let request = mirror_request(&self.config, &slug, version, platform, source)?;
+			self.refresh_auth()?;
+			let result = self.execute(request).and_then(|response| {
+				verify_download(version, platform, &response.body)?;
+				Ok(response)
+			});
+			match result {
+				Ok(response) => {
+					return Ok(MirroredDownload {
+						file: response.into(),
+						source,
+						failures,
+					});
+				}
+				Err(err) => failures.push((source, err)),
+			}
+		}
+		Err(failures
+			.pop()
+			.map(|(_, err)| err)
+			.expect("strategies have a source"))
+	}
+
+	/// Downloads the file of a version like `download`, reporting its progress, see
+	/// `HangarClient::download_with_progress`.
+	pub fn download_with_progress(
+		&self,
+		version: &Version,
+		platform: Platform,
+		mut progress: impl FnMut(u64, Option<u64>),
+	) -> Result<Vec<u8>> {
+		let url = download_url(version, platform)?;
+		let size = download_size(version, platform);
+		self.refresh_auth()?;
+		let mut progress = |downloaded, total: Option<u64>| progress(downloaded, total.or(size));
+		Ok(self
+			.execute_with_progress(
+				self.config.request(Method::GET, url, Vec::new()),
+				Some(&mut progress),
+			)?
+			.body)
+	}
+
+	/// Gets a new auth token if the client has an api key and its current token is about to expire.
+	/// Only one refresh happens at a time, with requests waiting on it using the new token.
+	fn refresh_auth(&self) -> Result<()> {
+		if self.config.refresh_key().is_none() {
+			return Ok(());
+		}
+		let _guard = self.refresh_lock.lock().expect("lock poisoned");
+		// another request may have refreshed the token while this one was waiting
+		if let Some(api_key) = self.config.refresh_key() {
+			self.authenticate(api_key.clone()).map_err(refresh_error)?;
+		}
+		Ok(())
+	}
+
+	/// Checks the user has the permission a request needs, fetching their permissions if they aren't known yet.
+	fn check_permission<R: HangarRequest>(&self, request: &R) -> Result<()> {
+		let Some(check) = self.config.check_permission(request)? else {
+			return Ok(());
+		};
+		let permissions = check.request();
+		let response = self.execute(self.config.http_request(&permissions)?)?;
+		self.config
+			.finish_permission_check(check, PermissionsRequest::parse_response(&response.body)?)
+	}
+
+	/// Sends a prepared request, returning a successful response.
+	fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+		self.execute_with_progress(request, None)
+	}
+
+	/// Sends a request like `execute`, reporting the progress of receiving each attempt's body.
+	fn execute_with_progress(
+		&self,
+		request: HttpRequest,
+		mut progress: Option<&mut (dyn FnMut(u64, Option<u64>) + '_)>,
+	) -> Result<HttpResponse> {
+		let mut attempts = Attempts::new(&self.config, &request);
+		loop {
+			attempts.start();
+			let start = Instant::now();
+			let result = self.attempt(request.clone(), progress.as_deref_mut());
+			attempts.finished(&result, start.elapsed());
+			match result {
+				Ok(response) => return Ok(response),
+				Err(err) => std::thread::sleep(attempts.failed(err)?),
+			}
+		}
+	}
+
+	/// Makes a single attempt at sending a request, running it through the client's middleware.
+	fn attempt(
+		&self,
+		mut request: HttpRequest,
+		progress: Option<&mut (dyn FnMut(u64, Option<u64>) + '_)>,
+	) -> Result<HttpResponse> {
+		if let Some(wait) = self.config.rate_limit_wait(&request) {
+			debug!(
+				?wait,
+				"no requests left, waiting for the rate limit to reset"
+			);
+			std::thread::sleep(wait);
+		}
+		self.config.before_request(&mut request)?;
+		let api = self.config.is_api(&request);
+		let start = Instant::now();
+		let response = self.send_http(request, progress)?;
+		self.config.record_rate_limit(&response);
+		self.config.record_deprecation(&response);
+		self.config.after_response(&response, start.elapsed())?;
+		check_response(response, api)
+	}
+
+	/// Sends a request, returning its response regardless of status.
+	/// The body is read in chunks when there's a `progress` callback to report to.
+	fn send_http(
+		&self,
+		request: HttpRequest,
+		progress: Option<&mut (dyn FnMut(u64, Option<u64>) + '_)>,
+	) -> Result<HttpResponse> {
+		let secret = request.has_secret_query();
+		let mut builder = self
+			.client
+			.request(request.method, &request.url)
+			.query(&request.query);
+		for (name, value) in &request.headers {
+			builder = builder.header(name, value);
+		}
+		if let Some(body) = request.body {
+			builder = builder.body(body);
+		}
+		let mut response = builder.send().map_err(|err| redact_url(err, secret))?;
+		let status = response.status().as_u16();
+		let url = response.url().to_string();
+		let headers = std::mem::take(response.headers_mut());
+		let body = match progress {
+			Some(progress) => read_with_progress(&mut response, progress)
+				.map_err(|err| redact_url(err, secret))?,
+			None => response
+				.bytes()
+				.map_err(|err| redact_url(err, secret))?
+				.to_vec(),
+		};
+		Ok(HttpResponse {
+			status,
+			url,
+			headers,
+			body,
+		})
+	}
+}
+
+/// Reads a response's body in chunks, calling `progress` after each one.
+fn read_with_progress(
+	response: &mut reqwest::blocking::Response,
+	progress: &mut (dyn FnMut(u64, Option<u64>) + '_),
+) -> reqwest::Result<Vec<u8>> {
+	/// Collects the body, reporting how much of it was written so far.
+	struct ProgressWriter<'a, 'b> {
+		body: Vec<u8>,
+		total: Option<u64>,
+		progress: &'a mut (dyn FnMut(u64, Option<u64>) + 'b),
+	}
+
+	impl std::io::Write for ProgressWriter<'_, '_> {
+		fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+			self.body.extend_from_slice(buf);
+			(self.progress)(self.body.len() as u64, self.total);
+			Ok(buf.len())
+		}
+
+		fn flush(&mut self) -> std::io::Result<()> {
+			Ok(())
+		}
+	}
+
+	let total = response.content_length();
+	progress(0, total);
+	let mut writer = ProgressWriter {
+		body: Vec::new(),
+		total,
+		progress,
+	};
+	response.copy_to(&mut writer)?;
+	Ok(writer.body)
+}
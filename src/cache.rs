@@ -0,0 +1,579 @@
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+use web_time::SystemTime;
+
+use crate::backend::{HttpResponse, MaybeSend, MaybeSync};
+
+/// A response stored in a cache, along with when it was fetched.
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+	/// The response as it was received, including its `ETag` header if it had one
+	pub response: HttpResponse,
+	/// When the response was received
+	pub stored_at: SystemTime,
+}
+
+/// Storage for the responses a client caches, which decides how many are kept.
+/// Keys are the request's method and url along with its sorted query parameters. Expiry is handled by the client,
+/// so stores can return responses of any age.
+///
+/// Implement this to keep responses somewhere else, e.g. to share them between processes.
+pub trait CacheStore: MaybeSend + MaybeSync {
+	/// Gets the response stored for a request.
+	fn get(&self, key: &str) -> Option<CachedResponse>;
+
+	/// Stores the response for a request, replacing any previous one.
+	fn put(&self, key: &str, response: CachedResponse);
+
+	/// Removes the response stored for a request.
+	fn remove(&self, key: &str);
+
+	/// Removes every stored response.
+	fn clear(&self);
+
+	/// Removes the stored responses whose keys `keep` returns false for, e.g. every response about a project.
+	/// Stores which can't list their keys can leave this to remove every response, which is always safe for a
+	/// cache.
+	fn retain(&self, keep: &dyn Fn(&str) -> bool) {
+		let _ = keep;
+		self.clear();
+	}
+}
+
+/// An in-memory `CacheStore`. Once full, storing another response evicts the least recently used one.
+#[derive(Debug)]
+pub struct MemoryCacheStore {
+	max_entries: usize,
+	entries: Mutex<MemoryEntries>,
+}
+
+/// The responses in a `MemoryCacheStore`, each with when it was last used by the store's own clock.
+#[derive(Debug, Default)]
+struct MemoryEntries {
+	responses: HashMap<String, (CachedResponse, u64)>,
+	clock: u64,
+}
+
+impl MemoryEntries {
+	fn tick(&mut self) -> u64 {
+		self.clock += 1;
+		self.clock
+	}
+}
+
+impl MemoryCacheStore {
+	/// Creates an empty store holding up to `max_entries` responses.
+	pub fn new(max_entries: usize) -> Self {
+		Self {
+			max_entries,
+			entries: Mutex::default(),
+		}
+	}
+}
+
+impl CacheStore for MemoryCacheStore {
+	fn get(&self, key: &str) -> Option<CachedResponse> {
+		let mut entries = self.entries.lock().expect("lock poisoned");
+		let now = entries.tick();
+		let (response, last_used) = entries.responses.get_mut(key)?;
+		*last_used = now;
+		Some(response.clone())
+	}
+
+	fn put(&self, key: &str, response: CachedResponse) {
+		if self.max_entries == 0 {
+			return;
+		}
+		let mut entries = self.entries.lock().expect("lock poisoned");
+		if entries.responses.len() >= self.max_entries && !entries.responses.contains_key(key) {
+			let least_recent = entries
+				.responses
+				.iter()
+				.min_by_key(|(_, (_, last_used))| *last_used)
+				.map(|(key, _)| key.clone());
+			if let Some(least_recent) = least_recent {
+				entries.responses.remove(&least_recent);
+			}
+		}
+		let now = entries.tick();
+		entries.responses.insert(key.to_string(), (response, now));
+	}
+
+	fn remove(&self, key: &str) {
+		self.entries
+			.lock()
+			.expect("lock poisoned")
+			.responses
+			.remove(key);
+	}
+
+	fn clear(&self) {
+		self.entries
+			.lock()
+			.expect("lock poisoned")
+			.responses
+			.clear();
+	}
+
+	fn retain(&self, keep: &dyn Fn(&str) -> bool) {
+		self.entries
+			.lock()
+			.expect("lock poisoned")
+			.responses
+			.retain(|key, _| keep(key));
+	}
+}
+
+/// The cache of responses to read-only requests, shared between clones of a client.
+#[derive(Clone)]
+pub(crate) struct ResponseCache {
+	ttl: Duration,
+	store: Arc<dyn CacheStore>,
+}
+
+impl ResponseCache {
+	pub(crate) fn new(ttl: Duration, store: impl CacheStore + 'static) -> Self {
+		Self {
+			ttl,
+			store: Arc::new(store),
+		}
+	}
+
+	/// Gets the response stored for a request if it hasn't expired.
+	pub(crate) fn get(&self, key: &str) -> Option<HttpResponse> {
+		let entry = self.store.get(key)?;
+		let age = SystemTime::now()
+			.duration_since(entry.stored_at)
+			.unwrap_or_default();
+		if age < self.ttl {
+			Some(entry.response)
+		} else {
+			self.store.remove(key);
+			None
+		}
+	}
+
+	/// Stores the response for a request.
+	pub(crate) fn insert(&self, key: String, response: HttpResponse) {
+		self.store.put(
+			&key,
+			CachedResponse {
+				response,
+				stored_at: SystemTime::now(),
+			},
+		);
+	}
+
+	/// Removes the response stored for a request.
+	pub(crate) fn remove(&self, key: &str) {
+		self.store.remove(key);
+	}
+
+	/// Removes every stored response.
+	pub(crate) fn clear(&self) {
+		self.store.clear();
+	}
+
+	/// Removes the stored responses whose keys `keep` returns false for.
+	pub(crate) fn retain(&self, keep: &dyn Fn(&str) -> bool) {
+		self.store.retain(keep);
+	}
+}
+
+#[cfg(feature = "disk-cache")]
+pub use disk::DiskCacheStore;
+
+#[cfg(feature = "disk-cache")]
+mod disk {
+	use std::{
+		fs::{self, File},
+		io::{BufRead, BufReader, Write},
+		path::{Path, PathBuf},
+		time::{Duration, SystemTime, UNIX_EPOCH},
+	};
+
+	use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+	use serde::{Deserialize, Serialize};
+
+	use super::{CacheStore, CachedResponse};
+	use crate::backend::{stable_hash, HttpResponse};
+
+	/// The extension of the files responses are stored in.
+	const EXTENSION: &str = "cache";
+
+	/// A `CacheStore` keeping every response in its own file in a directory, so responses are kept between runs.
+	///
+	/// Files which can't be read or parsed count as missing and are removed. Once more than `max_entries` responses
+	/// or `max_bytes` bytes are stored, the least recently used responses are removed.
+	#[derive(Debug, Clone)]
+	pub struct DiskCacheStore {
+		dir: PathBuf,
+		max_entries: usize,
+		max_bytes: u64,
+	}
+
+	/// What's stored about a response on the first line of its file, before the body.
+	#[derive(Debug, Serialize, Deserialize)]
+	struct EntryHeader {
+		key: String,
+		status: u16,
+		url: String,
+		headers: Vec<(String, String)>,
+		/// Milliseconds since the unix epoch when the response was received
+		stored_at: u64,
+		body_len: usize,
+	}
+
+	impl DiskCacheStore {
+		/// How many responses a store holds by default.
+		pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+		/// How many bytes of responses a store holds by default.
+		pub const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+		/// Creates a store in the given directory, which is created when the first response is stored.
+		pub fn new(dir: impl Into<PathBuf>) -> Self {
+			Self {
+				dir: dir.into(),
+				max_entries: Self::DEFAULT_MAX_ENTRIES,
+				max_bytes: Self::DEFAULT_MAX_BYTES,
+			}
+		}
+
+		/// Sets how many responses are kept. Defaults to `DEFAULT_MAX_ENTRIES`.
+		pub fn max_entries(mut self, max_entries: usize) -> Self {
+			self.max_entries = max_entries;
+			self
+		}
+
+		/// Sets how many bytes of responses are kept, counting the files they're stored in. Defaults to
+		/// `DEFAULT_MAX_BYTES`.
+		pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+			self.max_bytes = max_bytes;
+			self
+		}
+
+		/// Gets the directory responses are stored in.
+		pub fn dir(&self) -> &Path {
+			&self.dir
+		}
+
+		/// Gets the path of the file the response for a key is stored in.
+		fn path(&self, key: &str) -> PathBuf {
+			self.dir
+				.join(format!("{:016x}.{EXTENSION}", stable_hash(key)))
+		}
+
+		/// Reads the response stored in a file, if it's intact and stored for the key.
+		fn read(path: &Path, key: &str) -> Option<CachedResponse> {
+			let contents = fs::read(path).ok()?;
+			let split = contents.iter().position(|&byte| byte == b'\n')?;
+			let header: EntryHeader = serde_json::from_slice(&contents[..split]).ok()?;
+			let body = &contents[split + 1..];
+			if header.key != key || header.body_len != body.len() {
+				return None;
+			}
+			let mut headers = HeaderMap::new();
+			for (name, value) in header.headers {
+				headers.append(
+					HeaderName::try_from(name).ok()?,
+					HeaderValue::try_from(value).ok()?,
+				);
+			}
+			Some(CachedResponse {
+				response: HttpResponse {
+					status: header.status,
+					url: header.url,
+					headers,
+					body: body.to_vec(),
+				},
+				stored_at: UNIX_EPOCH + Duration::from_millis(header.stored_at),
+			})
+		}
+
+		/// Reads the key a file's response is stored for, without reading the response.
+		fn stored_key(path: &Path) -> Option<String> {
+			let mut line = Vec::new();
+			BufReader::new(File::open(path).ok()?)
+				.read_until(b'\n', &mut line)
+				.ok()?;
+			let header: EntryHeader = serde_json::from_slice(line.strip_suffix(b"\n")?).ok()?;
+			Some(header.key)
+		}
+
+		/// Writes a response to a temporary file and moves it into place, so readers never see half a file.
+		fn write(&self, path: &Path, key: &str, cached: &CachedResponse) -> std::io::Result<()> {
+			let response = &cached.response;
+			let header = EntryHeader {
+				key: key.to_string(),
+				status: response.status,
+				url: response.url.clone(),
+				headers: response
+					.headers
+					.iter()
+					.filter_map(|(name, value)| {
+						Some((name.to_string(), value.to_str().ok()?.to_string()))
+					})
+					.collect(),
+				stored_at: cached
+					.stored_at
+					.duration_since(UNIX_EPOCH)
+					.unwrap_or_default()
+					.as_millis() as u64,
+				body_len: response.body.len(),
+			};
+			fs::create_dir_all(&self.dir)?;
+			let temp = path.with_extension("tmp");
+			let mut file = File::create(&temp)?;
+			serde_json::to_writer(&mut file, &header)?;
+			file.write_all(b"\n")?;
+			file.write_all(&response.body)?;
+			drop(file);
+			fs::rename(temp, path)
+		}
+
+		/// Removes the least recently used responses until the store is within its limits.
+		fn evict(&self) {
+			let Ok(dir) = fs::read_dir(&self.dir) else {
+				return;
+			};
+			let mut files: Vec<(SystemTime, u64, PathBuf)> = dir
+				.filter_map(|entry| {
+					let entry = entry.ok()?;
+					let path = entry.path();
+					if path.extension()? != EXTENSION {
+						return None;
+					}
+					let metadata = entry.metadata().ok()?;
+					Some((metadata.modified().ok()?, metadata.len(), path))
+				})
+				.collect();
+			files.sort();
+			let mut count = files.len();
+			let mut bytes: u64 = files.iter().map(|(_, len, _)| len).sum();
+			for (_, len, path) in files {
+				if count <= self.max_entries && bytes <= self.max_bytes {
+					break;
+				}
+				if fs::remove_file(path).is_ok() {
+					count -= 1;
+					bytes -= len;
+				}
+			}
+		}
+	}
+
+	impl CacheStore for DiskCacheStore {
+		fn get(&self, key: &str) -> Option<CachedResponse> {
+			let path = self.path(key);
+			match Self::read(&path, key) {
+				Some(cached) => {
+					// the modification time tracks when a response was last used
+					if let Ok(file) = File::options().write(true).open(&path) {
+						let _ = file.set_modified(SystemTime::now());
+					}
+					Some(cached)
+				}
+				None => {
+					let _ = fs::remove_file(path);
+					None
+				}
+			}
+		}
+
+		fn put(&self, key: &str, response: CachedResponse) {
+			if self.max_entries == 0 {
+				return;
+			}
+			let path = self.path(key);
+			if self.write(&path, key, &response).is_err() {
+				let _ = fs::remove_file(path.with_extension("tmp"));
+				return;
+			}
+			self.evict();
+		}
+
+		fn remove(&self, key: &str) {
+			let _ = fs::remove_file(self.path(key));
+		}
+
+		fn clear(&self) {
+			self.retain(&|_| false);
+		}
+
+		fn retain(&self, keep: &dyn Fn(&str) -> bool) {
+			let Ok(dir) = fs::read_dir(&self.dir) else {
+				return;
+			};
+			for entry in dir.flatten() {
+				let path = entry.path();
+				if path
+					.extension()
+					.is_some_and(|extension| extension == EXTENSION)
+					&& !Self::stored_key(&path).is_some_and(|key| keep(&key))
+				{
+					let _ = fs::remove_file(path);
+				}
+			}
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+		use crate::api::{HangarRequest, ProjectRequest};
+
+		const KEY: &str = "GET https://hangar.papermc.io/api/v1/projects/Maintenance";
+
+		/// Gets an empty directory to store responses in, unique to the test.
+		fn scratch_dir(test: &str) -> PathBuf {
+			let dir = std::env::temp_dir().join(format!(
+				"hangar-api-disk-cache-{}-{test}",
+				std::process::id()
+			));
+			let _ = fs::remove_dir_all(&dir);
+			dir
+		}
+
+		fn cached(body: &[u8]) -> CachedResponse {
+			let mut headers = HeaderMap::new();
+			headers.insert("etag", HeaderValue::from_static("W/\"8-1718266374\""));
+			headers.insert("content-type", HeaderValue::from_static("application/json"));
+			CachedResponse {
+				response: HttpResponse {
+					status: 200,
+					url: "https://hangar.papermc.io/api/v1/projects/Maintenance".to_string(),
+					headers,
+					body: body.to_vec(),
+				},
+				stored_at: UNIX_EPOCH + Duration::from_millis(1_718_266_374_884),
+			}
+		}
+
+		fn files(dir: &Path) -> usize {
+			fs::read_dir(dir).map_or(0, |dir| dir.count())
+		}
+
+		/// Waits long enough for the next file touched to have a later modification time.
+		fn tick() {
+			std::thread::sleep(Duration::from_millis(20));
+		}
+
+		#[test]
+		fn responses_round_trip_through_the_filesystem() {
+			let dir = scratch_dir("round-trip");
+			let body = include_bytes!("../tests/fixtures/project.json");
+			DiskCacheStore::new(&dir).put(KEY, cached(body));
+
+			let reopened = DiskCacheStore::new(&dir);
+			let stored = reopened.get(KEY).expect("stored response");
+			let response = stored.response;
+			assert_eq!(response.status, 200);
+			assert_eq!(
+				response.url,
+				"https://hangar.papermc.io/api/v1/projects/Maintenance"
+			);
+			assert_eq!(response.headers["etag"], "W/\"8-1718266374\"");
+			assert_eq!(response.headers["content-type"], "application/json");
+			assert_eq!(response.body, body);
+			assert_eq!(stored.stored_at, cached(body).stored_at);
+			let project = ProjectRequest::parse_response(&response.body).unwrap();
+			assert_eq!(project.namespace.key(), "kennytv/Maintenance");
+			assert!(reopened
+				.get("GET https://hangar.papermc.io/api/v1/projects/ViaVersion")
+				.is_none());
+			let _ = fs::remove_dir_all(&dir);
+		}
+
+		#[test]
+		fn damaged_files_are_misses() {
+			let dir = scratch_dir("damaged");
+			let store = DiskCacheStore::new(&dir);
+			let path = store.path(KEY);
+			let intact = {
+				store.put(KEY, cached(b"{\"name\":\"Maintenance\"}"));
+				fs::read(&path).unwrap()
+			};
+			let damaged: [&[u8]; 5] = [
+				&intact[..intact.len() - 3],
+				&intact[..10],
+				b"",
+				b"\x00\xff garbage\n{}",
+				b"{\"key\":\"not the key\"}\n{}",
+			];
+			for contents in damaged {
+				fs::write(&path, contents).unwrap();
+				assert!(store.get(KEY).is_none(), "{contents:?}");
+				assert!(!path.exists(), "{contents:?} should be removed");
+			}
+			store.put(KEY, cached(b"{}"));
+			assert_eq!(store.get(KEY).unwrap().response.body, b"{}");
+			let _ = fs::remove_dir_all(&dir);
+		}
+
+		#[test]
+		fn the_least_recently_used_responses_are_evicted() {
+			let dir = scratch_dir("entries");
+			let store = DiskCacheStore::new(&dir).max_entries(2);
+			store.put("a", cached(b"a"));
+			tick();
+			store.put("b", cached(b"b"));
+			tick();
+			assert!(store.get("a").is_some());
+			tick();
+			store.put("c", cached(b"c"));
+			assert!(store.get("b").is_none());
+			assert_eq!(store.get("a").unwrap().response.body, b"a");
+			assert_eq!(store.get("c").unwrap().response.body, b"c");
+			assert_eq!(files(&dir), 2);
+			let _ = fs::remove_dir_all(&dir);
+		}
+
+		#[test]
+		fn responses_over_the_size_cap_are_evicted() {
+			let dir = scratch_dir("bytes");
+			let store = DiskCacheStore::new(&dir);
+			store.put("a", cached(&[b'a'; 1000]));
+			let entry = fs::metadata(store.path("a")).unwrap().len();
+			let store = store.max_bytes(entry * 2);
+			tick();
+			store.put("b", cached(&[b'b'; 1000]));
+			tick();
+			store.put("c", cached(&[b'c'; 1000]));
+			assert!(store.get("a").is_none());
+			assert!(store.get("b").is_some());
+			assert!(store.get("c").is_some());
+			assert_eq!(files(&dir), 2);
+			let _ = fs::remove_dir_all(&dir);
+		}
+
+		#[test]
+		fn no_responses_are_stored_without_entries() {
+			let dir = scratch_dir("disabled");
+			let store = DiskCacheStore::new(&dir).max_entries(0);
+			store.put(KEY, cached(b"{}"));
+			assert!(store.get(KEY).is_none());
+			assert!(!dir.exists());
+		}
+
+		#[test]
+		fn clearing_removes_only_stored_responses() {
+			let dir = scratch_dir("clear");
+			let store = DiskCacheStore::new(&dir);
+			store.put("a", cached(b"a"));
+			store.put("b", cached(b"b"));
+			fs::write(dir.join("notes.txt"), "not a response").unwrap();
+			store.retain(&|key| key == "b");
+			assert!(store.get("a").is_none());
+			assert!(store.get("b").is_some());
+			store.clear();
+			assert!(store.get("b").is_none());
+			assert_eq!(files(&dir), 1);
+			assert!(dir.join("notes.txt").exists());
+			let _ = fs::remove_dir_all(&dir);
+		}
+	}
+}
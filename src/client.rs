@@ -0,0 +1,2041 @@
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{Arc, RwLock},
+	time::Duration,
+};
+
+use futures::{Stream, StreamExt, TryStreamExt};
+use reqwest::{
+	header::{HeaderMap, HeaderValue, CONTENT_TYPE, ETAG, LOCATION, RETRY_AFTER},
+	Method,
+};
+use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+use web_time::Instant;
+
+use crate::api::{
+	encode_segment, validate_language, AuthenticateRequest, AuthenticateResponse, HangarRequest,
+	HttpMethod, LatestReleaseRequest, LatestVersionRequest, ModerationQueue,
+	ModerationQueueRequest, PaginatedRequest, PermissionsRequest, PermissionsResponse,
+	ProjectMatch, ProjectRequest, ProjectsRequest, ProjectsResponse, VersionDownloadRequest,
+	VersionsRequest, VersionsResponse, BASE_API_URL,
+};
+use crate::auth::{ApiKey, AuthToken};
+use crate::backend::{
+	credential_hash, url_key, HttpBackend, HttpRequest, HttpResponse, ReqwestBackend,
+};
+use crate::backend::{MaybeSend, MaybeSync};
+use crate::cache::{CacheStore, MemoryCacheStore, ResponseCache};
+use crate::coalesce::{InFlight, Join};
+use crate::deprecation::{Deprecation, DeprecationHook};
+use crate::diff::StatsSnapshot;
+use crate::error::{Error, Result};
+use crate::etag::{EtagStore, Revalidation};
+use crate::handle::ProjectHandle;
+use crate::middleware::{Middleware, OnRequest, OnResponse};
+use crate::object::{
+	ByPlatform, Category, Channel, NamedPermission, Pagination, Platform, Project, ProjectStats,
+	ProjectsSort, ReviewState, Slug, Version,
+};
+use crate::rate_limit::RateLimit;
+use crate::resolve::{DependencyReport, ResolveOptions, Resolver};
+use crate::retry::RetryPolicy;
+
+/// The user agent sent when one isn't configured.
+pub const DEFAULT_USER_AGENT: &str = concat!("hangar-api-rs/", env!("CARGO_PKG_VERSION"));
+
+/// The most projects `HangarClient::leaderboard` gets.
+pub const MAX_LEADERBOARD: usize = 500;
+
+/// Client for sending requests to Hangar, generic over the http backend used to execute them.
+#[derive(Debug, Clone)]
+pub struct HangarClient<B = ReqwestBackend> {
+	backend: B,
+	config: ClientConfig,
+	/// Held while refreshing the auth token so concurrent requests only refresh it once
+	refresh_lock: Arc<futures::lock::Mutex<()>>,
+	/// The requests being sent, if identical requests sent at the same time share a response
+	in_flight: Option<Arc<InFlight>>,
+}
+
+impl Default for HangarClient {
+	fn default() -> Self {
+		Self::builder()
+			.build()
+			.expect("the default client configuration is valid")
+	}
+}
+
+impl HangarClient {
+	/// Creates a new client for the official Hangar instance.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new client for the official Hangar instance which authenticates with an api key, getting a new
+	/// token whenever the current one is about to expire.
+	pub fn with_api_key(api_key: impl Into<ApiKey>) -> Self {
+		Self::builder()
+			.api_key(api_key)
+			.build()
+			.expect("the default client configuration is valid")
+	}
+
+	/// Creates a builder for configuring a client.
+	pub fn builder() -> HangarClientBuilder {
+		HangarClientBuilder::default()
+	}
+}
+
+impl<B: HttpBackend> HangarClient<B> {
+	/// Gets the base url requests are sent to.
+	pub fn base_url(&self) -> &str {
+		&self.config.base_url
+	}
+
+	/// Gets the backend requests are executed with.
+	pub fn backend(&self) -> &B {
+		&self.backend
+	}
+
+	/// Exchanges an api key for a JWT, which is then sent with every following request to Hangar.
+	/// The token expires after a while, after which this needs to be called again. Use `HangarClient::with_api_key`
+	/// to have the client do that for you.
+	///
+	/// ```no_run
+	/// # async fn run() -> hangar_api::Result<()> {
+	/// use hangar_api::{api::ProjectsRequest, client::HangarClient, object::Pagination};
+	///
+	/// let client = HangarClient::new();
+	/// client.authenticate("your-api-key").await?;
+	/// // hidden projects the key's owner can see are now included
+	/// let request = ProjectsRequest::builder().pagination(Pagination::default()).build();
+	/// let projects = client.send(&request).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn authenticate(&self, api_key: impl Into<ApiKey>) -> Result<AuthToken> {
+		let request = AuthenticateRequest::builder().api_key(api_key).build();
+		let response = self
+			.execute(self.config.http_request(&request)?)
+			.await
+			.map_err(api_key_error)?;
+		Ok(self
+			.config
+			.store_session(AuthenticateRequest::parse_response(&response.body)?))
+	}
+
+	/// Gets the token currently used to authenticate requests.
+	pub fn auth_token(&self) -> Option<AuthToken> {
+		self.config.auth_token()
+	}
+
+	/// Sets or clears the token used to authenticate requests.
+	pub fn set_auth_token(&self, token: Option<AuthToken>) {
+		self.config.set_auth_token(token);
+	}
+
+	/// Gets the rate limit Hangar reported in the most recent response, shared between clones of the client.
+	/// See `rate_limit::REMAINING_HEADERS` for the headers it's read from.
+	pub fn rate_limit(&self) -> Option<RateLimit> {
+		self.config.rate_limit()
+	}
+
+	/// Sends a request to Hangar, returning its parsed response.
+	/// Dropping the future cancels the request, and any identical requests sharing its response send it themselves.
+	pub async fn send<R: HangarRequest>(&self, request: &R) -> Result<R::Response> {
+		Ok(self.send_with_meta(request).await?.body)
+	}
+
+	/// Sends a request to Hangar, returning its parsed response alongside metadata about the response.
+	pub async fn send_with_meta<R: HangarRequest>(
+		&self,
+		request: &R,
+	) -> Result<ResponseEnvelope<R::Response>> {
+		self.fetch(request, true).await
+	}
+
+	/// Sends a request to Hangar without checking the cache, storing the fresh response in it.
+	pub async fn send_uncached<R: HangarRequest>(&self, request: &R) -> Result<R::Response> {
+		Ok(self.fetch(request, false).await?.body)
+	}
+
+	/// Checks whether what a request asks for exists, like a project or version, without downloading it.
+	/// The request is sent as a `HEAD` request, or as a `GET` request if Hangar doesn't allow `HEAD` for it.
+	/// Gives `false` for a 404 and fails for any other error, so a missing permission isn't mistaken for something
+	/// missing. Only works for requests sent with `GET`.
+	pub async fn exists<R: HangarRequest>(&self, request: &R) -> Result<bool> {
+		let mut http = self.config.head_request(request)?;
+		self.refresh_auth().await?;
+		self.check_permission(request).await?;
+		let result = match self.execute(http.clone()).await {
+			Err(err) if head_unsupported(&err) => {
+				http.method = Method::GET;
+				self.execute(http).await
+			}
+			result => result,
+		};
+		exists_result(result)
+	}
+
+	/// Removes the cached response to a request, if there is one.
+	pub fn invalidate<R: HangarRequest>(&self, request: &R) {
+		self.config.invalidate(request);
+	}
+
+	/// Removes every cached response.
+	pub fn clear_cache(&self) {
+		self.config.clear_cache();
+	}
+
+	/// Removes every cached response about a project, like the project itself, its versions, pages, and stats, e.g.
+	/// after publishing a version of it. Searches which might include the project are kept.
+	pub fn invalidate_project(&self, slug: impl Into<Slug>) {
+		self.config.invalidate_project(&slug.into());
+	}
+
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument(
+			name = "hangar_request",
+			level = "debug",
+			skip_all,
+			fields(
+				endpoint = endpoint_name::<R>(),
+				path = %request.path(),
+				offset = pagination_offset(&request.query().unwrap_or_default()),
+				attempt = tracing::field::Empty,
+			)
+		)
+	)]
+	async fn fetch<R: HangarRequest>(
+		&self,
+		request: &R,
+		use_cache: bool,
+	) -> Result<ResponseEnvelope<R::Response>> {
+		request.validate()?;
+		let cache_key = self.config.cache_key(request);
+		if let Some(response) = self.config.cached(cache_key.as_deref(), use_cache) {
+			return parse_envelope::<R>(response);
+		}
+		self.refresh_auth().await?;
+		self.check_permission(request).await?;
+		let mut http = self.config.http_request(request)?;
+		let revalidation = self.config.revalidation(&mut http);
+		let response = self.execute_shared(http).await?;
+		self.config.finish::<R>(cache_key, revalidation, response)
+	}
+
+	/// Streams every item matched by a paginated request, fetching pages as needed.
+	/// The request's limit is used as the page size, starting from its offset. Dropping the stream cancels the page
+	/// being fetched, if any.
+	///
+	/// Items which move to a later page while paginating, e.g. projects gaining downloads when sorting by them, are
+	/// only yielded once, see `PaginatedRequest::item_key`. Items moving to an earlier page can't be noticed and
+	/// are missed.
+	pub fn paginate<'a, R: PaginatedRequest + 'a>(
+		&'a self,
+		request: R,
+	) -> impl Stream<Item = Result<R::Item>> + 'a {
+		let state = Some((request, HashSet::new()));
+		futures::stream::try_unfold(state, move |state| async move {
+			let Some((mut request, mut seen)) = state else {
+				return Ok::<_, Error>(None);
+			};
+			let (pagination, mut items) = R::into_page(self.send(&request).await?);
+			let more = request.advance(&pagination, items.len());
+			retain_unseen::<R>(&mut items, &mut seen);
+			Ok(Some((
+				futures::stream::iter(items.into_iter().map(Ok)),
+				more.then_some((request, seen)),
+			)))
+		})
+		.try_flatten()
+	}
+
+	/// Gets up to `n` items matched by a paginated request, starting from its offset and keeping its sort order.
+	/// Pages are fetched one after another, as large as Hangar allows, so this is the way to get more than
+	/// `Pagination::MAX_LIMIT` items at once. Fewer items are returned if fewer match.
+	pub async fn take<R: PaginatedRequest>(
+		&self,
+		mut request: R,
+		n: usize,
+	) -> Result<Vec<R::Item>> {
+		if n == 0 {
+			return Ok(Vec::new());
+		}
+		request.pagination_mut().limit = page_size(n);
+		self.paginate(request).take(n).try_collect().await
+	}
+
+	/// Gets the `n` most downloaded projects, optionally in a single category, most downloaded first.
+	/// At most `MAX_LEADERBOARD` projects are returned, fetched one page of `Pagination::MAX_LIMIT` after another,
+	/// so this sends one request per 25 projects. The pages are fetched at slightly different times, so projects
+	/// are sorted again once they're all fetched.
+	pub async fn leaderboard(&self, category: Option<Category>, n: usize) -> Result<Vec<Project>> {
+		let projects = self
+			.take(leaderboard_request(category), n.min(MAX_LEADERBOARD))
+			.await?;
+		Ok(rank_by_downloads(projects))
+	}
+
+	/// Lists the projects and versions waiting on moderation, for staff with the `reviewer` permission. See
+	/// `ModerationQueueRequest` for what this can find. Other users get `Error::MissingPermission`, whether or not
+	/// the client checks permissions. The versions of every scanned project are fetched at the same time.
+	pub async fn moderation_queue(
+		&self,
+		request: &ModerationQueueRequest,
+	) -> Result<ModerationQueue> {
+		let permissions = self.send(&PermissionsRequest::default()).await?;
+		require_permission(&permissions, NamedPermission::Reviewer)?;
+		let projects = self.take(request.scan_request(), request.scan).await?;
+		let versions = if request.versions {
+			futures::future::try_join_all(projects.iter().map(|project| async {
+				let versions = self
+					.send(&channels_request(&project.namespace.slug))
+					.await?;
+				Ok::<_, Error>((project.namespace.clone(), versions.result))
+			}))
+			.await?
+		} else {
+			Vec::new()
+		};
+		Ok(ModerationQueue::from_scan(projects, versions))
+	}
+
+	/// Gets a page of projects similar to `project`, leaving it out, see `ProjectsRequest::similar_to` for which
+	/// projects count as similar.
+	pub async fn similar(
+		&self,
+		project: &Project,
+		pagination: Pagination,
+	) -> Result<ProjectsResponse> {
+		let response = self
+			.send(&ProjectsRequest::similar_to(project, pagination))
+			.await?;
+		Ok(response.without(project))
+	}
+
+	/// Searches for a project by name with `ProjectsRequest::named`, telling apart a project named exactly that from
+	/// ones which only resemble it. Use `find_project_exact` when only an exact match will do.
+	pub async fn find_project(&self, name: &str) -> Result<ProjectMatch> {
+		let response = self.send(&ProjectsRequest::named(name)).await?;
+		Ok(ProjectMatch::from_results(name, response.result))
+	}
+
+	/// Gets the project named exactly `name` by its display name or slug, see `Project::is_named`, or `None` if
+	/// searching for it only turned up other projects.
+	pub async fn find_project_exact(&self, name: &str) -> Result<Option<Project>> {
+		Ok(self.find_project(name).await?.exact())
+	}
+
+	/// Streams every item matched by a paginated request like `paginate`, stopping once `token` is cancelled.
+	/// After cancelling, the next item polled is `Error::Cancelled` and the stream ends, even if the items of a page
+	/// which was already fetched haven't all been yielded. A page being fetched at the time is dropped along with
+	/// its connection, which leaves the client usable for other requests.
+	#[cfg(feature = "cancel")]
+	pub fn paginate_cancellable<'a, R: PaginatedRequest + 'a>(
+		&'a self,
+		request: R,
+		token: tokio_util::sync::CancellationToken,
+	) -> impl Stream<Item = Result<R::Item>> + 'a {
+		let items = Box::pin(self.paginate(request));
+		futures::stream::unfold(Some((items, token)), |state| async move {
+			let (mut items, token) = state?;
+			match token.run_until_cancelled(items.next()).await {
+				Some(Some(item)) => Some((item, Some((items, token)))),
+				Some(None) => None,
+				None => Some((Err(Error::Cancelled), None)),
+			}
+		})
+	}
+
+	/// Gets a handle for looking up things about a project without repeating its slug. This doesn't send anything,
+	/// and each method on the handle is a shorthand for sending one of the request types.
+	///
+	/// ```no_run
+	/// # async fn run() -> hangar_api::error::Result<()> {
+	/// use hangar_api::api::{ProjectRequest, VersionRequest};
+	/// use hangar_api::client::HangarClient;
+	/// use hangar_api::object::Platform;
+	///
+	/// let client = HangarClient::new();
+	///
+	/// // with request types
+	/// let project = client.send(&ProjectRequest::new("ViaVersion")).await?;
+	/// let version = client.send(&VersionRequest::new("ViaVersion", "5.0.0")).await?;
+	/// let file = client.download(&version, Platform::Paper).await?;
+	///
+	/// // with a handle
+	/// let via_version = client.project("ViaVersion");
+	/// let project = via_version.get().await?;
+	/// let version = via_version.version("5.0.0").await?;
+	/// let file = client.download(&version, Platform::Paper).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn project(&self, slug: impl Into<String>) -> ProjectHandle<'_, B> {
+		ProjectHandle::new(self, slug.into())
+	}
+
+	/// Gets a project along with a page of its versions, sending both requests at once.
+	pub async fn project_with_versions(
+		&self,
+		slug: impl Into<String>,
+		pagination: Pagination,
+	) -> Result<(Project, VersionsResponse)> {
+		let slug = slug.into();
+		let project = ProjectRequest::new(slug.clone());
+		let versions = VersionsRequest::builder()
+			.slug(slug)
+			.pagination(pagination)
+			.build();
+		futures::future::try_join(self.send(&project), self.send(&versions)).await
+	}
+
+	/// Gets the latest version of a project in its default release channel, looking up its name and then fetching
+	/// the full version. Gives `Error::NotFound` if the project has never released a version.
+	pub async fn latest_release_version(&self, slug: impl Into<String>) -> Result<Version> {
+		let request = LatestReleaseRequest::builder().slug(slug).build();
+		let name = self.send(&request).await?;
+		self.send(&request.version_request(name)).await
+	}
+
+	/// Gets the latest version of a project in a channel, looking up its name and then fetching the full version.
+	pub async fn latest_version(
+		&self,
+		slug: impl Into<String>,
+		channel: impl Into<String>,
+	) -> Result<Version> {
+		let request = LatestVersionRequest::builder()
+			.slug(slug)
+			.channel(channel)
+			.build();
+		let name = self.send(&request).await?;
+		self.send(&request.version_request(name)).await
+	}
+
+	/// Resolves the plugin dependencies of a version on a platform, following the dependencies of each Hangar
+	/// project found. Dependencies are looked up by name and resolved to the newest available version with a download
+	/// for the platform, while external dependencies are listed with their url without being followed.
+	pub async fn resolve_dependencies(
+		&self,
+		version: &Version,
+		platform: Platform,
+		options: ResolveOptions,
+	) -> Result<DependencyReport> {
+		let mut resolver = Resolver::new(version, platform, options);
+		while let Some((dependency, depth)) = resolver.next() {
+			let projects = self.send(&Resolver::project_request(&dependency)).await?;
+			let Some(slug) = resolver.find_project(&dependency, projects) else {
+				continue;
+			};
+			let versions = self.send(&resolver.versions_request(&slug)).await?;
+			resolver.resolve(dependency, depth, slug, versions);
+		}
+		Ok(resolver.finish())
+	}
+
+	/// Counts how many projects matching a search are in each category, e.g. for showing next to category filters.
+	/// Hangar doesn't return counts alongside results, so one request is sent per category, all at once.
+	pub async fn category_counts(
+		&self,
+		request: &ProjectsRequest,
+	) -> Result<HashMap<Category, i64>> {
+		let counts = Category::ALL.map(|category| async move {
+			let request = request.category_count_request(category);
+			Ok::<_, Error>((category, self.send(&request).await?.pagination.count))
+		});
+		Ok(futures::future::try_join_all(counts)
+			.await?
+			.into_iter()
+			.collect())
+	}
+
+	/// Counts how many projects matching a search support each platform. Like `category_counts`, one request is
+	/// sent per platform, all at once.
+	pub async fn platform_counts(
+		&self,
+		request: &ProjectsRequest,
+	) -> Result<HashMap<Platform, i64>> {
+		let counts = Platform::ALL.map(|platform| async move {
+			let request = request.platform_count_request(platform);
+			Ok::<_, Error>((platform, self.send(&request).await?.pagination.count))
+		});
+		Ok(futures::future::try_join_all(counts)
+			.await?
+			.into_iter()
+			.collect())
+	}
+
+	/// Gets the version to recommend downloading for each platform: the newest public, reviewed version with a
+	/// download for it. Platforms without such a version are left `None`. The platforms are looked up at once, each
+	/// paging through versions until one is found.
+	pub async fn promoted_versions(&self, slug: impl Into<String>) -> Result<ByPlatform<Version>> {
+		let slug = slug.into();
+		let promoted = |platform| {
+			let versions = self.paginate(promoted_request(&slug, platform));
+			async move {
+				futures::pin_mut!(versions);
+				while let Some(version) = versions.try_next().await? {
+					if is_promotable(&version, platform) {
+						return Ok(Some(version));
+					}
+				}
+				Ok::<_, Error>(None)
+			}
+		};
+		let (paper, waterfall, velocity) = futures::future::try_join3(
+			promoted(Platform::Paper),
+			promoted(Platform::Waterfall),
+			promoted(Platform::Velocity),
+		)
+		.await?;
+		Ok(ByPlatform {
+			paper,
+			waterfall,
+			velocity,
+		})
+	}
+
+	/// Gets the channels a project has published versions in, including hidden ones, ordered by when they were
+	/// created. Hangar has no endpoint listing channels, so they're collected from every version of the project,
+	/// which takes a request per `Pagination::MAX_LIMIT` versions.
+	pub async fn project_channels(&self, slug: impl Into<String>) -> Result<Vec<Channel>> {
+		let versions = self.paginate(channels_request(&slug.into()));
+		let channels = versions
+			.try_fold(Vec::new(), |mut channels, version| async move {
+				add_channel(&mut channels, version.channel);
+				Ok(channels)
+			})
+			.await?;
+		Ok(sort_channels(channels))
+	}
+
+	/// Gets every project a user maintains, sorted by a field in its default direction.
+	///
+	/// Searching for projects by `owner` misses the projects of organizations the user is in, since they're owned
+	/// by the organization, so this searches for the projects the user is a member of as well as the ones they
+	/// own, and merges them. Whether an owner is an organization is on its `User::is_organization`.
+	pub async fn projects_for_user(
+		&self,
+		username: impl Into<String>,
+		sort: ProjectsSort,
+	) -> Result<Vec<Project>> {
+		let (member, owned) = user_projects_requests(&username.into(), sort);
+		let (member, owned) = futures::future::try_join(
+			self.paginate(member).try_collect(),
+			self.paginate(owned).try_collect(),
+		)
+		.await?;
+		Ok(merge_user_projects(member, owned, sort))
+	}
+
+	/// Fetches a project again by the slug it was seen with before, noticing if it was renamed or is gone.
+	/// Fails with `Error::Gone` rather than `Error::NotFound` if it doesn't exist anymore. Responses aren't read
+	/// from the cache, so the project is up to date.
+	pub async fn refresh_project(&self, slug: impl Into<String>) -> Result<RefreshedProject> {
+		let slug = slug.into();
+		let project = self.send_uncached(&ProjectRequest::new(&slug)).await;
+		RefreshedProject::from_result(slug, project)
+	}
+
+	/// Fetches several projects again like `refresh_project`, all at once, giving a result for each slug in order.
+	/// One project being gone doesn't stop the others from being fetched.
+	pub async fn refresh_projects(
+		&self,
+		slugs: impl IntoIterator<Item = impl Into<String>>,
+	) -> Vec<Result<RefreshedProject>> {
+		futures::future::join_all(slugs.into_iter().map(|slug| self.refresh_project(slug))).await
+	}
+
+	/// Gets the stats of a project, like its download and star counts.
+	pub async fn project_stats(&self, slug: impl Into<Slug>) -> Result<ProjectStats> {
+		let request = ProjectRequest::new(slug);
+		Ok(self.send(&request).await?.stats)
+	}
+
+	/// Gets the stats of a project as a snapshot taken now, see `StatsSnapshot`.
+	pub async fn project_stats_snapshot(&self, slug: impl Into<Slug>) -> Result<StatsSnapshot> {
+		Ok(StatsSnapshot::now(self.project_stats(slug).await?))
+	}
+
+	/// Gets how many users have starred a project, without paging through its stargazers.
+	pub async fn star_count(&self, slug: impl Into<Slug>) -> Result<i64> {
+		Ok(self.project_stats(slug).await?.stars)
+	}
+
+	/// Gets how many users are watching a project, without paging through its watchers.
+	pub async fn watcher_count(&self, slug: impl Into<Slug>) -> Result<i64> {
+		Ok(self.project_stats(slug).await?.watchers)
+	}
+
+	/// Downloads the file of a version for the given platform.
+	/// External downloads are fetched from their external url rather than from Hangar.
+	/// Dropping the future stops the download and closes its connection.
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument(
+			name = "hangar_download",
+			level = "debug",
+			skip_all,
+			fields(version = %version.name, %platform, attempt = tracing::field::Empty)
+		)
+	)]
+	pub async fn download(&self, version: &Version, platform: Platform) -> Result<Vec<u8>> {
+		Ok(self.download_with_meta(version, platform).await?.bytes)
+	}
+
+	/// Downloads the file of a version like `download`, along with the url it was downloaded from after any
+	/// redirects.
+	pub async fn download_with_meta(
+		&self,
+		version: &Version,
+		platform: Platform,
+	) -> Result<DownloadedFile> {
+		let url = download_url(version, platform)?;
+		self.refresh_auth().await?;
+		let response = self
+			.execute(self.config.request(Method::GET, url, Vec::new()))
+			.await?;
+		Ok(response.into())
+	}
+
+	/// Downloads the file of a version of a project, trying each source of the strategy in turn until one works,
+	/// e.g. so provisioning doesn't fail just because an external host is down. Files hosted on Hangar are checked
+	/// against their sha256 hash with the `sha2` feature, and a file which doesn't match counts as a failed source.
+	/// Fails with the error of the last source if none work.
+	pub async fn download_mirrored(
+		&self,
+		slug: impl Into<Slug>,
+		version: &Version,
+		platform: Platform,
+		strategy: MirrorStrategy,
+	) -> Result<MirroredDownload> {
+		let slug = slug.into();
+		let mut failures = Vec::new();
+		for &source in strategy.sources() {
+			let request = mirror_request(&self.config, &slug, version, platform, source)?;
+			self.refresh_auth().await?;
+			let result = self.execute(request).await.and_then(|response| {
+				verify_download(version, platform, &response.body)?;
+				Ok(response)
+			});
+			match result {
+				Ok(response) => {
+					return Ok(MirroredDownload {
+						file: response.into(),
+						source,
+						failures,
+					});
+				}
+				Err(err) => failures.push((source, err)),
+			}
+		}
+		Err(failures
+			.pop()
+			.map(|(_, err)| err)
+			.expect("strategies have a source"))
+	}
+
+	/// Downloads the file of a version like `download`, calling `progress` with how many bytes were downloaded so
+	/// far and how many there are in total as the file comes in, e.g. to show a progress bar.
+	/// The total is the response's `Content-Length`, or the file's size Hangar knows for files hosted on it, and is
+	/// `None` for external downloads which don't say how large they are. If the download is retried, progress
+	/// starts over from 0.
+	pub async fn download_with_progress(
+		&self,
+		version: &Version,
+		platform: Platform,
+		mut progress: impl FnMut(u64, Option<u64>) + MaybeSend,
+	) -> Result<Vec<u8>> {
+		let url = download_url(version, platform)?;
+		let size = download_size(version, platform);
+		self.refresh_auth().await?;
+		let mut progress = |downloaded, total: Option<u64>| progress(downloaded, total.or(size));
+		Ok(self
+			.execute_with_progress(
+				self.config.request(Method::GET, url, Vec::new()),
+				Some(&mut progress),
+			)
+			.await?
+			.body)
+	}
+
+	/// Downloads the file of a version like `download`, giving up with `Error::Cancelled` if `token` is cancelled
+	/// first. Nothing is returned of a download which was cancelled halfway, and its connection is closed rather
+	/// than kept for other requests.
+	#[cfg(feature = "cancel")]
+	pub async fn download_cancellable(
+		&self,
+		version: &Version,
+		platform: Platform,
+		token: &tokio_util::sync::CancellationToken,
+	) -> Result<Vec<u8>> {
+		token
+			.run_until_cancelled(self.download(version, platform))
+			.await
+			.unwrap_or(Err(Error::Cancelled))
+	}
+
+	/// Gets a new auth token if the client has an api key and its current token is about to expire.
+	/// Only one refresh happens at a time, with requests waiting on it using the new token.
+	async fn refresh_auth(&self) -> Result<()> {
+		if self.config.refresh_key().is_none() {
+			return Ok(());
+		}
+		let _guard = self.refresh_lock.lock().await;
+		// another request may have refreshed the token while this one was waiting
+		if let Some(api_key) = self.config.refresh_key() {
+			self.authenticate(api_key.clone())
+				.await
+				.map_err(refresh_error)?;
+		}
+		Ok(())
+	}
+
+	/// Checks the user has the permission a request needs, fetching their permissions if they aren't known yet.
+	async fn check_permission<R: HangarRequest>(&self, request: &R) -> Result<()> {
+		let Some(check) = self.config.check_permission(request)? else {
+			return Ok(());
+		};
+		let permissions = check.request();
+		let response = self
+			.execute(self.config.http_request(&permissions)?)
+			.await?;
+		self.config
+			.finish_permission_check(check, PermissionsRequest::parse_response(&response.body)?)
+	}
+
+	/// Sends a prepared request, returning a successful response.
+	async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+		self.execute_with_progress(request, None::<&mut fn(u64, Option<u64>)>)
+			.await
+	}
+
+	/// Sends a request like `execute`, reporting the progress of receiving each attempt's body.
+	async fn execute_with_progress<F: FnMut(u64, Option<u64>) + MaybeSend>(
+		&self,
+		request: HttpRequest,
+		mut progress: Option<&mut F>,
+	) -> Result<HttpResponse> {
+		let mut attempts = Attempts::new(&self.config, &request);
+		loop {
+			attempts.start();
+			let start = Instant::now();
+			let result = self.attempt(request.clone(), progress.as_deref_mut()).await;
+			attempts.finished(&result, start.elapsed());
+			match result {
+				Ok(response) => return Ok(response),
+				Err(err) => sleep(attempts.failed(err)?).await,
+			}
+		}
+	}
+
+	/// Sends a prepared request like `execute`, sharing the response with identical `GET` requests sent at the
+	/// same time if the client coalesces requests.
+	async fn execute_shared(&self, request: HttpRequest) -> Result<HttpResponse> {
+		let in_flight = self.in_flight.as_deref();
+		let Some(in_flight) = in_flight.filter(|_| request.method == Method::GET) else {
+			return self.execute(request).await;
+		};
+		// requests in different languages or sent as different users can get different responses
+		let key = format!("{} {}", request.method, request.response_key());
+		match in_flight.join(key) {
+			Join::Lead(guard) => {
+				let result = self.execute(request).await;
+				guard.finish(&result);
+				result
+			}
+			Join::Wait(receiver) => match receiver.await {
+				Ok(result) => {
+					debug!("shared the response of an identical request");
+					result
+				}
+				// the request being waited on was cancelled
+				Err(_) => self.execute(request).await,
+			},
+		}
+	}
+
+	/// Makes a single attempt at sending a request, running it through the client's middleware.
+	async fn attempt<F: FnMut(u64, Option<u64>) + MaybeSend>(
+		&self,
+		mut request: HttpRequest,
+		progress: Option<&mut F>,
+	) -> Result<HttpResponse> {
+		if let Some(wait) = self.config.rate_limit_wait(&request) {
+			debug!(
+				?wait,
+				"no requests left, waiting for the rate limit to reset"
+			);
+			sleep(wait).await;
+		}
+		self.config.before_request(&mut request)?;
+		let api = self.config.is_api(&request);
+		let start = Instant::now();
+		let response = match progress {
+			Some(progress) => {
+				self.backend
+					.execute_with_progress(request, progress)
+					.await?
+			}
+			None => self.backend.execute(request).await?,
+		};
+		self.config.record_rate_limit(&response);
+		self.config.record_deprecation(&response);
+		self.config.after_response(&response, start.elapsed())?;
+		check_response(response, api)
+	}
+}
+
+/// Configuration shared by the async and blocking clients.
+#[derive(Clone)]
+pub(crate) struct ClientConfig {
+	pub(crate) base_url: String,
+	user_agent: String,
+	/// The value of the `Accept-Language` header sent to the api, unless a request has its own
+	language: Option<String>,
+	rate_limit_retries: u32,
+	retry: RetryPolicy,
+	middleware: Vec<Arc<dyn Middleware>>,
+	/// Shared between clones of a client so authenticating once applies to all of them
+	auth: Arc<RwLock<Option<AuthToken>>>,
+	api_key: Option<ApiKey>,
+	refresh_window: time::Duration,
+	etag_store: Option<Arc<dyn EtagStore>>,
+	cache: Option<ResponseCache>,
+	/// Shared between clones of a client like the auth token, since Hangar limits them together
+	rate_limit: Arc<RwLock<Option<RateLimit>>>,
+	wait_for_rate_limit: bool,
+	coalesce_requests: bool,
+	check_permissions: bool,
+	/// The user's permissions by the project they apply in, `None` for global permissions.
+	/// Shared between clones of a client like the auth token, and forgotten whenever the token changes.
+	permissions: Arc<RwLock<HashMap<Option<String>, Vec<NamedPermission>>>>,
+	deprecation_hook: Option<Arc<dyn DeprecationHook>>,
+}
+
+impl std::fmt::Debug for ClientConfig {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ClientConfig")
+			.field("base_url", &self.base_url)
+			.field("user_agent", &self.user_agent)
+			.field("language", &self.language)
+			.field("rate_limit_retries", &self.rate_limit_retries)
+			.field("retry", &self.retry)
+			.field("middleware", &self.middleware.len())
+			.field("authenticated", &self.auth_token().is_some())
+			.field("api_key", &self.api_key)
+			.field("refresh_window", &self.refresh_window)
+			.field("etag_store", &self.etag_store.is_some())
+			.field("cache", &self.cache.is_some())
+			.field("rate_limit", &self.rate_limit())
+			.field("wait_for_rate_limit", &self.wait_for_rate_limit)
+			.field("coalesce_requests", &self.coalesce_requests)
+			.field("check_permissions", &self.check_permissions)
+			.field("deprecation_hook", &self.deprecation_hook.is_some())
+			.finish()
+	}
+}
+
+impl Default for ClientConfig {
+	fn default() -> Self {
+		Self {
+			base_url: BASE_API_URL.to_string(),
+			user_agent: DEFAULT_USER_AGENT.to_string(),
+			language: None,
+			rate_limit_retries: 3,
+			retry: RetryPolicy::default(),
+			middleware: Vec::new(),
+			auth: Arc::default(),
+			api_key: None,
+			refresh_window: DEFAULT_REFRESH_WINDOW,
+			etag_store: None,
+			cache: None,
+			rate_limit: Arc::default(),
+			wait_for_rate_limit: false,
+			coalesce_requests: false,
+			check_permissions: false,
+			permissions: Arc::default(),
+			deprecation_hook: None,
+		}
+	}
+}
+
+impl ClientConfig {
+	/// Runs a request through every middleware before it's sent.
+	pub(crate) fn before_request(&self, request: &mut HttpRequest) -> Result<()> {
+		self.middleware
+			.iter()
+			.try_for_each(|middleware| middleware.on_request(request))
+	}
+
+	/// Runs a response through every middleware after it's received.
+	pub(crate) fn after_response(&self, response: &HttpResponse, elapsed: Duration) -> Result<()> {
+		self.middleware
+			.iter()
+			.try_for_each(|middleware| middleware.on_response(response, elapsed))
+	}
+
+	/// Creates a request with the headers sent on every request.
+	/// The auth token is only sent to the api, never to external download hosts.
+	pub(crate) fn request(
+		&self,
+		method: Method,
+		url: String,
+		query: Vec<(String, String)>,
+	) -> HttpRequest {
+		let mut headers = vec![("User-Agent".to_string(), self.user_agent.clone())];
+		if url.starts_with(&self.base_url) {
+			if let Some(token) = self.auth_token() {
+				headers.push((
+					"Authorization".to_string(),
+					format!("HangarAuth {}", token.token.expose()),
+				));
+			}
+		}
+		HttpRequest {
+			method,
+			url,
+			query,
+			headers,
+			body: None,
+		}
+	}
+
+	/// Checks whether a request is sent to the api, rather than e.g. an external download host.
+	pub(crate) fn is_api(&self, request: &HttpRequest) -> bool {
+		request.url.starts_with(&self.base_url)
+	}
+
+	/// Creates the `HEAD` request for checking whether what a `GET` request asks for exists.
+	pub(crate) fn head_request<R: HangarRequest>(&self, request: &R) -> Result<HttpRequest> {
+		if R::METHOD != HttpMethod::Get {
+			return Err(Error::InvalidRequest(
+				"only GET requests can be checked for existence".to_string(),
+			));
+		}
+		request.validate()?;
+		let mut http = self.http_request(request)?;
+		http.method = Method::HEAD;
+		Ok(http)
+	}
+
+	/// Creates the http request to send a request to Hangar with.
+	/// Built from `HangarRequest::to_parts`, so requests are sent the same way with or without a client.
+	pub(crate) fn http_request<R: HangarRequest>(&self, request: &R) -> Result<HttpRequest> {
+		let parts = request.to_parts(&self.base_url)?;
+		let mut http = self.request(parts.method.into(), parts.url, parts.query);
+		if let (None, Some(language)) = (request.language(), &self.language) {
+			http.headers
+				.push(("Accept-Language".to_string(), language.clone()));
+		}
+		http.headers.extend(parts.headers);
+		http.body = parts.body;
+		Ok(http)
+	}
+
+	/// Gets the language a request is sent with, its own or the client's.
+	fn language<'a, R: HangarRequest>(&'a self, request: &'a R) -> Option<&'a str> {
+		request.language().or(self.language.as_deref())
+	}
+
+	/// Stores the token returned from authenticating.
+	pub(crate) fn store_session(&self, response: AuthenticateResponse) -> AuthToken {
+		let token = AuthToken::from(response);
+		self.set_auth_token(Some(token.clone()));
+		token
+	}
+
+	/// Makes a request conditional on its stored `ETag`, if the client has a store.
+	pub(crate) fn revalidation(&self, request: &mut HttpRequest) -> Option<Revalidation> {
+		let store = self.etag_store.as_deref()?;
+		if request.method != Method::GET {
+			return None;
+		}
+		Some(Revalidation::start(store, request))
+	}
+
+	/// Finishes a possibly conditional request, handling `304 Not Modified` responses.
+	pub(crate) fn revalidated(
+		&self,
+		revalidation: Option<Revalidation>,
+		response: HttpResponse,
+	) -> Result<HttpResponse> {
+		match (revalidation, self.etag_store.as_deref()) {
+			(Some(revalidation), Some(store)) => revalidation.finish(store, response),
+			_ if response.status == 304 => Err(Error::NotModified),
+			_ => Ok(response),
+		}
+	}
+
+	/// Gets the key a request's response is cached under, if it's cacheable and the client has a cache. Requests
+	/// whose query can't be serialized aren't cached, and fail when they're sent instead.
+	pub(crate) fn cache_key<R: HangarRequest>(&self, request: &R) -> Option<String> {
+		if !R::CACHEABLE || self.cache.is_none() {
+			return None;
+		}
+		let key = format!(
+			"{} {}",
+			Method::from(R::METHOD),
+			url_key(
+				&request.url_with_base(&self.base_url),
+				&request.query().ok()?
+			)
+		);
+		// responses in different languages, or to different users, are cached separately
+		let key = match self.language(request) {
+			Some(language) => format!("{key} {language}"),
+			None => key,
+		};
+		Some(match self.auth_token() {
+			Some(token) => format!("{key} auth={}", credential_hash(token.token.expose())),
+			None => key,
+		})
+	}
+
+	/// Gets the cached response for a key, unless the cache is being bypassed.
+	pub(crate) fn cached(&self, key: Option<&str>, use_cache: bool) -> Option<HttpResponse> {
+		if !use_cache {
+			return None;
+		}
+		let response = self.cache.as_ref()?.get(key?)?;
+		debug!("serving cached response");
+		Some(response)
+	}
+
+	/// Finishes a request sent to Hangar, parsing its response and caching it if possible.
+	pub(crate) fn finish<R: HangarRequest>(
+		&self,
+		cache_key: Option<String>,
+		revalidation: Option<Revalidation>,
+		response: HttpResponse,
+	) -> Result<ResponseEnvelope<R::Response>> {
+		let response = self.revalidated(revalidation, response)?;
+		let (Some(key), Some(cache)) = (cache_key, &self.cache) else {
+			return parse_envelope::<R>(response);
+		};
+		let envelope = parse_envelope::<R>(response.clone())?;
+		cache.insert(key, response);
+		Ok(envelope)
+	}
+
+	pub(crate) fn invalidate<R: HangarRequest>(&self, request: &R) {
+		if let (Some(key), Some(cache)) = (self.cache_key(request), &self.cache) {
+			cache.remove(&key);
+		}
+	}
+
+	pub(crate) fn clear_cache(&self) {
+		if let Some(cache) = &self.cache {
+			cache.clear();
+		}
+	}
+
+	pub(crate) fn invalidate_project(&self, slug: &str) {
+		if let Some(cache) = &self.cache {
+			let slug = encode_segment(slug);
+			cache.retain(&|key| !is_project_key(key, &self.base_url, &slug));
+		}
+	}
+
+	/// Gets the api key to authenticate with if the current token is missing or about to expire.
+	pub(crate) fn refresh_key(&self) -> Option<&ApiKey> {
+		let api_key = self.api_key.as_ref()?;
+		match self.auth_token() {
+			Some(token) if !token.expires_within(self.refresh_window) => None,
+			_ => Some(api_key),
+		}
+	}
+
+	/// Remembers the rate limit reported by a response from the api. Responses from external download hosts are
+	/// ignored.
+	pub(crate) fn record_rate_limit(&self, response: &HttpResponse) {
+		if !response.url.starts_with(&self.base_url) {
+			return;
+		}
+		if let Some(rate_limit) = RateLimit::from_headers(response.status, &response.headers) {
+			*self.rate_limit.write().expect("lock poisoned") = Some(rate_limit);
+		}
+	}
+
+	/// Warns about the deprecation notices sent with a response from the api and passes them to the deprecation
+	/// hook. Each endpoint is only warned about once per process, whichever project it was for, while the hook sees
+	/// every response.
+	pub(crate) fn record_deprecation(&self, response: &HttpResponse) {
+		if !response.url.starts_with(&self.base_url) {
+			return;
+		}
+		let Some(deprecation) = Deprecation::from_headers(&response.headers) else {
+			return;
+		};
+		#[cfg(feature = "tracing")]
+		{
+			let endpoint = crate::deprecation::endpoint_template(&self.base_url, &response.url);
+			if crate::deprecation::first_sighting(&endpoint) {
+				tracing::warn!(
+					%endpoint,
+					deprecated = deprecation.deprecated,
+					sunset = ?deprecation.sunset,
+					warnings = ?deprecation.warnings,
+					"the hangar api sent deprecation notices for an endpoint"
+				);
+			}
+		}
+		if let Some(hook) = &self.deprecation_hook {
+			hook.notify(&response.url, &deprecation);
+		}
+	}
+
+	pub(crate) fn rate_limit(&self) -> Option<RateLimit> {
+		*self.rate_limit.read().expect("lock poisoned")
+	}
+
+	/// Gets how long to wait before sending a request to the api, if the client waits for the rate limit and no
+	/// requests are left.
+	pub(crate) fn rate_limit_wait(&self, request: &HttpRequest) -> Option<Duration> {
+		if !self.wait_for_rate_limit || !request.url.starts_with(&self.base_url) {
+			return None;
+		}
+		let rate_limit = self.rate_limit().filter(RateLimit::is_exhausted)?;
+		rate_limit.time_until_reset().filter(|wait| !wait.is_zero())
+	}
+
+	pub(crate) fn auth_token(&self) -> Option<AuthToken> {
+		self.auth.read().expect("lock poisoned").clone()
+	}
+
+	pub(crate) fn set_auth_token(&self, token: Option<AuthToken>) {
+		*self.auth.write().expect("lock poisoned") = token;
+		self.permissions.write().expect("lock poisoned").clear();
+	}
+
+	/// Checks the user has the permission a request needs, if the client checks permissions.
+	/// Unauthenticated clients are assumed to only have `view_public_info`. Gives a check to finish when the
+	/// user's permissions need to be fetched first.
+	pub(crate) fn check_permission<R: HangarRequest>(
+		&self,
+		request: &R,
+	) -> Result<Option<PermissionCheck>> {
+		let Some(permission) = R::REQUIRED_PERMISSION.filter(|_| self.check_permissions) else {
+			return Ok(None);
+		};
+		if self.auth_token().is_none() {
+			return match permission {
+				NamedPermission::ViewPublicInfo => Ok(None),
+				permission => Err(Error::MissingPermission { permission }),
+			};
+		}
+		let project = request.permission_project().map(str::to_string);
+		let check = PermissionCheck {
+			permission,
+			project,
+		};
+		match self
+			.permissions
+			.read()
+			.expect("lock poisoned")
+			.get(&check.project)
+		{
+			Some(permissions) => check.finish(permissions).map(|_| None),
+			None => Ok(Some(check)),
+		}
+	}
+
+	/// Remembers the user's permissions fetched for a check, then finishes it.
+	pub(crate) fn finish_permission_check(
+		&self,
+		check: PermissionCheck,
+		response: PermissionsResponse,
+	) -> Result<()> {
+		let result = check.finish(&response.permissions);
+		self.permissions
+			.write()
+			.expect("lock poisoned")
+			.insert(check.project, response.permissions);
+		result
+	}
+}
+
+/// A check of the user's permissions waiting on them to be fetched.
+pub(crate) struct PermissionCheck {
+	permission: NamedPermission,
+	project: Option<String>,
+}
+
+impl PermissionCheck {
+	/// Creates the request for the permissions the check needs.
+	pub(crate) fn request(&self) -> PermissionsRequest {
+		PermissionsRequest::builder()
+			.project(self.project.clone())
+			.build()
+	}
+
+	fn finish(&self, permissions: &[NamedPermission]) -> Result<()> {
+		if permissions.contains(&self.permission) {
+			Ok(())
+		} else {
+			Err(Error::MissingPermission {
+				permission: self.permission.clone(),
+			})
+		}
+	}
+}
+
+/// Tracks the attempts made at sending a request, deciding whether to retry it.
+/// Rate limited requests are retried after waiting as long as Hangar asks, and idempotent requests are retried
+/// according to the client's retry policy.
+pub(crate) struct Attempts<'a> {
+	config: &'a ClientConfig,
+	idempotent: bool,
+	attempts: u32,
+	rate_limited: u32,
+	retries: u32,
+	/// The kind of endpoint the request is for, used to label metrics
+	#[cfg(feature = "metrics")]
+	endpoint: &'static str,
+}
+
+impl<'a> Attempts<'a> {
+	pub(crate) fn new(config: &'a ClientConfig, request: &HttpRequest) -> Self {
+		Self {
+			config,
+			idempotent: request.method.is_idempotent(),
+			attempts: 0,
+			rate_limited: 0,
+			retries: 0,
+			#[cfg(feature = "metrics")]
+			endpoint: crate::metrics::endpoint(&config.base_url, &request.url),
+		}
+	}
+
+	/// Records the start of another attempt.
+	pub(crate) fn start(&mut self) {
+		self.attempts += 1;
+		#[cfg(feature = "tracing")]
+		tracing::Span::current().record("attempt", self.attempts);
+	}
+
+	/// Records the outcome of the current attempt in metrics, if enabled.
+	#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+	pub(crate) fn finished(&self, result: &Result<HttpResponse>, elapsed: Duration) {
+		#[cfg(feature = "metrics")]
+		crate::metrics::record_attempt(self.endpoint, result, elapsed);
+	}
+
+	/// Records that the current attempt is being retried.
+	fn retrying(&self) {
+		#[cfg(feature = "metrics")]
+		crate::metrics::record_retry(self.endpoint);
+	}
+
+	/// Decides what to do after an attempt failed, returning how long to wait before retrying or the error to give
+	/// up with.
+	pub(crate) fn failed(&mut self, err: Error) -> Result<Duration> {
+		match err {
+			Error::RateLimited { retry_after }
+				if self.rate_limited < self.config.rate_limit_retries =>
+			{
+				self.rate_limited += 1;
+				self.retrying();
+				let wait = retry_after.unwrap_or(DEFAULT_RATE_LIMIT_WAIT);
+				debug!(?wait, "rate limited, waiting before retrying");
+				Ok(wait)
+			}
+			err if self.idempotent
+				&& self.retries + 1 < self.config.retry.max_attempts
+				&& self.config.retry.is_retryable(&err) =>
+			{
+				self.retries += 1;
+				self.retrying();
+				let delay = self.config.retry.delay(self.retries);
+				debug!(?delay, %err, "request failed, retrying");
+				Ok(delay)
+			}
+			// only transient errors are wrapped, so running out of rate limit retries still gives
+			// `RateLimited` and an answer like `NotFound` after a retried 503 is returned as is
+			err if self.retries > 0 && self.config.retry.is_retryable(&err) => {
+				Err(Error::Retried {
+					attempts: self.attempts,
+					source: Box::new(err),
+				})
+			}
+			err => Err(err),
+		}
+	}
+}
+
+/// Turns authenticate being rejected into an error saying the api key is invalid.
+pub(crate) fn api_key_error(err: Error) -> Error {
+	match err {
+		Error::Unauthorized { message } => Error::InvalidApiKey { message },
+		err => err,
+	}
+}
+
+/// Wraps an error from refreshing the auth token. An invalid api key is returned as is, since refreshing again
+/// won't help.
+pub(crate) fn refresh_error(err: Error) -> Error {
+	match err {
+		err @ Error::InvalidApiKey { .. } => err,
+		err => Error::AuthRefresh {
+			source: Box::new(err),
+		},
+	}
+}
+
+/// Turns error statuses into errors. `304 Not Modified` is left for conditional requests to handle.
+/// Responses from the api which are html pages give `Error::Unavailable` whatever their status.
+pub(crate) fn check_response(response: HttpResponse, api: bool) -> Result<HttpResponse> {
+	if api && is_html(&response) {
+		return Err(Error::Unavailable {
+			status: response.status,
+			snippet: html_snippet(&response.body),
+		});
+	}
+	match response.status {
+		429 => Err(Error::RateLimited {
+			retry_after: response
+				.headers
+				.get(RETRY_AFTER)
+				.and_then(|v| v.to_str().ok())
+				.and_then(parse_retry_after),
+		}),
+		200..=299 | 304 => Ok(response),
+		status @ 300..=399 => Err(Error::Redirected {
+			status,
+			location: response
+				.headers
+				.get(LOCATION)
+				.and_then(|v| v.to_str().ok())
+				.map(str::to_string),
+		}),
+		status => Err(Error::from_response(status, &response.body)),
+	}
+}
+
+/// Checks whether a response is an html page, going by its `Content-Type` or the start of its body if it has none.
+fn is_html(response: &HttpResponse) -> bool {
+	if let Some(content_type) = response.headers.get(CONTENT_TYPE) {
+		return content_type
+			.to_str()
+			.is_ok_and(|value| value.trim().to_ascii_lowercase().starts_with("text/html"));
+	}
+	let start = response.body.trim_ascii_start();
+	let start = start[..start.len().min(14)].to_ascii_lowercase();
+	start.starts_with(b"<!doctype html") || start.starts_with(b"<html")
+}
+
+/// Gets the start of an html page for an error, with its whitespace collapsed.
+fn html_snippet(body: &[u8]) -> String {
+	const MAX_CHARS: usize = 200;
+	let text = String::from_utf8_lossy(&body[..body.len().min(4 * MAX_CHARS)]);
+	let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+	match text.char_indices().nth(MAX_CHARS) {
+		Some((end, _)) => format!("{}...", &text[..end]),
+		None => text,
+	}
+}
+
+/// Checks whether a cache key is for a request about a project, given its slug as encoded in urls. Slugs are
+/// compared ignoring ascii case like Hangar does.
+fn is_project_key(key: &str, base_url: &str, slug: &str) -> bool {
+	let Some(path) = key
+		.split(' ')
+		.nth(1)
+		.and_then(|url| url.strip_prefix(base_url))
+	else {
+		return false;
+	};
+	let path = path.split('?').next().unwrap_or_default();
+	let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+	match segments.as_slice() {
+		["projects", project, ..] | ["pages", "main" | "page", project, ..] => {
+			project.eq_ignore_ascii_case(slug)
+		}
+		_ => false,
+	}
+}
+
+/// Checks whether an error from a `HEAD` request means Hangar doesn't allow `HEAD` for the endpoint.
+pub(crate) fn head_unsupported(err: &Error) -> bool {
+	matches!(
+		err,
+		Error::Api {
+			status: 405 | 501,
+			..
+		}
+	)
+}
+
+/// Maps the response to an existence check, see `HangarClient::exists`.
+pub(crate) fn exists_result(result: Result<HttpResponse>) -> Result<bool> {
+	match result {
+		Ok(_) => Ok(true),
+		Err(err) if err.is_not_found() => Ok(false),
+		Err(err) => Err(err),
+	}
+}
+
+/// Parses the body of a successful response for a request.
+pub(crate) fn parse_envelope<R: HangarRequest>(
+	response: HttpResponse,
+) -> Result<ResponseEnvelope<R::Response>> {
+	let body = R::parse_response(&response.body);
+	#[cfg(feature = "tracing")]
+	if let Err(err) = &body {
+		tracing::debug!(
+			url = %response.url,
+			body = %truncated_body(&response.body),
+			%err,
+			"failed to deserialize response"
+		);
+	}
+	Ok(ResponseEnvelope {
+		body: body?,
+		status: response.status,
+		url: response.url,
+		headers: response.headers,
+	})
+}
+
+/// Creates the request for the versions of a project which might be promoted for a platform, newest first.
+pub(crate) fn promoted_request(slug: &str, platform: Platform) -> VersionsRequest {
+	VersionsRequest::builder()
+		.slug(slug)
+		.pagination(Pagination::page(0, Pagination::MAX_LIMIT))
+		.platform(platform)
+		.build()
+}
+
+/// Checks whether a version can be promoted for a platform.
+pub(crate) fn is_promotable(version: &Version, platform: Platform) -> bool {
+	version.is_available()
+		&& version.review_state == ReviewState::Reviewed
+		&& version.download_for(platform).is_some()
+}
+
+/// Creates the request for every version of a project in any channel, for finding the project's channels.
+pub(crate) fn channels_request(slug: &str) -> VersionsRequest {
+	VersionsRequest::builder()
+		.slug(slug)
+		.pagination(Pagination::page(0, Pagination::MAX_LIMIT))
+		.include_hidden_channels(true)
+		.build()
+}
+
+/// Creates the request for the most downloaded projects, optionally in a single category.
+pub(crate) fn leaderboard_request(category: Option<Category>) -> ProjectsRequest {
+	ProjectsRequest::builder()
+		.pagination(Pagination::page(0, Pagination::MAX_LIMIT))
+		.sort(ProjectsSort::Downloads)
+		.categories(Vec::from_iter(category))
+		.build()
+}
+
+/// Orders projects by their downloads, most first, keeping the order of projects with as many downloads.
+pub(crate) fn rank_by_downloads(mut projects: Vec<Project>) -> Vec<Project> {
+	projects.sort_by_key(|project| std::cmp::Reverse(project.stats.downloads));
+	projects
+}
+
+/// Checks a user has a permission, for methods which need it whether or not the client checks permissions.
+pub(crate) fn require_permission(
+	permissions: &PermissionsResponse,
+	permission: NamedPermission,
+) -> Result<()> {
+	if permissions.permissions.contains(&permission) {
+		Ok(())
+	} else {
+		Err(Error::MissingPermission { permission })
+	}
+}
+
+/// Creates the requests for the projects a user is a member of and the projects they own, sorted by a field.
+pub(crate) fn user_projects_requests(
+	username: &str,
+	sort: ProjectsSort,
+) -> (ProjectsRequest, ProjectsRequest) {
+	let request = |member: Option<&str>, owner: Option<&str>| {
+		ProjectsRequest::builder()
+			.pagination(Pagination::page(0, Pagination::MAX_LIMIT))
+			.sort(sort)
+			.member(member.map(str::to_string))
+			.owner(owner.map(str::to_string))
+			.build()
+	};
+	(request(Some(username), None), request(None, Some(username)))
+}
+
+/// Merges the projects a user is a member of with the projects they own, dropping the ones in both and keeping
+/// the order Hangar sorted them in.
+pub(crate) fn merge_user_projects(
+	member: Vec<Project>,
+	owned: Vec<Project>,
+	sort: ProjectsSort,
+) -> Vec<Project> {
+	let mut seen = HashSet::new();
+	let mut projects: Vec<Project> = member
+		.into_iter()
+		.chain(owned)
+		.filter(|project| seen.insert(project.namespace.key()))
+		.collect();
+	// both lists are already sorted, and the stable sort keeps Hangar's order for ties
+	projects.sort_by(|a, b| sort.compare(a, b, sort.default_direction()));
+	projects
+}
+
+/// Adds a channel to the channels found so far, unless one with the same name was already found.
+pub(crate) fn add_channel(channels: &mut Vec<Channel>, channel: Channel) {
+	if !channels.iter().any(|found| found.name == channel.name) {
+		channels.push(channel);
+	}
+}
+
+/// Orders channels by when they were created, then by name, so the order doesn't depend on the versions.
+pub(crate) fn sort_channels(mut channels: Vec<Channel>) -> Vec<Channel> {
+	channels.sort_by(|a, b| {
+		a.created_at
+			.cmp(&b.created_at)
+			.then_with(|| a.name.cmp(&b.name))
+	});
+	channels
+}
+
+/// Removes the items of a page which were already seen on an earlier page, remembering the rest.
+pub(crate) fn retain_unseen<R: PaginatedRequest>(
+	items: &mut Vec<R::Item>,
+	seen: &mut HashSet<String>,
+) {
+	items.retain(|item| R::item_key(item).is_none_or(|key| seen.insert(key)));
+}
+
+/// Gets the page size to fetch `n` items with, as large as Hangar allows.
+pub(crate) fn page_size(n: usize) -> i64 {
+	i64::try_from(n).map_or(Pagination::MAX_LIMIT, |n| n.min(Pagination::MAX_LIMIT))
+}
+
+/// Gets the url to download a version for the given platform from.
+pub(crate) fn download_url(version: &Version, platform: Platform) -> Result<String> {
+	version
+		.downloads
+		.get(platform)
+		.map(|downloads| downloads.url().to_string())
+		.ok_or(Error::NoDownload(platform))
+}
+
+/// Gets the size of the file to download a version for the given platform, if it's hosted on Hangar.
+pub(crate) fn download_size(version: &Version, platform: Platform) -> Option<u64> {
+	let file_info = version.download_for(platform)?.file_info()?;
+	u64::try_from(file_info.size_bytes).ok()
+}
+
+/// A project fetched again by the slug it was seen with before, see `HangarClient::refresh_project`.
+#[derive(Debug, Clone)]
+pub struct RefreshedProject {
+	/// The project as it is now
+	pub project: Project,
+	/// The slug the project was fetched with, if the project has a different slug now
+	pub previous_slug: Option<String>,
+}
+
+impl RefreshedProject {
+	/// Maps the result of fetching a project by a slug seen before, turning `NotFound` into `Error::Gone`.
+	pub(crate) fn from_result(slug: String, project: Result<Project>) -> Result<Self> {
+		let project = match project {
+			Ok(project) => project,
+			Err(err) if err.is_not_found() => return Err(Error::Gone { slug }),
+			Err(err) => return Err(err),
+		};
+		let previous_slug = (!project.namespace.slug.eq_ignore_ascii_case(&slug)).then_some(slug);
+		Ok(Self {
+			project,
+			previous_slug,
+		})
+	}
+
+	/// Checks whether the project has a different slug than it was fetched with.
+	pub fn is_renamed(&self) -> bool {
+		self.previous_slug.is_some()
+	}
+}
+
+/// A parsed response along with metadata about the http response it came from.
+#[derive(Debug, Clone)]
+pub struct ResponseEnvelope<T> {
+	/// The parsed body of the response
+	pub body: T,
+	/// The http status code of the response
+	pub status: u16,
+	/// The final url of the response, after any redirects
+	pub url: String,
+	/// The headers of the response, moved out of the response rather than copied
+	pub headers: HeaderMap,
+}
+
+impl<T> ResponseEnvelope<T> {
+	/// Gets the value of a header, if present and valid utf-8.
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers.get(name).and_then(|v| v.to_str().ok())
+	}
+
+	/// Gets the response's `ETag` header.
+	pub fn etag(&self) -> Option<&str> {
+		self.headers.get(ETAG).and_then(|v| v.to_str().ok())
+	}
+
+	/// Gets the deprecation notices sent with the response, if the endpoint is deprecated or going away.
+	pub fn deprecation(&self) -> Option<Deprecation> {
+		Deprecation::from_headers(&self.headers)
+	}
+}
+
+/// A file downloaded from Hangar or an external host.
+#[derive(Debug, Clone)]
+pub struct DownloadedFile {
+	/// The contents of the file
+	pub bytes: Vec<u8>,
+	/// The url the file was downloaded from, after any redirects, e.g. to show which cdn or mirror it came from
+	pub url: String,
+}
+
+impl From<HttpResponse> for DownloadedFile {
+	fn from(value: HttpResponse) -> Self {
+		Self {
+			bytes: value.body,
+			url: value.url,
+		}
+	}
+}
+
+/// Where a download can be fetched from, see `MirrorStrategy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadSource {
+	/// Hangar's download endpoint, as requested with a `VersionDownloadRequest`. Hangar serves files uploaded to it
+	/// and redirects external downloads to their external url.
+	Hangar,
+	/// The url given in the version's downloads, which is the external url for external downloads and Hangar's cdn
+	/// otherwise
+	Direct,
+}
+
+/// Which sources `HangarClient::download_mirrored` tries, and in which order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MirrorStrategy {
+	/// Tries Hangar's download endpoint, falling back to the version's own url
+	#[default]
+	PreferHangar,
+	/// Tries the version's own url, falling back to Hangar's download endpoint
+	PreferDirect,
+	/// Only downloads through Hangar's download endpoint, never fetching the version's own url directly
+	HangarOnly,
+}
+
+impl MirrorStrategy {
+	/// Gets the sources to try, in order.
+	pub fn sources(self) -> &'static [DownloadSource] {
+		match self {
+			Self::PreferHangar => &[DownloadSource::Hangar, DownloadSource::Direct],
+			Self::PreferDirect => &[DownloadSource::Direct, DownloadSource::Hangar],
+			Self::HangarOnly => &[DownloadSource::Hangar],
+		}
+	}
+}
+
+/// A file downloaded by `HangarClient::download_mirrored`.
+#[derive(Debug, Clone)]
+pub struct MirroredDownload {
+	/// The downloaded file
+	pub file: DownloadedFile,
+	/// The source the file was downloaded from
+	pub source: DownloadSource,
+	/// The sources which were tried first and failed, with why they failed
+	pub failures: Vec<(DownloadSource, Error)>,
+}
+
+/// Creates the request to download a version from a source.
+pub(crate) fn mirror_request(
+	config: &ClientConfig,
+	slug: &str,
+	version: &Version,
+	platform: Platform,
+	source: DownloadSource,
+) -> Result<HttpRequest> {
+	let url = download_url(version, platform)?;
+	Ok(match source {
+		DownloadSource::Hangar => config.http_request(
+			&VersionDownloadRequest::builder()
+				.slug(slug.to_string())
+				.name(version.name.clone())
+				.platform(platform)
+				.build(),
+		)?,
+		DownloadSource::Direct => config.request(Method::GET, url, Vec::new()),
+	})
+}
+
+/// Checks a downloaded file matches the size and sha256 hash Hangar has for it. External downloads can't be checked.
+#[cfg(feature = "sha2")]
+pub(crate) fn verify_download(version: &Version, platform: Platform, bytes: &[u8]) -> Result<()> {
+	match version.download_for(platform).and_then(|d| d.file_info()) {
+		Some(file_info) => Ok(file_info.verify(bytes)?),
+		None => Ok(()),
+	}
+}
+
+/// Without the `sha2` feature downloads can't be checked.
+#[cfg(not(feature = "sha2"))]
+pub(crate) fn verify_download(
+	_version: &Version,
+	_platform: Platform,
+	_bytes: &[u8],
+) -> Result<()> {
+	Ok(())
+}
+
+/// Gets the name of a request type without its module path, for use in spans.
+#[cfg(feature = "tracing")]
+pub(crate) fn endpoint_name<R>() -> &'static str {
+	let name = std::any::type_name::<R>();
+	name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Gets the pagination offset from a request's query, if it has one.
+#[cfg(feature = "tracing")]
+pub(crate) fn pagination_offset(query: &[(String, String)]) -> Option<&str> {
+	query
+		.iter()
+		.find(|(key, _)| key == "offset")
+		.map(|(_, value)| value.as_str())
+}
+
+/// Truncates a response body for logging.
+#[cfg(feature = "tracing")]
+fn truncated_body(body: &[u8]) -> String {
+	const MAX_LEN: usize = 512;
+	let mut text = String::from_utf8_lossy(&body[..body.len().min(MAX_LEN)]).into_owned();
+	if body.len() > MAX_LEN {
+		text.push_str("...");
+	}
+	text
+}
+
+/// How long before a token expires that clients with an api key get a new one by default.
+const DEFAULT_REFRESH_WINDOW: time::Duration = time::Duration::seconds(30);
+
+/// How long to wait after being rate limited when Hangar doesn't send a `Retry-After` header.
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(1);
+
+/// Waits for the given duration, using a timer which works on the current target.
+pub(crate) async fn sleep(duration: Duration) {
+	#[cfg(not(target_arch = "wasm32"))]
+	tokio::time::sleep(duration).await;
+	#[cfg(target_arch = "wasm32")]
+	gloo_timers::future::sleep(duration).await;
+}
+
+/// Parses a `Retry-After` header in either its delta-seconds or http-date form.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+	if let Ok(seconds) = value.trim().parse() {
+		return Some(Duration::from_secs(seconds));
+	}
+	let date = OffsetDateTime::parse(value.trim(), &Rfc2822).ok()?;
+	Some(
+		(date - OffsetDateTime::now_utc())
+			.try_into()
+			.unwrap_or_default(),
+	)
+}
+
+/// Builder for a `HangarClient`.
+#[derive(Debug, Default)]
+pub struct HangarClientBuilder {
+	config: ClientConfig,
+	reqwest: ReqwestConfig,
+}
+
+/// Settings for the reqwest client a builder creates, unset ones keeping reqwest's defaults.
+#[derive(Debug, Clone, Copy, Default)]
+struct ReqwestConfig {
+	max_idle_per_host: Option<usize>,
+	idle_timeout: Option<Option<Duration>>,
+	redirect: RedirectPolicy,
+}
+
+/// Whether a client follows redirects, like the ones from Hangar's download endpoint to where files are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectPolicy {
+	/// Follow up to this many redirects for each request, failing with `Error::Http` after that
+	Limited(usize),
+	/// Never follow redirects, failing with `Error::Redirected` instead
+	None,
+}
+
+impl RedirectPolicy {
+	/// How many redirects are followed by default, which is reqwest's default.
+	pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+	#[cfg(not(target_arch = "wasm32"))]
+	fn to_reqwest(self) -> reqwest::redirect::Policy {
+		match self {
+			Self::Limited(max) => reqwest::redirect::Policy::limited(max),
+			Self::None => reqwest::redirect::Policy::none(),
+		}
+	}
+}
+
+/// Follows up to `DEFAULT_MAX_REDIRECTS` redirects.
+impl Default for RedirectPolicy {
+	fn default() -> Self {
+		Self::Limited(Self::DEFAULT_MAX_REDIRECTS)
+	}
+}
+
+impl ReqwestConfig {
+	#[cfg(not(target_arch = "wasm32"))]
+	fn apply(self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+		builder = builder.redirect(self.redirect.to_reqwest());
+		if let Some(max) = self.max_idle_per_host {
+			builder = builder.pool_max_idle_per_host(max);
+		}
+		if let Some(timeout) = self.idle_timeout {
+			builder = builder.pool_idle_timeout(timeout);
+		}
+		builder
+	}
+
+	/// The browser manages connections and follows redirects on wasm.
+	#[cfg(target_arch = "wasm32")]
+	fn apply(self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+		builder
+	}
+
+	#[cfg(feature = "blocking")]
+	fn apply_blocking(
+		self,
+		mut builder: reqwest::blocking::ClientBuilder,
+	) -> reqwest::blocking::ClientBuilder {
+		builder = builder.redirect(self.redirect.to_reqwest());
+		if let Some(max) = self.max_idle_per_host {
+			builder = builder.pool_max_idle_per_host(max);
+		}
+		if let Some(timeout) = self.idle_timeout {
+			builder = builder.pool_idle_timeout(timeout);
+		}
+		builder
+	}
+}
+
+impl HangarClientBuilder {
+	/// Sets the base url of the api, for use with self-hosted Hangar instances.
+	/// This should include any path prefix, e.g. `https://example.com/hangar/api/v1`.
+	pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+		self.config.base_url = base_url.into().trim_end_matches('/').to_string();
+		self
+	}
+
+	/// Sets the user agent sent with every request, including downloads.
+	/// Defaults to `DEFAULT_USER_AGENT`.
+	pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+		self.config.user_agent = user_agent.into();
+		self
+	}
+
+	/// Sets the language requests ask Hangar to respond in with the `Accept-Language` header, like `de` or
+	/// `de-DE, en;q=0.5`, falling back to Hangar's default for anything it doesn't have in that language.
+	/// Use `HangarRequest::with_language` to override it for a request. Hangar doesn't localize any of its
+	/// responses yet, so this currently changes nothing about them.
+	pub fn language(mut self, language: impl Into<String>) -> Self {
+		self.config.language = Some(language.into());
+		self
+	}
+
+	/// Sets how many times a rate limited request is retried before returning `Error::RateLimited`.
+	/// Defaults to 3, set to 0 to handle rate limiting yourself.
+	pub fn rate_limit_retries(mut self, retries: u32) -> Self {
+		self.config.rate_limit_retries = retries;
+		self
+	}
+
+	/// Sets the policy for retrying idempotent requests after transient failures.
+	/// Defaults to `RetryPolicy::default()`, use `RetryPolicy::none()` to disable retries.
+	pub fn retry(mut self, retry: RetryPolicy) -> Self {
+		self.config.retry = retry;
+		self
+	}
+
+	/// Sets an api key to authenticate with. The client gets a token before its first request and a new one
+	/// whenever the current token is about to expire, failing requests with `Error::AuthRefresh` if it can't, or
+	/// `Error::InvalidApiKey` if Hangar rejects the key.
+	pub fn api_key(mut self, api_key: impl Into<ApiKey>) -> Self {
+		self.config.api_key = Some(api_key.into());
+		self
+	}
+
+	/// Sets whether requests wait for the rate limit to reset when Hangar said no requests are left, rather than
+	/// being sent only to be rate limited. Defaults to false.
+	pub fn wait_for_rate_limit(mut self, wait: bool) -> Self {
+		self.config.wait_for_rate_limit = wait;
+		self
+	}
+
+	/// Sets whether requests are checked against the user's permissions before they're sent, failing with
+	/// `Error::MissingPermission` rather than being rejected by Hangar. The permissions are fetched once per
+	/// project and remembered until the auth token changes. Hangar allows anonymous access to some requests which
+	/// nominally need a permission, so this is off by default.
+	pub fn check_permissions(mut self, check: bool) -> Self {
+		self.config.check_permissions = check;
+		self
+	}
+
+	/// Sets whether identical `GET` requests sent at the same time share one response, rather than each being sent
+	/// to Hangar. Every caller gets the same result, including errors. Only the async client coalesces requests.
+	/// Defaults to false.
+	pub fn coalesce_requests(mut self, coalesce: bool) -> Self {
+		self.config.coalesce_requests = coalesce;
+		self
+	}
+
+	/// Enables an in-memory cache of responses to read-only requests like `ProjectRequest`, shared between clones
+	/// of the client. Requests are answered from the cache without touching the network until their response is
+	/// older than `ttl`, unlike `etag_store` which still asks Hangar whether the response changed. The least
+	/// recently used response is dropped once `max_entries` are stored.
+	/// Use `send_uncached`, `invalidate` and `invalidate_project` to get around it.
+	pub fn cache(self, ttl: Duration, max_entries: usize) -> Self {
+		self.cache_store(ttl, MemoryCacheStore::new(max_entries))
+	}
+
+	/// Enables a cache of responses to read-only requests kept in the given store, e.g. a `DiskCacheStore` to keep
+	/// them between runs. Responses are served from it until they're older than `ttl`.
+	pub fn cache_store(mut self, ttl: Duration, store: impl CacheStore + 'static) -> Self {
+		self.config.cache = Some(ResponseCache::new(ttl, store));
+		self
+	}
+
+	/// Sets how long before the auth token expires that a client with an api key gets a new one. Defaults to 30
+	/// seconds.
+	pub fn refresh_window(mut self, window: Duration) -> Self {
+		self.config.refresh_window = window.try_into().unwrap_or(time::Duration::MAX);
+		self
+	}
+
+	/// Sets the store used to remember the `ETag`s of responses, making repeated requests conditional with
+	/// `If-None-Match`. Unchanged responses are served from the store, or give `Error::NotModified` if it doesn't
+	/// keep bodies. See `MemoryEtagStore` for a bounded in-memory store.
+	pub fn etag_store(mut self, store: impl EtagStore + 'static) -> Self {
+		self.config.etag_store = Some(Arc::new(store));
+		self
+	}
+
+	/// Adds middleware which hooks into every request the client sends. Middleware runs in registration order, once
+	/// per attempt.
+	pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+		self.config.middleware.push(Arc::new(middleware));
+		self
+	}
+
+	/// Sets a hook run with the deprecation notices of every response from the api which has any, given the
+	/// response's url, e.g. to alert before deprecated endpoints stop working. With the `tracing` feature each
+	/// deprecated endpoint is also warned about the first time it's seen.
+	pub fn on_deprecation(
+		mut self,
+		hook: impl Fn(&str, &Deprecation) + MaybeSend + MaybeSync + 'static,
+	) -> Self {
+		self.config.deprecation_hook = Some(Arc::new(hook));
+		self
+	}
+
+	/// Adds a hook run before every request is sent, which can modify the request or fail it with an error.
+	pub fn on_request(
+		self,
+		hook: impl Fn(&mut HttpRequest) -> Result<()> + MaybeSend + MaybeSync + 'static,
+	) -> Self {
+		self.middleware(OnRequest(hook))
+	}
+
+	/// Adds a hook run after every response is received, given the time the request took.
+	pub fn on_response(
+		self,
+		hook: impl Fn(&HttpResponse, Duration) -> Result<()> + MaybeSend + MaybeSync + 'static,
+	) -> Self {
+		self.middleware(OnResponse(hook))
+	}
+
+	/// Sets how many idle connections are kept open to each host, e.g. the api and the cdn files are downloaded
+	/// from. Defaults to reqwest's default, which keeps any number of them.
+	///
+	/// Idle connections are what's reused for the next request, so keeping a few saves connecting again, but each
+	/// takes up a file descriptor. This doesn't limit how many connections are open while requests are being sent,
+	/// which is the number of requests sent at once, so bound that too when sending lots of requests. For
+	/// downloading hundreds of files, something like 8 idle connections per host with a 30 second idle timeout and
+	/// 8 downloads at once works well without overwhelming the cdn.
+	///
+	/// Only applies to clients built with `build` or `build_blocking`, and not on wasm, where the browser manages
+	/// connections.
+	pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+		self.reqwest.max_idle_per_host = Some(max);
+		self
+	}
+
+	/// Sets how long idle connections are kept open before they're closed, or `None` to keep them open until the
+	/// host closes them. Defaults to reqwest's default of 90 seconds. See `pool_max_idle_per_host` for when it
+	/// applies.
+	pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+		self.reqwest.idle_timeout = Some(timeout);
+		self
+	}
+
+	/// Sets whether the client follows redirects, which it does up to `RedirectPolicy::DEFAULT_MAX_REDIRECTS` times
+	/// per request by default. Downloads are redirected to where files are stored, so with `RedirectPolicy::None`
+	/// they fail with `Error::Redirected` giving the url to download from, e.g. for proxies which break on
+	/// redirects being followed. Use `download_with_meta` to see where a followed download ended up.
+	///
+	/// Only applies to clients built with `build` or `build_blocking`, and not on wasm, where the browser follows
+	/// redirects itself.
+	pub fn redirect_policy(mut self, policy: RedirectPolicy) -> Self {
+		self.reqwest.redirect = policy;
+		self
+	}
+
+	/// Builds the client with the default reqwest backend, failing if the configuration is invalid.
+	pub fn build(self) -> Result<HangarClient> {
+		let client = self.reqwest.apply(reqwest::Client::builder()).build()?;
+		self.build_with_backend(ReqwestBackend::new(client))
+	}
+
+	/// Builds the client with a custom http backend, failing if the configuration is invalid.
+	pub fn build_with_backend<B: HttpBackend>(self, backend: B) -> Result<HangarClient<B>> {
+		let config = self.validate()?;
+		Ok(HangarClient {
+			backend,
+			in_flight: config.coalesce_requests.then(Arc::default),
+			config,
+			refresh_lock: Arc::default(),
+		})
+	}
+
+	/// Builds a blocking client, failing if the configuration is invalid.
+	/// This must not be called from within an async runtime.
+	#[cfg(feature = "blocking")]
+	pub fn build_blocking(self) -> Result<crate::blocking::BlockingClient> {
+		let client = self
+			.reqwest
+			.apply_blocking(reqwest::blocking::Client::builder())
+			.build()?;
+		Ok(crate::blocking::BlockingClient::from_parts(
+			client,
+			self.validate()?,
+		))
+	}
+
+	/// Checks the configuration is valid.
+	fn validate(self) -> Result<ClientConfig> {
+		HeaderValue::from_str(&self.config.user_agent).map_err(|_| {
+			Error::InvalidRequest(format!("invalid user agent: {}", self.config.user_agent))
+		})?;
+		if let Some(language) = &self.config.language {
+			validate_language(language)?;
+		}
+		Ok(self.config)
+	}
+}
@@ -0,0 +1,79 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use futures::channel::oneshot;
+
+use crate::backend::HttpResponse;
+use crate::error::Result;
+
+type Waiters = Vec<oneshot::Sender<Result<HttpResponse>>>;
+
+/// Requests a client is currently sending, so identical requests sent at the same time can share one response.
+#[derive(Debug, Default)]
+pub(crate) struct InFlight {
+	requests: Mutex<HashMap<String, Waiters>>,
+}
+
+/// What a request should do given the requests already in flight.
+pub(crate) enum Join<'a> {
+	/// No identical request is in flight, so this one is sent and its result shared through the guard
+	Lead(LeadGuard<'a>),
+	/// An identical request is in flight, whose result is received here
+	Wait(oneshot::Receiver<Result<HttpResponse>>),
+}
+
+impl InFlight {
+	/// Joins the requests in flight under a key.
+	pub(crate) fn join(&self, key: String) -> Join<'_> {
+		let mut requests = self.requests.lock().expect("lock poisoned");
+		match requests.get_mut(&key) {
+			Some(waiters) => {
+				let (sender, receiver) = oneshot::channel();
+				waiters.push(sender);
+				Join::Wait(receiver)
+			}
+			None => {
+				requests.insert(key.clone(), Vec::new());
+				Join::Lead(LeadGuard {
+					in_flight: self,
+					key: Some(key),
+				})
+			}
+		}
+	}
+}
+
+/// Held by the request being sent for everyone waiting on it.
+/// If it's dropped without finishing, e.g. because the request was cancelled, the waiters are told to send the
+/// request themselves.
+pub(crate) struct LeadGuard<'a> {
+	in_flight: &'a InFlight,
+	key: Option<String>,
+}
+
+impl LeadGuard<'_> {
+	fn take_waiters(&mut self) -> Waiters {
+		let Some(key) = self.key.take() else {
+			return Vec::new();
+		};
+		self.in_flight
+			.requests
+			.lock()
+			.expect("lock poisoned")
+			.remove(&key)
+			.unwrap_or_default()
+	}
+
+	/// Gives the result of the request to everyone waiting on it.
+	pub(crate) fn finish(mut self, result: &Result<HttpResponse>) {
+		for waiter in self.take_waiters() {
+			let _ = waiter.send(result.clone());
+		}
+	}
+}
+
+impl Drop for LeadGuard<'_> {
+	fn drop(&mut self) {
+		// dropping the senders wakes the waiters up with `Canceled`
+		self.take_waiters();
+	}
+}
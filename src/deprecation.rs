@@ -0,0 +1,210 @@
+use reqwest::header::{HeaderMap, WARNING};
+use time::{format_description::well_known::Rfc2822, Duration, OffsetDateTime};
+
+use crate::backend::{MaybeSend, MaybeSync};
+
+/// The header Hangar sends on responses from deprecated endpoints, either `true` or when the endpoint was or will be
+/// deprecated.
+pub const DEPRECATION_HEADER: &str = "Deprecation";
+/// The header Hangar sends with when an endpoint will stop working.
+pub const SUNSET_HEADER: &str = "Sunset";
+
+/// The deprecation notices Hangar sent with a response, from its `Deprecation`, `Sunset` and `Warning` headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+	/// Whether the endpoint is deprecated, which it is once the `Deprecation` header is sent unless it's dated in the
+	/// future
+	pub deprecated: bool,
+	/// When the endpoint was or will be deprecated, if the `Deprecation` header gave a date
+	pub since: Option<OffsetDateTime>,
+	/// When the endpoint will stop working, if known
+	pub sunset: Option<OffsetDateTime>,
+	/// The text of every `Warning` header, as sent
+	pub warnings: Vec<String>,
+}
+
+impl Deprecation {
+	/// Parses the deprecation headers of a response, if it has any.
+	pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+		let deprecation = header(headers, DEPRECATION_HEADER);
+		let sunset = header(headers, SUNSET_HEADER).and_then(parse_sunset);
+		let warnings: Vec<String> = headers
+			.get_all(WARNING)
+			.iter()
+			.filter_map(|v| v.to_str().ok())
+			.map(|v| v.trim().to_string())
+			.collect();
+		if deprecation.is_none() && sunset.is_none() && warnings.is_empty() {
+			return None;
+		}
+		let since = deprecation.and_then(parse_deprecation_date);
+		let deprecated = match (deprecation, since) {
+			(None, _) => false,
+			(Some(value), None) => !value.eq_ignore_ascii_case("false"),
+			(Some(_), Some(since)) => since <= OffsetDateTime::now_utc(),
+		};
+		Some(Self {
+			deprecated,
+			since,
+			sunset,
+			warnings,
+		})
+	}
+
+	/// Gets how long is left until the endpoint stops working, if known. Zero once the sunset has passed.
+	pub fn time_until_sunset(&self) -> Option<std::time::Duration> {
+		let left = self.sunset? - OffsetDateTime::now_utc();
+		Some(left.try_into().unwrap_or_default())
+	}
+}
+
+/// Gets a header, if present and valid utf-8.
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+	headers
+		.get(name)
+		.and_then(|v| v.to_str().ok())
+		.map(str::trim)
+}
+
+/// Parses the date of a `Deprecation` header, which is either a unix timestamp like `@1688169599` or an http-date.
+fn parse_deprecation_date(value: &str) -> Option<OffsetDateTime> {
+	match value.strip_prefix('@') {
+		Some(timestamp) => OffsetDateTime::from_unix_timestamp(timestamp.parse().ok()?).ok(),
+		None => OffsetDateTime::parse(value, &Rfc2822).ok(),
+	}
+}
+
+/// Parses a `Sunset` header in either its http-date or delta-seconds form.
+fn parse_sunset(value: &str) -> Option<OffsetDateTime> {
+	if let Ok(seconds) = value.parse() {
+		return Some(OffsetDateTime::now_utc() + Duration::seconds(seconds));
+	}
+	OffsetDateTime::parse(value, &Rfc2822).ok()
+}
+
+/// A hook run with the deprecation notices of a response from the api, given the response's url.
+pub(crate) trait DeprecationHook: MaybeSend + MaybeSync {
+	fn notify(&self, url: &str, deprecation: &Deprecation);
+}
+
+impl<F> DeprecationHook for F
+where
+	F: Fn(&str, &Deprecation) + MaybeSend + MaybeSync,
+{
+	fn notify(&self, url: &str, deprecation: &Deprecation) {
+		self(url, deprecation)
+	}
+}
+
+/// Gets the route of an api url with the slugs and names in it replaced by placeholders, e.g.
+/// `/projects/{slug}/versions/{name}`, so an endpoint is the same endpoint whichever project it's for.
+#[cfg(feature = "tracing")]
+pub(crate) fn endpoint_template(base_url: &str, url: &str) -> String {
+	let path = url.split('?').next().unwrap_or_default();
+	let path = path.strip_prefix(base_url).unwrap_or(path);
+	let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+	let Some(&first) = segments.first() else {
+		return "/".to_string();
+	};
+	let template: Vec<&str> = segments
+		.iter()
+		.enumerate()
+		.map(|(i, &segment)| match (first, i) {
+			("projects", 1) => "{slug}",
+			("projects", 3) if segments[2] == "versions" => "{name}",
+			("projects", 4) if segments.get(5) == Some(&"download") => "{platform}",
+			("pages", 2) => "{slug}",
+			("users", 1) => "{user}",
+			_ => segment,
+		})
+		.collect();
+	format!("/{}", template.join("/"))
+}
+
+/// Checks whether a deprecated endpoint is seen for the first time in this process, so it's only warned about once.
+#[cfg(feature = "tracing")]
+pub(crate) fn first_sighting(endpoint: &str) -> bool {
+	use std::{collections::HashSet, sync::Mutex};
+
+	static SEEN: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+	SEEN.lock()
+		.expect("lock poisoned")
+		.get_or_insert_with(HashSet::new)
+		.insert(endpoint.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+		pairs
+			.iter()
+			.map(|(name, value)| {
+				(
+					reqwest::header::HeaderName::try_from(*name).unwrap(),
+					reqwest::header::HeaderValue::try_from(*value).unwrap(),
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn no_headers_is_no_deprecation() {
+		assert_eq!(Deprecation::from_headers(&HeaderMap::new()), None);
+	}
+
+	#[test]
+	fn parses_dates_and_deltas() {
+		let deprecation = Deprecation::from_headers(&headers(&[
+			("Deprecation", "@1688169599"),
+			("Sunset", "Sat, 01 Jan 2000 00:00:00 GMT"),
+			("Warning", "299 - \"use v2\""),
+		]))
+		.unwrap();
+		assert!(deprecation.deprecated);
+		assert_eq!(deprecation.since.unwrap().unix_timestamp(), 1688169599);
+		assert_eq!(deprecation.sunset.unwrap().unix_timestamp(), 946684800);
+		assert_eq!(deprecation.warnings, ["299 - \"use v2\""]);
+		assert_eq!(
+			deprecation.time_until_sunset(),
+			Some(std::time::Duration::ZERO)
+		);
+
+		let deprecation = Deprecation::from_headers(&headers(&[("Sunset", "3600")])).unwrap();
+		assert!(!deprecation.deprecated);
+		let left = deprecation.time_until_sunset().unwrap();
+		assert!(left.as_secs() > 3500 && left.as_secs() <= 3600, "{left:?}");
+	}
+
+	#[test]
+	fn future_deprecations_are_not_deprecated_yet() {
+		let deprecation =
+			Deprecation::from_headers(&headers(&[("Deprecation", "@4102444800")])).unwrap();
+		assert!(!deprecation.deprecated);
+		let deprecation = Deprecation::from_headers(&headers(&[("Deprecation", "true")])).unwrap();
+		assert!(deprecation.deprecated);
+		assert_eq!(deprecation.since, None);
+	}
+
+	#[cfg(feature = "tracing")]
+	#[test]
+	fn templates_leave_out_slugs_and_names() {
+		let base = "https://hangar.papermc.io/api/v1";
+		let template = |path: &str| endpoint_template(base, &format!("{base}{path}"));
+		assert_eq!(template("/projects?limit=25"), "/projects");
+		assert_eq!(template("/projects/Maintenance"), "/projects/{slug}");
+		assert_eq!(template("/projects/Other"), "/projects/{slug}");
+		assert_eq!(
+			template("/projects/Maintenance/versions/4.2.0/stats"),
+			"/projects/{slug}/versions/{name}/stats"
+		);
+		assert_eq!(
+			template("/projects/Maintenance/versions/4.2.0/PAPER/download"),
+			"/projects/{slug}/versions/{name}/{platform}/download"
+		);
+		assert_eq!(template("/pages/main/Maintenance"), "/pages/main/{slug}");
+		assert_eq!(template("/users/kennytv/starred"), "/users/{user}/starred");
+		assert_eq!(template("/permissions/hasAll"), "/permissions/hasAll");
+	}
+}
@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::object::{Category, Project, ProjectName, ProjectSettings, ProjectStats, Visibility};
+
+/// A value which changed between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<T> {
+	pub old: T,
+	pub new: T,
+}
+
+impl<T: Clone + PartialEq> Change<T> {
+	/// Gets the change between two values, or `None` if they're the same.
+	fn between(old: &T, new: &T) -> Option<Self> {
+		(old != new).then(|| Self {
+			old: old.clone(),
+			new: new.clone(),
+		})
+	}
+}
+
+/// How much each of a project's stats went up between two snapshots. Negative if they went down, which can happen
+/// for the recent stats and for stars and watchers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatsDelta {
+	pub views: i64,
+	pub downloads: i64,
+	pub recent_views: i64,
+	pub recent_downloads: i64,
+	pub stars: i64,
+	pub watchers: i64,
+}
+
+impl StatsDelta {
+	/// Gets how much each stat changed from `old` to `new`.
+	pub fn between(old: &ProjectStats, new: &ProjectStats) -> Self {
+		Self {
+			views: new.views - old.views,
+			downloads: new.downloads - old.downloads,
+			recent_views: new.recent_views - old.recent_views,
+			recent_downloads: new.recent_downloads - old.recent_downloads,
+			stars: new.stars - old.stars,
+			watchers: new.watchers - old.watchers,
+		}
+	}
+
+	/// Checks whether none of the stats changed.
+	pub fn is_zero(&self) -> bool {
+		*self == Self::default()
+	}
+}
+
+/// A project's stats at a point in time, which can be stored to work out how a project grows over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsSnapshot {
+	/// The project's stats
+	pub stats: ProjectStats,
+	/// When the stats were fetched
+	#[serde(with = "time::serde::rfc3339")]
+	pub taken_at: OffsetDateTime,
+}
+
+impl StatsSnapshot {
+	/// Creates a snapshot of stats fetched just now.
+	pub fn now(stats: ProjectStats) -> Self {
+		Self {
+			stats,
+			taken_at: OffsetDateTime::now_utc(),
+		}
+	}
+
+	/// Gets how much a project's stats went up from an earlier snapshot to a later one.
+	/// Views and downloads only ever add up, so if they went down, e.g. because Hangar removed spam downloads,
+	/// they count as not having gone up at all. The other stats can go down.
+	pub fn diff(earlier: &Self, later: &Self) -> StatsDelta {
+		let delta = StatsDelta::between(&earlier.stats, &later.stats);
+		StatsDelta {
+			views: delta.views.max(0),
+			downloads: delta.downloads.max(0),
+			..delta
+		}
+	}
+
+	/// Gets how long passed between an earlier snapshot and this one.
+	pub fn since(&self, earlier: &Self) -> time::Duration {
+		self.taken_at - earlier.taken_at
+	}
+}
+
+/// A part of a project's settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+	Links,
+	Tags,
+	License,
+	Keywords,
+	Sponsors,
+	Donation,
+}
+
+/// What changed about a project between two snapshots of it, e.g. from polling it with a `ProjectRequest`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectDiff {
+	/// When the project was last updated, if that changed, which happens when a version is released
+	pub last_updated: Option<Change<OffsetDateTime>>,
+	/// How much the project's stats went up
+	pub stats: StatsDelta,
+	/// The project's visibility, if it changed
+	pub visibility: Option<Change<Visibility>>,
+	/// The project's name, if it was renamed
+	pub name: Option<Change<ProjectName>>,
+	/// The project's short description, if it changed
+	pub description: Option<Change<String>>,
+	/// The project's category, if it changed
+	pub category: Option<Change<Category>>,
+	/// The project's icon url, if it changed
+	pub avatar_url: Option<Change<String>>,
+	/// The parts of the project's settings which changed, in the order of `ProjectSettings`' fields
+	pub settings: Vec<SettingsField>,
+}
+
+impl ProjectDiff {
+	/// Compares an older snapshot of a project with a newer one.
+	pub fn between(old: &Project, new: &Project) -> Self {
+		Self {
+			last_updated: Change::between(&old.last_updated, &new.last_updated),
+			stats: StatsDelta::between(&old.stats, &new.stats),
+			visibility: Change::between(&old.visibility, &new.visibility),
+			name: Change::between(&old.name, &new.name),
+			description: Change::between(&old.description, &new.description),
+			category: Change::between(&old.category, &new.category),
+			avatar_url: Change::between(&old.avatar_url, &new.avatar_url),
+			settings: settings_changes(&old.settings, &new.settings),
+		}
+	}
+
+	/// Checks whether nothing changed.
+	pub fn is_empty(&self) -> bool {
+		self.last_updated.is_none() && self.stats.is_zero() && !self.has_metadata_changes()
+	}
+
+	/// Checks whether only the project's stats changed, which is most of what polling a project turns up.
+	pub fn is_stats_only(&self) -> bool {
+		!self.stats.is_zero() && self.last_updated.is_none() && !self.has_metadata_changes()
+	}
+
+	/// Checks whether the project was updated since the older snapshot, most likely because a version was
+	/// released. Fetch the project's versions to find out which.
+	pub fn has_new_version(&self) -> bool {
+		self.last_updated
+			.as_ref()
+			.is_some_and(|change| change.new > change.old)
+	}
+
+	/// Checks whether anything other than the stats and update time changed.
+	fn has_metadata_changes(&self) -> bool {
+		self.visibility.is_some()
+			|| self.name.is_some()
+			|| self.description.is_some()
+			|| self.category.is_some()
+			|| self.avatar_url.is_some()
+			|| !self.settings.is_empty()
+	}
+}
+
+fn settings_changes(old: &ProjectSettings, new: &ProjectSettings) -> Vec<SettingsField> {
+	[
+		(old.links != new.links, SettingsField::Links),
+		(old.tags != new.tags, SettingsField::Tags),
+		(old.license != new.license, SettingsField::License),
+		(old.keywords != new.keywords, SettingsField::Keywords),
+		(old.sponsors != new.sponsors, SettingsField::Sponsors),
+		(old.donation != new.donation, SettingsField::Donation),
+	]
+	.into_iter()
+	.filter_map(|(changed, field)| changed.then_some(field))
+	.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn stats(views: i64, downloads: i64, stars: i64) -> ProjectStats {
+		ProjectStats {
+			views,
+			downloads,
+			recent_views: views / 10,
+			recent_downloads: downloads / 10,
+			stars,
+			watchers: 3,
+		}
+	}
+
+	fn snapshot(stats: ProjectStats, unix: i64) -> StatsSnapshot {
+		StatsSnapshot {
+			stats,
+			taken_at: OffsetDateTime::from_unix_timestamp(unix).unwrap(),
+		}
+	}
+
+	#[test]
+	fn identical_snapshots_have_no_delta() {
+		let earlier = snapshot(stats(1000, 300, 12), 0);
+		let later = snapshot(stats(1000, 300, 12), 86400);
+		assert!(StatsSnapshot::diff(&earlier, &later).is_zero());
+		assert_eq!(later.since(&earlier), time::Duration::days(1));
+	}
+
+	#[test]
+	fn views_and_downloads_going_down_count_as_no_growth() {
+		let earlier = snapshot(stats(1000, 300, 12), 0);
+		let later = snapshot(stats(900, 250, 10), 86400);
+		let delta = StatsSnapshot::diff(&earlier, &later);
+		assert_eq!(delta.views, 0);
+		assert_eq!(delta.downloads, 0);
+		// stars can really go down
+		assert_eq!(delta.stars, -2);
+		assert_eq!(delta.recent_views, -10);
+		let raw = StatsDelta::between(&earlier.stats, &later.stats);
+		assert_eq!((raw.views, raw.downloads), (-100, -50));
+	}
+
+	#[test]
+	fn growth_is_the_difference() {
+		let earlier = snapshot(stats(1000, 300, 12), 0);
+		let later = snapshot(stats(1250, 340, 15), 86400);
+		let delta = StatsSnapshot::diff(&earlier, &later);
+		assert_eq!((delta.views, delta.downloads, delta.stars), (250, 40, 3));
+		assert!(!delta.is_zero());
+	}
+
+	#[test]
+	fn snapshots_round_trip() {
+		let snapshot = snapshot(stats(1000, 300, 12), 1_718_000_000);
+		let json = serde_json::to_string(&snapshot).unwrap();
+		assert!(
+			json.contains(r#""takenAt":"2024-06-10T06:13:20Z""#),
+			"{json}"
+		);
+		assert_eq!(
+			serde_json::from_str::<StatsSnapshot>(&json).unwrap(),
+			snapshot
+		);
+	}
+
+	fn project() -> Project {
+		serde_json::from_str(include_str!("../tests/fixtures/project.json")).unwrap()
+	}
+
+	#[test]
+	fn identical_projects_have_no_changes() {
+		let diff = ProjectDiff::between(&project(), &project());
+		assert!(diff.is_empty());
+		assert!(!diff.is_stats_only());
+		assert!(!diff.has_new_version());
+	}
+
+	#[test]
+	fn only_stats_changed() {
+		let old = project();
+		let mut new = project();
+		new.stats.downloads += 40;
+		new.stats.views += 120;
+		new.stats.stars -= 1;
+		let diff = ProjectDiff::between(&old, &new);
+		assert!(diff.is_stats_only());
+		assert!(!diff.is_empty());
+		assert!(!diff.has_new_version());
+		assert_eq!(
+			diff.stats,
+			StatsDelta {
+				views: 120,
+				downloads: 40,
+				stars: -1,
+				..StatsDelta::default()
+			}
+		);
+		assert!(diff.settings.is_empty());
+	}
+
+	#[test]
+	fn new_version_released() {
+		let old = project();
+		let mut new = project();
+		new.last_updated += time::Duration::days(3);
+		new.stats.downloads += 5;
+		let diff = ProjectDiff::between(&old, &new);
+		assert!(diff.has_new_version());
+		assert!(!diff.is_stats_only());
+		assert_eq!(
+			diff.last_updated,
+			Some(Change {
+				old: old.last_updated,
+				new: new.last_updated,
+			})
+		);
+		assert_eq!(diff.stats.downloads, 5);
+	}
+
+	#[test]
+	fn older_update_times_are_not_new_versions() {
+		let old = project();
+		let mut new = project();
+		new.last_updated -= time::Duration::days(1);
+		let diff = ProjectDiff::between(&old, &new);
+		assert!(diff.last_updated.is_some());
+		assert!(!diff.has_new_version());
+	}
+
+	#[test]
+	fn visibility_and_settings_edits_are_reported() {
+		let old = project();
+		let mut new = project();
+		new.visibility = Visibility::SoftDelete;
+		new.settings.keywords.push("motd".to_string());
+		new.settings.sponsors = "Thanks to everyone!".to_string();
+		let diff = ProjectDiff::between(&old, &new);
+		assert_eq!(
+			diff.visibility,
+			Some(Change {
+				old: Visibility::Public,
+				new: Visibility::SoftDelete,
+			})
+		);
+		assert_eq!(
+			diff.settings,
+			[SettingsField::Keywords, SettingsField::Sponsors]
+		);
+		assert!(!diff.is_stats_only());
+		assert!(!diff.is_empty());
+	}
+}
@@ -0,0 +1,348 @@
+use std::{sync::Arc, time::Duration};
+
+use serde::Deserialize;
+
+use crate::object::{NamedPermission, Platform};
+
+/// Result type used throughout the crate.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors which can occur when talking to Hangar.
+/// Errors from other crates are kept in an `Arc` so errors can be cloned, e.g. to give the same error to every
+/// caller of a shared request.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Error {
+	/// The underlying http request failed
+	#[cfg(feature = "reqwest")]
+	#[error("http error: {0}")]
+	Http(#[source] Arc<reqwest::Error>),
+	/// The response body couldn't be deserialized
+	#[error(
+		"failed to deserialize response at `{path}`{}: {source}",
+		item.as_ref().map(|item| format!(" (in {item})")).unwrap_or_default()
+	)]
+	Deserialize {
+		/// Where in the json the error happened, like `result[17].settings.donation`, or `.` for the whole body
+		path: String,
+		/// The slug of the project, or the name of the version or user, the error happened in, if it was in a page
+		/// and could be read
+		item: Option<String>,
+		/// The error from deserializing
+		#[source]
+		source: Arc<serde_json::Error>,
+	},
+	/// Exporting to csv failed
+	#[cfg(feature = "export")]
+	#[error("csv error: {0}")]
+	Csv(#[source] Arc<csv::Error>),
+	/// The request was rejected before being sent
+	#[error("invalid request: {0}")]
+	InvalidRequest(String),
+	/// The request's pagination information is invalid, so it wasn't sent
+	#[error("invalid pagination: {0}")]
+	Pagination(#[from] PaginationError),
+	/// Hangar responded with an error status not covered by the other variants
+	#[error("hangar returned {status}: {message}")]
+	Api {
+		/// The http status code of the response
+		status: u16,
+		/// The error message returned by Hangar, or the raw body if it wasn't json
+		message: String,
+	},
+	/// Hangar responded with an html page rather than json, like Cloudflare's error pages or Hangar's maintenance
+	/// page, or because the base url points at Hangar's website instead of its api. Retried like `Api` errors if
+	/// the status is one of the retry policy's statuses.
+	#[error("hangar is unavailable, it returned an html page with status {status}: {snippet}")]
+	Unavailable {
+		/// The http status code of the response, which can be 200 for maintenance pages
+		status: u16,
+		/// The start of the page, with its whitespace collapsed
+		snippet: String,
+	},
+	/// The response was a redirect, which the client doesn't follow with `RedirectPolicy::None`
+	#[error("redirected with status {status} to {}", location.as_deref().unwrap_or("nowhere"))]
+	Redirected {
+		/// The http status code of the response
+		status: u16,
+		/// Where the response redirects to, from its `Location` header
+		location: Option<String>,
+	},
+	/// The requested resource doesn't exist.
+	/// Searches and lists matching nothing give an empty page instead, see `Paginated`.
+	#[error("not found")]
+	NotFound,
+	/// A project which was seen before, e.g. while paginating, doesn't exist anymore, most likely because it was
+	/// deleted or hidden
+	#[error("project `{slug}` is gone")]
+	Gone {
+		/// The slug the project was seen with
+		slug: String,
+	},
+	/// The request wasn't authenticated or lacks the permissions needed (401 or 403)
+	#[error("unauthorized: {message}")]
+	Unauthorized {
+		/// The error message returned by Hangar
+		message: String,
+	},
+	/// The version has no download for the requested platform
+	#[error("no download available for {0}")]
+	NoDownload(Platform),
+	/// The request kept failing with transient errors, like `503 Service Unavailable`, until it ran out of
+	/// retries. Errors which retrying can't fix, like `NotFound`, are returned as they are even after a retry.
+	#[error("failed after {attempts} attempts: {source}")]
+	Retried {
+		/// How many attempts were made in total
+		attempts: u32,
+		/// The error from the final attempt
+		source: Box<Error>,
+	},
+	/// Too many requests have been sent to Hangar
+	#[error("rate limited")]
+	RateLimited {
+		/// How long Hangar asked to wait before trying again, if it said
+		retry_after: Option<Duration>,
+	},
+	/// Hangar rejected the api key, most likely because it was revoked
+	#[error("invalid api key: {message}")]
+	InvalidApiKey {
+		/// The error message returned by Hangar
+		message: String,
+	},
+	/// The auth token expired and getting a new one with the client's api key failed, so the request wasn't sent
+	#[error("failed to refresh the auth token: {source}")]
+	AuthRefresh {
+		/// The error from authenticating
+		source: Box<Error>,
+	},
+	/// Hangar says the response hasn't changed since it was last fetched, and no body was stored for it
+	#[error("not modified")]
+	NotModified,
+	/// The client checks permissions and the user lacks the permission the request needs, so it wasn't sent
+	#[error("missing the `{permission}` permission")]
+	MissingPermission {
+		/// The permission the request needs
+		permission: NamedPermission,
+	},
+	/// A response couldn't be recorded, or a request being replayed wasn't recorded
+	#[cfg(feature = "replay")]
+	#[error("replay error: {0}")]
+	Replay(String),
+	/// The operation's cancellation token was cancelled before it finished
+	#[error("cancelled")]
+	Cancelled,
+	/// A downloaded file doesn't match what Hangar knows about it
+	#[error("downloaded file is corrupt: {0}")]
+	Integrity(#[from] IntegrityError),
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for Error {
+	fn from(value: reqwest::Error) -> Self {
+		Self::Http(Arc::new(value))
+	}
+}
+
+impl From<serde_json::Error> for Error {
+	fn from(value: serde_json::Error) -> Self {
+		Self::Deserialize {
+			path: ".".to_string(),
+			item: None,
+			source: Arc::new(value),
+		}
+	}
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for Error {
+	fn from(value: serde_path_to_error::Error<serde_json::Error>) -> Self {
+		Self::Deserialize {
+			path: value.path().to_string(),
+			item: None,
+			source: Arc::new(value.into_inner()),
+		}
+	}
+}
+
+#[cfg(feature = "export")]
+impl From<csv::Error> for Error {
+	fn from(value: csv::Error) -> Self {
+		Self::Csv(Arc::new(value))
+	}
+}
+
+/// The json body Hangar sends alongside error statuses.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorBody {
+	message: String,
+	#[serde(default)]
+	message_args: Vec<serde_json::Value>,
+}
+
+impl Error {
+	/// Gets the error a request failed with in the end, looking through `Retried`.
+	pub fn root(&self) -> &Error {
+		match self {
+			Self::Retried { source, .. } => source.root(),
+			err => err,
+		}
+	}
+
+	/// Checks whether Hangar said what was requested doesn't exist, even if the request was retried first.
+	pub fn is_not_found(&self) -> bool {
+		matches!(self.root(), Self::NotFound)
+	}
+
+	/// Creates an error from a non-successful response.
+	pub fn from_response(status: u16, body: &[u8]) -> Self {
+		let message = match serde_json::from_slice::<ErrorBody>(body) {
+			Ok(body) if body.message_args.is_empty() => body.message,
+			Ok(body) => {
+				let args: Vec<_> = body
+					.message_args
+					.iter()
+					.map(|arg| match arg {
+						serde_json::Value::String(s) => s.clone(),
+						other => other.to_string(),
+					})
+					.collect();
+				format!("{} ({})", body.message, args.join(", "))
+			}
+			Err(_) => String::from_utf8_lossy(body).into_owned(),
+		};
+		match status {
+			401 | 403 => Self::Unauthorized { message },
+			404 => Self::NotFound,
+			429 => Self::RateLimited { retry_after: None },
+			_ => Self::Api { status, message },
+		}
+	}
+}
+
+/// Errors from verifying a downloaded file against its expected metadata.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum IntegrityError {
+	/// The file isn't the expected size
+	#[error("expected {expected} bytes, got {actual}")]
+	Size {
+		/// The size Hangar reported for the file
+		expected: i64,
+		/// The size of the downloaded file
+		actual: usize,
+	},
+	/// The file's sha256 hash doesn't match
+	#[error("expected sha256 hash {expected}, got {actual}")]
+	Hash {
+		/// The hash Hangar reported for the file
+		expected: String,
+		/// The hash of the downloaded file
+		actual: String,
+	},
+}
+
+/// Error from parsing an enum like `Platform` from a string.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid {kind} `{value}`, expected one of: {expected}")]
+pub struct ParseEnumError {
+	/// What was being parsed, e.g. `platform`
+	pub kind: &'static str,
+	/// The string which couldn't be parsed
+	pub value: String,
+	/// The valid values, separated by commas
+	pub expected: String,
+}
+
+/// Errors from invalid pagination information.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PaginationError {
+	/// The limit isn't within `1..=25`
+	#[error("limit must be between 1 and 25, got {0}")]
+	Limit(i64),
+	/// The offset is negative
+	#[error("offset must not be negative, got {0}")]
+	Offset(i64),
+}
+
+/// Errors from invalid plugin dependencies, see `PluginDependenciesBuilder`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DependencyError {
+	/// The dependency has no name
+	#[error("dependencies need a name")]
+	MissingName,
+	/// The external dependency's url is empty or not an http(s) url
+	#[error("invalid url `{url}` for external dependency `{name}`")]
+	InvalidUrl {
+		/// The name of the dependency
+		name: String,
+		/// The url it was given
+		url: String,
+	},
+	/// The dependency is listed more than once for a platform
+	#[error("dependency `{name}` is listed more than once for {platform}")]
+	Duplicate {
+		/// The name of the dependency
+		name: String,
+		/// The platform it's listed for
+		platform: Platform,
+	},
+}
+
+/// Error from parsing a Minecraft version or version range.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid minecraft version `{0}`")]
+pub struct ParseVersionError(pub String);
+
+/// Error from parsing a hex color like `#2A4BC2`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid hex color `{0}`")]
+pub struct ParseColorError(pub String);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn not_found_bodies_are_not_found() {
+		let body = include_bytes!("../tests/fixtures/errors/not_found.json");
+		assert!(matches!(Error::from_response(404, body), Error::NotFound));
+	}
+
+	#[test]
+	fn forbidden_bodies_are_unauthorized() {
+		let body = include_bytes!("../tests/fixtures/errors/forbidden.json");
+		match Error::from_response(403, body) {
+			Error::Unauthorized { message } => {
+				assert_eq!(message, "error.noPermission (view_unlisted)")
+			}
+			err => panic!("expected Unauthorized, got {err:?}"),
+		}
+	}
+
+	#[test]
+	fn rate_limited_bodies_are_rate_limited() {
+		let body = include_bytes!("../tests/fixtures/errors/rate_limited.json");
+		assert!(matches!(
+			Error::from_response(429, body),
+			Error::RateLimited { retry_after: None }
+		));
+	}
+
+	#[test]
+	fn other_statuses_keep_the_message_and_its_arguments() {
+		let body = include_bytes!("../tests/fixtures/errors/bad_request.json");
+		match Error::from_response(400, body) {
+			Error::Api { status, message } => {
+				assert_eq!(status, 400);
+				assert_eq!(message, "Invalid request (limit, 50)");
+			}
+			err => panic!("expected Api, got {err:?}"),
+		}
+	}
+
+	#[test]
+	fn bodies_which_are_not_json_are_kept_as_the_message() {
+		match Error::from_response(502, b"Bad Gateway") {
+			Error::Api { message, .. } => assert_eq!(message, "Bad Gateway"),
+			err => panic!("expected Api, got {err:?}"),
+		}
+	}
+}
@@ -0,0 +1,160 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use reqwest::header::{ETAG, IF_NONE_MATCH};
+
+use crate::backend::{HttpRequest, HttpResponse, MaybeSend, MaybeSync};
+use crate::error::{Error, Result};
+
+/// What's remembered about a previous response to a request.
+#[derive(Debug, Clone)]
+pub struct EtagEntry {
+	/// The `ETag` the response was sent with
+	pub etag: String,
+	/// The body of the response, returned when Hangar says it hasn't changed.
+	/// Without it unchanged responses give `Error::NotModified`.
+	pub body: Option<Vec<u8>>,
+}
+
+/// Storage for the `ETag`s of responses, used to make conditional requests.
+/// Keys are the request's url along with its sorted query parameters, followed by the language it asks for
+/// and a hash of the credentials it's sent with, if any, since either can change the response.
+///
+/// Implement this to persist entries yourself, e.g. to keep them between runs.
+pub trait EtagStore: MaybeSend + MaybeSync {
+	/// Gets the entry stored for a request.
+	fn get(&self, key: &str) -> Option<EtagEntry>;
+
+	/// Stores the entry for a request, replacing any previous one.
+	/// The entry always includes the response body, which can be dropped to only remember the `ETag`.
+	fn put(&self, key: &str, entry: EtagEntry);
+}
+
+/// An in-memory `EtagStore`, keeping response bodies so unchanged responses can be served from it.
+/// Once full, storing another entry evicts the least recently used one.
+#[derive(Debug)]
+pub struct MemoryEtagStore {
+	capacity: usize,
+	entries: Mutex<LruEntries>,
+}
+
+/// The entries of a `MemoryEtagStore`, each stamped with when it was last used.
+#[derive(Debug, Default)]
+struct LruEntries {
+	entries: HashMap<String, (EtagEntry, u64)>,
+	clock: u64,
+}
+
+impl LruEntries {
+	fn tick(&mut self) -> u64 {
+		self.clock += 1;
+		self.clock
+	}
+}
+
+impl Default for MemoryEtagStore {
+	fn default() -> Self {
+		Self::with_capacity(Self::DEFAULT_CAPACITY)
+	}
+}
+
+impl MemoryEtagStore {
+	/// How many entries a store holds by default.
+	pub const DEFAULT_CAPACITY: usize = 1000;
+
+	/// Creates an empty store holding up to `DEFAULT_CAPACITY` entries.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates an empty store holding up to `capacity` entries.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			capacity,
+			entries: Mutex::default(),
+		}
+	}
+
+	/// Gets how many entries are stored.
+	pub fn len(&self) -> usize {
+		self.entries.lock().expect("lock poisoned").entries.len()
+	}
+
+	/// Checks whether no entries are stored.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl EtagStore for MemoryEtagStore {
+	fn get(&self, key: &str) -> Option<EtagEntry> {
+		let mut lru = self.entries.lock().expect("lock poisoned");
+		let now = lru.tick();
+		let (entry, last_used) = lru.entries.get_mut(key)?;
+		*last_used = now;
+		Some(entry.clone())
+	}
+
+	fn put(&self, key: &str, entry: EtagEntry) {
+		if self.capacity == 0 {
+			return;
+		}
+		let mut lru = self.entries.lock().expect("lock poisoned");
+		let now = lru.tick();
+		if !lru.entries.contains_key(key) && lru.entries.len() >= self.capacity {
+			let oldest = lru
+				.entries
+				.iter()
+				.min_by_key(|(_, (_, last_used))| *last_used)
+				.map(|(key, _)| key.clone());
+			if let Some(oldest) = oldest {
+				lru.entries.remove(&oldest);
+			}
+		}
+		lru.entries.insert(key.to_string(), (entry, now));
+	}
+}
+
+/// A conditional request in flight.
+pub(crate) struct Revalidation {
+	key: String,
+	cached: Option<EtagEntry>,
+}
+
+impl Revalidation {
+	/// Makes a request conditional on the stored `ETag` for it, if there is one.
+	pub(crate) fn start(store: &dyn EtagStore, request: &mut HttpRequest) -> Self {
+		let key = request.response_key();
+		let cached = store.get(&key);
+		if let Some(entry) = &cached {
+			request
+				.headers
+				.push((IF_NONE_MATCH.to_string(), entry.etag.clone()));
+		}
+		Self { key, cached }
+	}
+
+	/// Stores the `ETag` of a fresh response, or swaps in the stored body for an unchanged one.
+	pub(crate) fn finish(
+		self,
+		store: &dyn EtagStore,
+		mut response: HttpResponse,
+	) -> Result<HttpResponse> {
+		if response.status == 304 {
+			response.body = self
+				.cached
+				.and_then(|entry| entry.body)
+				.ok_or(Error::NotModified)?;
+			return Ok(response);
+		}
+		if let Some(etag) = response.headers.get(ETAG).and_then(|v| v.to_str().ok()) {
+			store.put(
+				&self.key,
+				EtagEntry {
+					etag: etag.to_string(),
+					body: Some(response.body.clone()),
+				},
+			);
+		}
+		Ok(response)
+	}
+}
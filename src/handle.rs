@@ -0,0 +1,220 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use time::Date;
+
+use crate::api::{
+	MainPageRequest, MembersRequest, MembersResponse, ProjectRequest, ProjectStatsRequest,
+	VersionRequest, VersionsRequest, VersionsResponse,
+};
+use crate::backend::HttpBackend;
+use crate::client::HangarClient;
+use crate::error::Result;
+use crate::object::{Channel, DayProjectStats, Page, Pagination, Platform, Project, Version};
+use crate::resolve::{DependencyReport, ResolveOptions};
+
+/// Filters for the versions of a project, as sent with a `VersionsRequest`.
+#[derive(Debug, Clone, Default)]
+pub struct VersionFilters {
+	/// A name of a version channel to filter for, either a `CommonChannel` or any other name
+	pub channel: Option<String>,
+	/// A platform to filter for
+	pub platform: Option<Platform>,
+	/// A platform version to filter for, like `1.20.4`
+	pub platform_version: Option<String>,
+	/// Whether to include hidden-by-default channels
+	pub include_hidden_channels: Option<bool>,
+}
+
+impl VersionFilters {
+	/// Creates the request for a page of a project's versions matching the filters.
+	pub fn request(self, slug: impl Into<String>, pagination: Pagination) -> VersionsRequest {
+		VersionsRequest::builder()
+			.slug(slug)
+			.pagination(pagination)
+			.channel_opt(self.channel)
+			.platform(self.platform)
+			.platform_version(self.platform_version)
+			.include_hidden_channels(self.include_hidden_channels)
+			.build()
+	}
+}
+
+/// Creates the request for a project's daily stats over a range of days.
+fn stats_request(slug: &str, days: RangeInclusive<Date>) -> Result<ProjectStatsRequest> {
+	let (from, to) = days.into_inner();
+	ProjectStatsRequest::new(slug, from, to)
+}
+
+/// A project on Hangar, for looking up things about it without repeating its slug.
+/// Creating one doesn't send anything; every method sends the request type it's named after, which can still be
+/// used directly with `HangarClient::send` for anything the handle doesn't cover.
+#[derive(Debug, Clone)]
+pub struct ProjectHandle<'a, B> {
+	client: &'a HangarClient<B>,
+	slug: String,
+}
+
+impl<'a, B: HttpBackend> ProjectHandle<'a, B> {
+	pub(crate) fn new(client: &'a HangarClient<B>, slug: String) -> Self {
+		Self { client, slug }
+	}
+
+	/// Gets the slug of the project.
+	pub fn slug(&self) -> &str {
+		&self.slug
+	}
+
+	/// Gets the project, with a `ProjectRequest`.
+	pub async fn get(&self) -> Result<Project> {
+		self.client.send(&ProjectRequest::new(&self.slug)).await
+	}
+
+	/// Gets a page of the project's versions matching the filters, with a `VersionsRequest`.
+	pub async fn versions(
+		&self,
+		filters: VersionFilters,
+		pagination: Pagination,
+	) -> Result<VersionsResponse> {
+		self.client
+			.send(&filters.request(&self.slug, pagination))
+			.await
+	}
+
+	/// Gets a version of the project by name, with a `VersionRequest`.
+	pub async fn version(&self, name: impl Into<String>) -> Result<Version> {
+		self.client
+			.send(&VersionRequest::new(&self.slug, name))
+			.await
+	}
+
+	/// Gets a version of the project by name and resolves its plugin dependencies on a platform, see
+	/// `HangarClient::resolve_dependencies`.
+	pub async fn resolve_dependencies(
+		&self,
+		name: impl Into<String>,
+		platform: Platform,
+		options: ResolveOptions,
+	) -> Result<DependencyReport> {
+		let version = self.version(name).await?;
+		self.client
+			.resolve_dependencies(&version, platform, options)
+			.await
+	}
+
+	/// Gets the latest version of the project in its default release channel, see
+	/// `HangarClient::latest_release_version`.
+	pub async fn latest_release(&self) -> Result<Version> {
+		self.client.latest_release_version(&self.slug).await
+	}
+
+	/// Gets the channels the project has published versions in, see `HangarClient::project_channels`.
+	pub async fn channels(&self) -> Result<Vec<Channel>> {
+		self.client.project_channels(&self.slug).await
+	}
+
+	/// Gets the project's main page, with a `MainPageRequest`.
+	pub async fn main_page(&self) -> Result<Page> {
+		let request = MainPageRequest::builder().slug(&self.slug).build();
+		self.client.send(&request).await
+	}
+
+	/// Gets a page of the project's members, with a `MembersRequest`.
+	pub async fn members(&self, pagination: Pagination) -> Result<MembersResponse> {
+		let request = MembersRequest::builder()
+			.slug(&self.slug)
+			.pagination(pagination)
+			.build();
+		self.client.send(&request).await
+	}
+
+	/// Gets the project's stats for each day in a range, with a `ProjectStatsRequest`.
+	pub async fn stats(
+		&self,
+		days: RangeInclusive<Date>,
+	) -> Result<BTreeMap<Date, DayProjectStats>> {
+		self.client.send(&stats_request(&self.slug, days)?).await
+	}
+}
+
+/// A project on Hangar for a `BlockingClient`, mirroring `ProjectHandle`.
+#[cfg(feature = "blocking")]
+#[derive(Debug, Clone)]
+pub struct BlockingProjectHandle<'a> {
+	client: &'a crate::blocking::BlockingClient,
+	slug: String,
+}
+
+#[cfg(feature = "blocking")]
+impl<'a> BlockingProjectHandle<'a> {
+	pub(crate) fn new(client: &'a crate::blocking::BlockingClient, slug: String) -> Self {
+		Self { client, slug }
+	}
+
+	/// Gets the slug of the project.
+	pub fn slug(&self) -> &str {
+		&self.slug
+	}
+
+	/// Gets the project, with a `ProjectRequest`.
+	pub fn get(&self) -> Result<Project> {
+		self.client.send(&ProjectRequest::new(&self.slug))
+	}
+
+	/// Gets a page of the project's versions matching the filters, with a `VersionsRequest`.
+	pub fn versions(
+		&self,
+		filters: VersionFilters,
+		pagination: Pagination,
+	) -> Result<VersionsResponse> {
+		self.client.send(&filters.request(&self.slug, pagination))
+	}
+
+	/// Gets a version of the project by name, with a `VersionRequest`.
+	pub fn version(&self, name: impl Into<String>) -> Result<Version> {
+		self.client.send(&VersionRequest::new(&self.slug, name))
+	}
+
+	/// Gets a version of the project by name and resolves its plugin dependencies on a platform, see
+	/// `BlockingClient::resolve_dependencies`.
+	pub fn resolve_dependencies(
+		&self,
+		name: impl Into<String>,
+		platform: Platform,
+		options: ResolveOptions,
+	) -> Result<DependencyReport> {
+		let version = self.version(name)?;
+		self.client
+			.resolve_dependencies(&version, platform, options)
+	}
+
+	/// Gets the latest version of the project in its default release channel, see
+	/// `BlockingClient::latest_release_version`.
+	pub fn latest_release(&self) -> Result<Version> {
+		self.client.latest_release_version(&self.slug)
+	}
+
+	/// Gets the channels the project has published versions in, see `BlockingClient::project_channels`.
+	pub fn channels(&self) -> Result<Vec<Channel>> {
+		self.client.project_channels(&self.slug)
+	}
+
+	/// Gets the project's main page, with a `MainPageRequest`.
+	pub fn main_page(&self) -> Result<Page> {
+		let request = MainPageRequest::builder().slug(&self.slug).build();
+		self.client.send(&request)
+	}
+
+	/// Gets a page of the project's members, with a `MembersRequest`.
+	pub fn members(&self, pagination: Pagination) -> Result<MembersResponse> {
+		let request = MembersRequest::builder()
+			.slug(&self.slug)
+			.pagination(pagination)
+			.build();
+		self.client.send(&request)
+	}
+
+	/// Gets the project's stats for each day in a range, with a `ProjectStatsRequest`.
+	pub fn stats(&self, days: RangeInclusive<Date>) -> Result<BTreeMap<Date, DayProjectStats>> {
+		self.client.send(&stats_request(&self.slug, days)?)
+	}
+}
@@ -1,2 +1,52 @@
+/// Logs a debug event when the `tracing` feature is enabled, compiling to nothing otherwise.
+#[allow(unused_macros)]
+macro_rules! debug {
+	($($arg:tt)*) => {
+		#[cfg(feature = "tracing")]
+		tracing::debug!($($arg)*);
+	};
+}
+
 pub mod api;
+pub mod auth;
+#[cfg(feature = "reqwest")]
+pub mod backend;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "reqwest")]
+pub mod cache;
+#[cfg(feature = "reqwest")]
+pub mod client;
+#[cfg(feature = "reqwest")]
+mod coalesce;
+#[cfg(feature = "reqwest")]
+pub mod deprecation;
+pub mod diff;
+pub mod error;
+#[cfg(feature = "reqwest")]
+pub mod etag;
+#[cfg(feature = "reqwest")]
+pub mod handle;
+#[cfg(feature = "markdown")]
+pub mod markdown;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "reqwest")]
+pub mod middleware;
+pub mod minecraft;
 pub mod object;
+#[cfg(feature = "reqwest")]
+pub mod rate_limit;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "reqwest")]
+pub mod resolve;
+#[cfg(feature = "reqwest")]
+pub mod retry;
+#[cfg(feature = "reqwest")]
+pub mod service;
+#[cfg(feature = "reqwest")]
+pub mod watch;
+
+pub use api::decode_response;
+pub use error::{Error, Result};
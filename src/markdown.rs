@@ -0,0 +1,285 @@
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd};
+
+use crate::object::{Page, Project};
+
+/// The markdown extensions Hangar renders pages with.
+fn options() -> Options {
+	Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS
+}
+
+/// Renders markdown to html which is safe to embed in a page even when the markdown isn't trusted.
+/// Html written in the markdown is escaped and shown as text rather than passed through, and links and images
+/// with urls which could run scripts, like `javascript:` urls, lose their urls.
+///
+/// ```
+/// let html = hangar_api::markdown::to_html("**hi** <script>alert(1)</script> [x](javascript:alert(1))");
+/// assert_eq!(
+///     html,
+///     "<p><strong>hi</strong> &lt;script&gt;alert(1)&lt;/script&gt; <a href=\"\">x</a></p>\n",
+/// );
+/// ```
+pub fn to_html(markdown: &str) -> String {
+	let events = Parser::new_ext(markdown, options()).map(|event| match event {
+		Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+		Event::Start(Tag::Link {
+			link_type,
+			dest_url,
+			title,
+			id,
+		}) => Event::Start(Tag::Link {
+			link_type,
+			dest_url: safe_url(dest_url),
+			title,
+			id,
+		}),
+		Event::Start(Tag::Image {
+			link_type,
+			dest_url,
+			title,
+			id,
+		}) => Event::Start(Tag::Image {
+			link_type,
+			dest_url: safe_url(dest_url),
+			title,
+			id,
+		}),
+		event => event,
+	});
+	let mut html = String::with_capacity(markdown.len() * 3 / 2);
+	pulldown_cmark::html::push_html(&mut html, events);
+	html
+}
+
+/// Strips the formatting from markdown, leaving plain text for e.g. a terminal.
+/// Blocks like paragraphs and headings are separated by blank lines, list items start with `- `, and links and
+/// images are replaced by their text. Html written in the markdown is dropped.
+///
+/// ```
+/// let text = hangar_api::markdown::to_plain("# Title\n\nSome *text* with [a link](https://example.com).\n\n- one\n- two");
+/// assert_eq!(text, "Title\n\nSome text with a link.\n\n- one\n- two");
+/// ```
+pub fn to_plain(markdown: &str) -> String {
+	let mut text = String::with_capacity(markdown.len());
+	for event in Parser::new_ext(markdown, options()) {
+		match event {
+			Event::Text(value) | Event::Code(value) => text.push_str(&value),
+			Event::SoftBreak => text.push(' '),
+			Event::HardBreak => text.push('\n'),
+			Event::Start(Tag::Item) => {
+				end_line(&mut text);
+				text.push_str("- ");
+			}
+			Event::TaskListMarker(done) => text.push_str(if done { "[x] " } else { "[ ] " }),
+			Event::Start(Tag::TableCell) if !text.ends_with('\n') && !text.is_empty() => {
+				text.push_str(" | ")
+			}
+			Event::End(TagEnd::TableHead | TagEnd::TableRow) => end_line(&mut text),
+			Event::End(
+				TagEnd::Paragraph
+				| TagEnd::Heading(_)
+				| TagEnd::CodeBlock
+				| TagEnd::BlockQuote(_)
+				| TagEnd::List(_)
+				| TagEnd::Table,
+			)
+			| Event::Rule => end_block(&mut text),
+			_ => {}
+		}
+	}
+	text.trim_end().to_string()
+}
+
+/// Starts a new line unless the text is empty or already on one.
+fn end_line(text: &mut String) {
+	if !text.is_empty() && !text.ends_with('\n') {
+		text.push('\n');
+	}
+}
+
+/// Leaves a blank line after the text, unless it's empty or already ends with one.
+fn end_block(text: &mut String) {
+	end_line(text);
+	if !text.is_empty() && !text.ends_with("\n\n") {
+		text.push('\n');
+	}
+}
+
+/// Removes urls with schemes which could run scripts when clicked or loaded.
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+	let scheme: String = url
+		.trim_start()
+		.chars()
+		.take_while(|c| *c != ':')
+		.filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control())
+		.map(|c| c.to_ascii_lowercase())
+		.collect();
+	let has_scheme = url.contains(':') && !scheme.contains(['/', '?', '#']);
+	if has_scheme && matches!(scheme.as_str(), "javascript" | "vbscript" | "data" | "file") {
+		CowStr::Borrowed("")
+	} else {
+		url
+	}
+}
+
+impl Page {
+	/// Renders the page's markdown to html which is safe to embed, see `markdown::to_html`.
+	pub fn to_html(&self) -> String {
+		to_html(&self.contents)
+	}
+
+	/// Strips the formatting from the page's markdown, see `markdown::to_plain`.
+	pub fn to_plain(&self) -> String {
+		to_plain(&self.contents)
+	}
+}
+
+impl Project {
+	/// Renders the project's short description to html which is safe to embed, see `markdown::to_html`.
+	pub fn description_html(&self) -> String {
+		to_html(&self.description)
+	}
+
+	/// Strips the formatting from the project's short description, see `markdown::to_plain`.
+	pub fn description_plain(&self) -> String {
+		to_plain(&self.description)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn project(description: &str) -> Project {
+		let mut project: serde_json::Value =
+			serde_json::from_str(include_str!("../tests/fixtures/project.json")).unwrap();
+		project["description"] = description.into();
+		serde_json::from_value(project).unwrap()
+	}
+
+	/// Checks that html has no tags other than the ones markdown produces, with nothing which could run scripts.
+	fn assert_inert(html: &str) {
+		let lower = html.to_ascii_lowercase();
+		for unsafe_html in [
+			"<script",
+			"<img src=x",
+			"<iframe",
+			"<b ",
+			"<div",
+			"=\"javascript:",
+			"\" on",
+		] {
+			assert!(!lower.contains(unsafe_html), "{unsafe_html} in {html}");
+		}
+	}
+
+	#[test]
+	fn scripts_are_escaped() {
+		let html = to_html(
+			"<script>alert(1)</script>\n\nHello <script src=\"https://evil.example\"></script>",
+		);
+		assert_inert(&html);
+		assert!(
+			html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"),
+			"{html}"
+		);
+		assert!(html.starts_with("&lt;script&gt;"), "{html}");
+	}
+
+	#[test]
+	fn script_urls_are_removed() {
+		for markdown in [
+			"[x](javascript:alert(1))",
+			"[x](JavaScript:alert(1))",
+			"[x]( javascript:alert(1))",
+			"[x](vbscript:msgbox(1))",
+			"[x](data:text/html;base64,PHNjcmlwdD4=)",
+			"![x](javascript:alert(1))",
+			"<javascript:alert(1)>",
+			"[x][ref]\n\n[ref]: javascript:alert(1)",
+		] {
+			let html = to_html(markdown);
+			assert_inert(&html);
+			assert!(!html.contains("alert(1)\""), "{markdown}: {html}");
+			assert!(
+				html.contains("href=\"\"") || html.contains("src=\"\""),
+				"{markdown}: {html}"
+			);
+		}
+	}
+
+	#[test]
+	fn other_urls_are_kept() {
+		assert_eq!(
+			to_html("[docs](https://docs.papermc.io) [wiki](/wiki/a:b) ![icon](icon.png)"),
+			"<p><a href=\"https://docs.papermc.io\">docs</a> <a href=\"/wiki/a:b\">wiki</a> <img src=\"icon.png\" alt=\"icon\" /></p>\n"
+		);
+	}
+
+	#[test]
+	fn event_handlers_are_escaped() {
+		for markdown in [
+			"<img src=x onerror=alert(1)>",
+			"Click <b onclick=\"alert(1)\">here</b>",
+			"<div onmouseover=\"alert(1)\">\n\nhover\n\n</div>",
+			"![x](icon.png \"\\\" onerror=\\\"alert(1)\")",
+		] {
+			let html = to_html(markdown);
+			assert_inert(&html);
+		}
+	}
+
+	#[test]
+	fn raw_html_is_shown_as_text() {
+		let project =
+			project("A <b>bold</b> plugin<br>with <iframe src=\"https://evil.example\"></iframe>");
+		let html = project.description_html();
+		assert_inert(&html);
+		assert_eq!(
+			html,
+			"<p>A &lt;b&gt;bold&lt;/b&gt; plugin&lt;br&gt;with &lt;iframe src=\"https://evil.example\"&gt;&lt;/iframe&gt;</p>\n"
+		);
+		assert_eq!(
+			project.description,
+			"A <b>bold</b> plugin<br>with <iframe src=\"https://evil.example\"></iframe>"
+		);
+	}
+
+	#[test]
+	fn plain_text_drops_formatting_and_html() {
+		let project = project(
+			"**Maintenance** mode with a <b>custom</b> [motd](https://example.com) and `icon`",
+		);
+		assert_eq!(
+			project.description_plain(),
+			"Maintenance mode with a custom motd and icon"
+		);
+		let text = to_plain(concat!(
+			"## Features\n\n",
+			"1. Custom motd\n",
+			"2. Custom icon\n\n",
+			"- [x] Paper\n",
+			"- [ ] Velocity\n\n",
+			"| Command | Permission |\n",
+			"| --- | --- |\n",
+			"| /maintenance | maintenance.admin |\n\n",
+			"> Quoted\n\n",
+			"<div>dropped</div>\n\n",
+			"---\n\n",
+			"Line one  \nline two\nsame line",
+		));
+		assert_eq!(
+			text,
+			concat!(
+				"Features\n\n",
+				"- Custom motd\n",
+				"- Custom icon\n\n",
+				"- [x] Paper\n",
+				"- [ ] Velocity\n\n",
+				"Command | Permission\n",
+				"/maintenance | maintenance.admin\n\n",
+				"Quoted\n\n",
+				"Line one\nline two same line",
+			)
+		);
+	}
+}
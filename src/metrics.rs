@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use crate::backend::HttpResponse;
+use crate::error::{Error, Result};
+
+/// Gets the kind of endpoint a url points to, e.g. `projects` or `versions`.
+/// Urls outside of the api are downloads from external hosts.
+pub(crate) fn endpoint(base_url: &str, url: &str) -> &'static str {
+	let Some(path) = url.strip_prefix(base_url) else {
+		return "downloads";
+	};
+	let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+	match segments.as_slice() {
+		[.., "download"] => "downloads",
+		["projects", _, "versions" | "latest" | "latestrelease", ..] => "versions",
+		["projects", ..] => "projects",
+		["pages", ..] => "pages",
+		["users" | "authors" | "staff", ..] => "users",
+		["authenticate", ..] => "authenticate",
+		["keys", ..] => "keys",
+		["permissions", ..] => "permissions",
+		_ => "other",
+	}
+}
+
+/// Records the outcome of an attempt at sending a request.
+pub(crate) fn record_attempt(
+	endpoint: &'static str,
+	result: &Result<HttpResponse>,
+	elapsed: Duration,
+) {
+	let status = status_class(result);
+	metrics::counter!("hangar_requests_total", "endpoint" => endpoint, "status" => status)
+		.increment(1);
+	metrics::histogram!(
+		"hangar_request_duration_seconds",
+		"endpoint" => endpoint,
+		"status" => status
+	)
+	.record(elapsed.as_secs_f64());
+	if let Err(Error::RateLimited { .. }) = result {
+		metrics::counter!("hangar_rate_limited_total", "endpoint" => endpoint).increment(1);
+	}
+}
+
+/// Records that a failed attempt is being retried.
+pub(crate) fn record_retry(endpoint: &'static str) {
+	metrics::counter!("hangar_retries_total", "endpoint" => endpoint).increment(1);
+}
+
+/// Gets the class of the status an attempt ended with.
+fn status_class(result: &Result<HttpResponse>) -> &'static str {
+	let status = match result {
+		Ok(response) => response.status,
+		Err(
+			Error::Api { status, .. }
+			| Error::Unavailable { status, .. }
+			| Error::Redirected { status, .. },
+		) => *status,
+		Err(Error::NotFound | Error::Unauthorized { .. } | Error::RateLimited { .. }) => 400,
+		Err(_) => return "error",
+	};
+	match status {
+		100..=199 => "1xx",
+		200..=299 => "2xx",
+		300..=399 => "3xx",
+		400..=499 => "4xx",
+		500..=599 => "5xx",
+		_ => "error",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const API: &str = "https://hangar.papermc.io/api/v1";
+
+	#[test]
+	fn endpoints_are_grouped_by_kind() {
+		let cases = [
+			("/projects", "projects"),
+			("/projects/Maintenance", "projects"),
+			("/projects/Maintenance/members", "projects"),
+			("/projects/Maintenance/versions", "versions"),
+			("/projects/Maintenance/versions/4.2.1", "versions"),
+			("/projects/Maintenance/latestrelease", "versions"),
+			(
+				"/projects/Maintenance/versions/4.2.1/PAPER/download",
+				"downloads",
+			),
+			("/pages/main/Maintenance", "pages"),
+			("/users/kennytv", "users"),
+			("/authors", "users"),
+			("/authenticate", "authenticate"),
+			("/keys", "keys"),
+			("/permissions", "permissions"),
+			("/something/else", "other"),
+		];
+		for (path, kind) in cases {
+			assert_eq!(endpoint(API, &format!("{API}{path}")), kind, "{path}");
+		}
+	}
+
+	#[test]
+	fn external_urls_are_downloads() {
+		assert_eq!(
+			endpoint(
+				API,
+				"https://github.com/kennytv/Maintenance/releases/download/4.2.1/Maintenance.jar"
+			),
+			"downloads"
+		);
+	}
+}
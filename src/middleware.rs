@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use crate::backend::{HttpRequest, HttpResponse, MaybeSend, MaybeSync};
+use crate::error::Result;
+
+/// Hooks into every request a client sends, e.g. to add headers or audit the urls hit.
+///
+/// Middleware runs in the order it was registered on the client builder. It fires once per attempt, so a request
+/// which is retried passes through every middleware again. Returning an error from either hook fails the attempt
+/// with that error, which is then retried only if the client's retry policy considers it retryable.
+pub trait Middleware: MaybeSend + MaybeSync {
+	/// Called before a request is sent, allowing it to be modified.
+	fn on_request(&self, request: &mut HttpRequest) -> Result<()> {
+		let _ = request;
+		Ok(())
+	}
+
+	/// Called when a response is received, before its status is checked.
+	fn on_response(&self, response: &HttpResponse, elapsed: Duration) -> Result<()> {
+		let _ = (response, elapsed);
+		Ok(())
+	}
+}
+
+/// Middleware running a closure before every request.
+pub(crate) struct OnRequest<F>(pub(crate) F);
+
+impl<F> Middleware for OnRequest<F>
+where
+	F: Fn(&mut HttpRequest) -> Result<()> + MaybeSend + MaybeSync,
+{
+	fn on_request(&self, request: &mut HttpRequest) -> Result<()> {
+		(self.0)(request)
+	}
+}
+
+/// Middleware running a closure after every response.
+pub(crate) struct OnResponse<F>(pub(crate) F);
+
+impl<F> Middleware for OnResponse<F>
+where
+	F: Fn(&HttpResponse, Duration) -> Result<()> + MaybeSend + MaybeSync,
+{
+	fn on_response(&self, response: &HttpResponse, elapsed: Duration) -> Result<()> {
+		(self.0)(response, elapsed)
+	}
+}
@@ -0,0 +1,275 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::ParseVersionError;
+
+/// A release of Minecraft like `1.20.4`, ordered from oldest to newest.
+/// Versions without a patch number like `1.20` are the same as patch 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MinecraftVersion {
+	pub major: u32,
+	pub minor: u32,
+	pub patch: u32,
+}
+
+impl MinecraftVersion {
+	pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+		Self {
+			major,
+			minor,
+			patch,
+		}
+	}
+}
+
+impl Display for MinecraftVersion {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.patch == 0 {
+			write!(f, "{}.{}", self.major, self.minor)
+		} else {
+			write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+		}
+	}
+}
+
+impl FromStr for MinecraftVersion {
+	type Err = ParseVersionError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let error = || ParseVersionError(s.to_string());
+		let mut parts = s.trim().split('.');
+		let mut number = |required| match parts.next() {
+			Some(part) => part.parse::<u32>().map_err(|_| error()),
+			None if required => Err(error()),
+			None => Ok(0),
+		};
+		let version = Self::new(number(true)?, number(true)?, number(false)?);
+		match parts.next() {
+			Some(_) => Err(error()),
+			None => Ok(version),
+		}
+	}
+}
+
+/// A range of Minecraft versions as Hangar lists them for platform dependencies.
+/// Single versions (`1.20.4`), every patch of a release (`1.20.x`), and inclusive ranges between the two
+/// (`1.19-1.20.x`) are supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VersionRange {
+	/// The oldest version in the range
+	pub min: MinecraftVersion,
+	/// The newest version in the range, with a patch of `u32::MAX` when every patch is included
+	pub max: MinecraftVersion,
+}
+
+impl VersionRange {
+	/// Checks whether a version is within the range.
+	pub fn contains(&self, version: &MinecraftVersion) -> bool {
+		(self.min..=self.max).contains(version)
+	}
+
+	/// Parses one end of a range, which may include every patch of a release.
+	fn parse_bound(s: &str) -> Result<Self, ParseVersionError> {
+		let s = s.trim();
+		match s.strip_suffix(".x") {
+			Some(release) => {
+				let min: MinecraftVersion = release
+					.parse()
+					.map_err(|_| ParseVersionError(s.to_string()))?;
+				if min.patch != 0 || release.matches('.').count() != 1 {
+					return Err(ParseVersionError(s.to_string()));
+				}
+				Ok(Self {
+					min,
+					max: MinecraftVersion {
+						patch: u32::MAX,
+						..min
+					},
+				})
+			}
+			None => {
+				let version = s.parse()?;
+				Ok(Self {
+					min: version,
+					max: version,
+				})
+			}
+		}
+	}
+}
+
+/// Writes one end of a range, using `x` for every patch.
+fn write_bound(f: &mut std::fmt::Formatter<'_>, version: &MinecraftVersion) -> std::fmt::Result {
+	if version.patch == u32::MAX {
+		write!(f, "{}.{}.x", version.major, version.minor)
+	} else {
+		write!(f, "{version}")
+	}
+}
+
+impl Display for VersionRange {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let whole_release = self.min.patch == 0
+			&& self.max
+				== MinecraftVersion {
+					patch: u32::MAX,
+					..self.min
+				};
+		if whole_release {
+			write_bound(f, &self.max)
+		} else if self.min == self.max {
+			write_bound(f, &self.min)
+		} else {
+			write_bound(f, &self.min)?;
+			write!(f, "-")?;
+			write_bound(f, &self.max)
+		}
+	}
+}
+
+impl FromStr for VersionRange {
+	type Err = ParseVersionError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.split_once('-') {
+			Some((start, end)) => {
+				let min = Self::parse_bound(start)?.min;
+				let max = Self::parse_bound(end)?.max;
+				if min > max {
+					return Err(ParseVersionError(s.to_string()));
+				}
+				Ok(Self { min, max })
+			}
+			None => Self::parse_bound(s),
+		}
+	}
+}
+
+impl From<MinecraftVersion> for VersionRange {
+	fn from(value: MinecraftVersion) -> Self {
+		Self {
+			min: value,
+			max: value,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::object::{Platform, Version};
+
+	fn version(s: &str) -> MinecraftVersion {
+		s.parse().unwrap()
+	}
+
+	fn range(s: &str) -> VersionRange {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn versions_parse_with_or_without_a_patch() {
+		assert_eq!(version("1.20.4"), MinecraftVersion::new(1, 20, 4));
+		assert_eq!(version("1.20"), MinecraftVersion::new(1, 20, 0));
+		assert_eq!(version(" 1.8.9 "), MinecraftVersion::new(1, 8, 9));
+		assert_eq!(version("1.20.0"), version("1.20"));
+		assert_eq!(version("1.20.4").to_string(), "1.20.4");
+		assert_eq!(version("1.20.0").to_string(), "1.20");
+	}
+
+	#[test]
+	fn versions_are_ordered_oldest_first() {
+		let mut versions = ["1.21", "1.8.9", "1.20.4", "1.20", "1.9", "1.20.10"].map(version);
+		versions.sort();
+		assert_eq!(
+			versions.map(|version| version.to_string()),
+			["1.8.9", "1.9", "1.20", "1.20.4", "1.20.10", "1.21"]
+		);
+	}
+
+	#[test]
+	fn single_versions_only_contain_themselves() {
+		let single = range("1.20.4");
+		assert_eq!(single, VersionRange::from(version("1.20.4")));
+		assert!(single.contains(&version("1.20.4")));
+		assert!(!single.contains(&version("1.20.3")));
+		assert!(!single.contains(&version("1.20.5")));
+		assert_eq!(single.to_string(), "1.20.4");
+	}
+
+	#[test]
+	fn wildcards_contain_every_patch_of_the_release() {
+		let release = range("1.20.x");
+		assert_eq!(release.min, version("1.20"));
+		for contained in ["1.20", "1.20.4", "1.20.6"] {
+			assert!(release.contains(&version(contained)), "{contained}");
+		}
+		for outside in ["1.19.4", "1.21", "1.21.1"] {
+			assert!(!release.contains(&version(outside)), "{outside}");
+		}
+		assert_eq!(release.to_string(), "1.20.x");
+	}
+
+	#[test]
+	fn ranges_are_inclusive() {
+		let versions = range("1.19-1.20.x");
+		assert_eq!(versions.min, version("1.19"));
+		for contained in ["1.19", "1.19.4", "1.20", "1.20.6"] {
+			assert!(versions.contains(&version(contained)), "{contained}");
+		}
+		for outside in ["1.18.2", "1.21"] {
+			assert!(!versions.contains(&version(outside)), "{outside}");
+		}
+		assert_eq!(versions.to_string(), "1.19-1.20.x");
+
+		let versions = range("1.8-1.21");
+		assert!(versions.contains(&version("1.21")));
+		assert!(!versions.contains(&version("1.21.1")));
+		assert_eq!(versions.to_string(), "1.8-1.21");
+	}
+
+	#[test]
+	fn malformed_versions_are_rejected() {
+		for malformed in [
+			"", "1", "1.", "1.20.", "a.b", "1.20.4.1", "1.-1", "v1.20", "1.20.x.x",
+		] {
+			assert_eq!(
+				malformed.parse::<MinecraftVersion>(),
+				Err(ParseVersionError(malformed.to_string()))
+			);
+		}
+		for malformed in [
+			"",
+			"1.x",
+			"1.20.4.x",
+			"1.20.1.x",
+			"x",
+			"1.20-",
+			"-1.20",
+			"1.21-1.20",
+			"1.20.x-1.19",
+			"1.20.y",
+		] {
+			assert!(malformed.parse::<VersionRange>().is_err(), "{malformed}");
+		}
+	}
+
+	#[test]
+	fn supported_versions_are_parsed_from_platform_dependencies() {
+		let mut json: serde_json::Value =
+			serde_json::from_str(include_str!("../tests/fixtures/version_internal.json")).unwrap();
+		json["platformDependencies"]["PAPER"] = serde_json::json!(["1.8-1.19", "1.20.x", "latest"]);
+		let version: Version = serde_json::from_value(json).unwrap();
+		assert_eq!(
+			version.supported_versions(Platform::Paper),
+			[range("1.8-1.19"), range("1.20.x")]
+		);
+		assert_eq!(
+			version.supported_versions(Platform::Velocity),
+			[range("3.3")]
+		);
+		assert!(version.supported_versions(Platform::Waterfall).is_empty());
+		assert!(version.supports(Platform::Paper, &MinecraftVersion::new(1, 20, 4)));
+		assert!(!version.supports(Platform::Paper, &MinecraftVersion::new(1, 21, 0)));
+		assert!(!version.supports(Platform::Waterfall, &MinecraftVersion::new(1, 20, 4)));
+	}
+}
@@ -1,9 +1,14 @@
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
 
 use serde::{Deserialize, Serialize};
-use time::OffsetDateTime;
+use time::{Date, OffsetDateTime};
 
-#[derive(Debug, Serialize)]
+use crate::api::UserRequest;
+use crate::error::{DependencyError, PaginationError, ParseColorError, ParseEnumError};
+use crate::minecraft::{MinecraftVersion, VersionRange};
+
+/// Which page of results to return. Requests check it's valid before they're sent.
+#[derive(Debug, Clone, Serialize)]
 pub struct Pagination {
 	pub limit: i64,
 	pub offset: i64,
@@ -18,6 +23,40 @@ impl Default for Pagination {
 	}
 }
 
+impl Pagination {
+	/// The most results Hangar returns in a single page.
+	pub const MAX_LIMIT: i64 = 25;
+
+	/// Creates pagination information, checking the limit is within `1..=25` and the offset isn't negative.
+	pub fn new(limit: i64, offset: i64) -> Result<Self, PaginationError> {
+		let pagination = Self { limit, offset };
+		pagination.validate()?;
+		Ok(pagination)
+	}
+
+	/// Creates pagination information for a page of results, where the first page is 0.
+	/// Like the `From` impl this isn't checked until the request is sent.
+	pub fn page(number: i64, per_page: i64) -> Self {
+		Self {
+			limit: per_page,
+			offset: number.saturating_mul(per_page),
+		}
+	}
+
+	/// Checks the limit is within `1..=25` and the offset isn't negative.
+	pub fn validate(&self) -> Result<(), PaginationError> {
+		if !(1..=Self::MAX_LIMIT).contains(&self.limit) {
+			return Err(PaginationError::Limit(self.limit));
+		}
+		if self.offset < 0 {
+			return Err(PaginationError::Offset(self.offset));
+		}
+		Ok(())
+	}
+}
+
+/// Creates pagination information from a limit and offset without checking them. Invalid values are caught when
+/// the request is sent instead, use `Pagination::new` to catch them sooner.
 impl From<(i64, i64)> for Pagination {
 	fn from(value: (i64, i64)) -> Self {
 		Self {
@@ -27,29 +66,155 @@ impl From<(i64, i64)> for Pagination {
 	}
 }
 
-/// for some reason sorting is.. backwards by default? and there's no mention of this in the api documentation
-#[derive(Debug, Clone, Copy, Serialize)]
+/// Fields projects can be sorted by.
+/// Hangar sorts ascending unless the field is prefixed with `-`, which `ProjectsRequest` adds based on its direction.
+/// Serialized, each field keeps the form Hangar takes for its default direction, so everything but `Slug` is
+/// prefixed with `-`. The form without the `-` is accepted too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[serde(rename_all = "snake_case")]
 pub enum ProjectsSort {
-	#[serde(rename = "-views")]
+	#[serde(rename = "-views", alias = "views")]
 	Views,
-	#[serde(rename = "-downloads")]
+	#[serde(rename = "-downloads", alias = "downloads")]
 	Downloads,
-	#[serde(rename = "-newest")]
+	#[serde(rename = "-newest", alias = "newest")]
 	Newest,
-	#[serde(rename = "-stars")]
+	#[serde(rename = "-stars", alias = "stars")]
 	Stars,
-	#[serde(rename = "-updated")]
+	#[serde(rename = "-updated", alias = "updated")]
 	Updated,
-	#[serde(rename = "-recent-downloads")]
+	#[serde(rename = "-recent-downloads", alias = "recent-downloads")]
 	RecentDownloads,
-	#[serde(rename = "-recent-views")]
+	#[serde(rename = "-recent-views", alias = "recent-views")]
 	RecentViews,
-	Slug, // this one *isn't* inverted though..
+	Slug,
+}
+
+impl ProjectsSort {
+	/// The direction used when none is given. Everything but `Slug` sorts descending, so the most viewed, newest,
+	/// etc. projects come first.
+	pub fn default_direction(self) -> SortDirection {
+		match self {
+			Self::Slug => SortDirection::Ascending,
+			_ => SortDirection::Descending,
+		}
+	}
+
+	/// Gets the value Hangar expects for sorting by this field in a direction.
+	pub fn query_value(self, direction: SortDirection) -> String {
+		let field = match self {
+			Self::Views => "views",
+			Self::Downloads => "downloads",
+			Self::Newest => "newest",
+			Self::Stars => "stars",
+			Self::Updated => "updated",
+			Self::RecentDownloads => "recent-downloads",
+			Self::RecentViews => "recent-views",
+			Self::Slug => "slug",
+		};
+		direction.apply(field)
+	}
+
+	/// Compares two projects by this field the way Hangar sorts them, in the given direction.
+	pub fn compare(self, a: &Project, b: &Project, direction: SortDirection) -> std::cmp::Ordering {
+		let ordering = match self {
+			Self::Views => a.stats.views.cmp(&b.stats.views),
+			Self::Downloads => a.stats.downloads.cmp(&b.stats.downloads),
+			Self::Newest => a.created_at.cmp(&b.created_at),
+			Self::Stars => a.stats.stars.cmp(&b.stats.stars),
+			Self::Updated => a.last_updated.cmp(&b.last_updated),
+			Self::RecentDownloads => a.stats.recent_downloads.cmp(&b.stats.recent_downloads),
+			Self::RecentViews => a.stats.recent_views.cmp(&b.stats.recent_views),
+			Self::Slug => a
+				.namespace
+				.slug
+				.to_lowercase()
+				.cmp(&b.namespace.slug.to_lowercase()),
+		};
+		match direction {
+			SortDirection::Ascending => ordering,
+			SortDirection::Descending => ordering.reverse(),
+		}
+	}
+}
+
+/// Fields users can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "camelCase")]
+pub enum UsersSort {
+	Name,
+	CreatedAt,
+	ProjectCount,
+}
+
+impl UsersSort {
+	/// Gets the value Hangar expects for sorting by this field in a direction.
+	pub fn query_value(self, direction: SortDirection) -> String {
+		let field = match self {
+			Self::Name => "name",
+			Self::CreatedAt => "createdAt",
+			Self::ProjectCount => "projectCount",
+		};
+		direction.apply(field)
+	}
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+/// Fields versions can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "camelCase")]
+pub enum VersionsSort {
+	CreatedAt,
+}
+
+impl VersionsSort {
+	/// Gets the value Hangar expects for sorting by this field in a direction.
+	pub fn query_value(self, direction: SortDirection) -> String {
+		let field = match self {
+			Self::CreatedAt => "createdAt",
+		};
+		direction.apply(field)
+	}
+}
+
+impl Display for ProjectsSort {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let s = match self {
+			Self::Views => "Most Views",
+			Self::Downloads => "Most Downloads",
+			Self::Newest => "Newest",
+			Self::Stars => "Most Stars",
+			Self::Updated => "Recently Updated",
+			Self::RecentDownloads => "Recent Downloads",
+			Self::RecentViews => "Recent Views",
+			Self::Slug => "Slug",
+		};
+		write!(f, "{s}")
+	}
+}
+
+/// The direction to sort results in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+	Ascending,
+	Descending,
+}
+
+impl SortDirection {
+	/// Prefixes a sort field with `-` when sorting descending, which is how Hangar tells the directions apart.
+	fn apply(self, field: &str) -> String {
+		match self {
+			Self::Ascending => field.to_string(),
+			Self::Descending => format!("-{field}"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[serde(rename_all = "snake_case")]
 pub enum Category {
@@ -85,7 +250,33 @@ impl Display for Category {
 	}
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+impl Category {
+	/// Every category, in the order Hangar lists them.
+	pub const ALL: [Self; 11] = [
+		Self::AdminTools,
+		Self::Chat,
+		Self::DevTools,
+		Self::Economy,
+		Self::Gameplay,
+		Self::Games,
+		Self::Protection,
+		Self::RolePlaying,
+		Self::WorldManagement,
+		Self::Misc,
+		Self::Undefined,
+	];
+}
+
+/// Parses either the serde form (`admin_tools`) or the display form (`Admin Tools`), ignoring case.
+impl FromStr for Category {
+	type Err = ParseEnumError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse_variant("category", s, &Self::ALL)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Platform {
@@ -105,13 +296,64 @@ impl Display for Platform {
 	}
 }
 
-#[derive(Debug, Deserialize)]
+impl Platform {
+	/// Every platform.
+	pub const ALL: [Self; 3] = [Self::Paper, Self::Waterfall, Self::Velocity];
+}
+
+/// Parses either the serde form (`PAPER`) or the display form (`Paper`), ignoring case.
+impl FromStr for Platform {
+	type Err = ParseEnumError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse_variant("platform", s, &Self::ALL)
+	}
+}
+
+/// Finds the variant whose display form matches a string, treating spaces, dashes, and underscores the same and
+/// ignoring case.
+fn parse_variant<T: Copy + Display>(
+	kind: &'static str,
+	s: &str,
+	variants: &[T],
+) -> Result<T, ParseEnumError> {
+	fn normalize(s: &str) -> String {
+		s.trim()
+			.chars()
+			.map(|c| match c {
+				' ' | '-' => '_',
+				c => c.to_ascii_lowercase(),
+			})
+			.collect()
+	}
+
+	let normalized = normalize(s);
+	variants
+		.iter()
+		.copied()
+		.find(|variant| normalize(&variant.to_string()) == normalized)
+		.ok_or_else(|| ParseEnumError {
+			kind,
+			value: s.to_string(),
+			expected: variants
+				.iter()
+				.map(|variant| variant.to_string())
+				.collect::<Vec<_>>()
+				.join(", "),
+		})
+}
+
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Project {
 	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
 	pub created_at: OffsetDateTime,
+	/// The internal id of the project, which unlike its slug never changes
+	#[serde(default)]
+	pub id: Option<i64>,
 	/// The unique name of the project
-	pub name: String,
+	pub name: ProjectName,
 	/// The namespace of the project
 	pub namespace: Namespace,
 	/// Stats of the project
@@ -130,23 +372,278 @@ pub struct Project {
 	/// Information about your interactions with the project
 	pub user_actions: UserActions,
 	/// The settings of the project
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub settings: ProjectSettings,
+	/// Information only staff can see, which Hangar leaves out unless the request is authenticated as a user with
+	/// the `ModNotesAndFlags` permission
+	#[serde(default)]
+	pub admin: Option<ProjectAdminInfo>,
+}
+
+impl Project {
+	/// Checks whether the project is available to everyone, see `Visibility::is_available`.
+	pub fn is_available(&self) -> bool {
+		self.visibility.is_available()
+	}
+
+	/// Gets the url to the project's icon, resolving relative urls against the official Hangar instance.
+	pub fn avatar_url_absolute(&self) -> String {
+		absolute_url(&self.avatar_url)
+	}
+
+	/// Checks whether a name, e.g. one typed to install a project, names this project rather than just resembling
+	/// it. Ignoring ascii case, the name has to match one of:
+	/// - the project's display name, like `Cool Plugin!`
+	/// - the project's slug, like `CoolPlugin`
+	/// - the project's slug with only letters and digits kept, after doing the same to the name. Hangar leaves
+	///   spaces and some punctuation out of slugs, so `cool plugin` and `cool-plugin` both name a project with the
+	///   slug `CoolPlugin`, but `coolplugins` doesn't.
+	pub fn is_named(&self, name: &str) -> bool {
+		let name = name.trim();
+		let alphanumeric = |s: &str| {
+			s.chars()
+				.filter(char::is_ascii_alphanumeric)
+				.map(|c| c.to_ascii_lowercase())
+				.collect::<String>()
+		};
+		self.name.eq_ignore_ascii_case(name)
+			|| self.namespace.slug.eq_ignore_ascii_case(name)
+			|| (!alphanumeric(name).is_empty()
+				&& alphanumeric(name) == alphanumeric(&self.namespace.slug))
+	}
+}
+
+/// The parts of a project only staff can see, sent with `Project` to users with the `ModNotesAndFlags` permission.
+/// Fields Hangar leaves out are empty.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ProjectAdminInfo {
+	/// The notes staff left on the project, oldest first
+	#[serde(default, deserialize_with = "null_as_default")]
+	pub notes: Vec<ProjectNote>,
+	/// How many flags users raised on the project which haven't been resolved yet
+	#[serde(default)]
+	pub flag_count: u64,
+}
+
+impl ProjectAdminInfo {
+	/// Checks whether the project has flags waiting for staff to look at them.
+	pub fn has_open_flags(&self) -> bool {
+		self.flag_count > 0
+	}
+}
+
+/// A note staff left on a project, e.g. to explain why it was hidden.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ProjectNote {
+	pub id: u64,
+	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
+	pub created_at: OffsetDateTime,
+	/// The text of the note
+	pub message: String,
+	/// The name of the user who left the note
+	pub user_name: String,
+}
+
+impl ProjectCompact {
+	/// Gets the url to the project's icon, resolving relative urls against the official Hangar instance.
+	pub fn avatar_url_absolute(&self) -> String {
+		absolute_url(&self.avatar_url)
+	}
+}
+
+/// The url of the official Hangar instance, which relative urls in responses are relative to.
+const HANGAR_URL: &str = "https://hangar.papermc.io";
+
+/// Resolves a url Hangar sent against the official instance if it's relative, leaving absolute urls as they are.
+fn absolute_url(url: &str) -> String {
+	if url.contains("://") {
+		url.to_string()
+	} else if let Some(url) = url.strip_prefix("//") {
+		format!("https://{url}")
+	} else {
+		format!("{HANGAR_URL}/{}", url.trim_start_matches('/'))
+	}
+}
+
+/// The compact form of a project Hangar uses in user specific listings, like starred projects.
+/// Unlike `Project` it has no description, settings or user actions.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ProjectCompact {
+	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
+	pub created_at: OffsetDateTime,
+	/// The internal id of the project, which unlike its slug never changes
+	#[serde(default)]
+	pub id: Option<i64>,
+	/// The unique name of the project
+	pub name: ProjectName,
+	/// The namespace of the project
+	pub namespace: Namespace,
+	/// Stats of the project
+	pub stats: ProjectStats,
+	/// The category of the project
+	pub category: Category,
+	/// The last time the project was updated
+	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
+	pub last_updated: OffsetDateTime,
+	/// The visibility of a project or version
+	pub visibility: Visibility,
+	/// The url to the project's icon
+	pub avatar_url: String,
+}
+
+/// Drops the fields the compact form doesn't have, so full and compact projects can be listed together.
+impl From<Project> for ProjectCompact {
+	fn from(value: Project) -> Self {
+		Self {
+			created_at: value.created_at,
+			id: value.id,
+			name: value.name,
+			namespace: value.namespace,
+			stats: value.stats,
+			category: value.category,
+			last_updated: value.last_updated,
+			visibility: value.visibility,
+			avatar_url: value.avatar_url,
+		}
+	}
+}
+
+/// Defines a string newtype which derefs to `str` and converts from strings.
+macro_rules! string_newtype {
+	($(#[$meta:meta])* $name:ident) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+		#[serde(transparent)]
+		pub struct $name(pub String);
+
+		impl $name {
+			/// Gets the string.
+			pub fn as_str(&self) -> &str {
+				&self.0
+			}
+
+			/// Gets the owned string.
+			pub fn into_string(self) -> String {
+				self.0
+			}
+		}
+
+		impl std::ops::Deref for $name {
+			type Target = str;
+
+			fn deref(&self) -> &str {
+				&self.0
+			}
+		}
+
+		impl AsRef<str> for $name {
+			fn as_ref(&self) -> &str {
+				&self.0
+			}
+		}
+
+		impl Display for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				f.write_str(&self.0)
+			}
+		}
+
+		impl From<String> for $name {
+			fn from(value: String) -> Self {
+				Self(value)
+			}
+		}
+
+		impl From<&String> for $name {
+			fn from(value: &String) -> Self {
+				Self(value.clone())
+			}
+		}
+
+		impl From<&str> for $name {
+			fn from(value: &str) -> Self {
+				Self(value.to_string())
+			}
+		}
+
+		impl From<&$name> for $name {
+			fn from(value: &$name) -> Self {
+				value.clone()
+			}
+		}
+
+		impl From<$name> for String {
+			fn from(value: $name) -> Self {
+				value.0
+			}
+		}
+
+		impl PartialEq<str> for $name {
+			fn eq(&self, other: &str) -> bool {
+				self.0 == other
+			}
+		}
+
+		impl PartialEq<&str> for $name {
+			fn eq(&self, other: &&str) -> bool {
+				self.0 == *other
+			}
+		}
+	};
 }
 
-#[derive(Debug, Deserialize)]
+string_newtype!(
+	/// The slug identifying a project in urls and requests, like `ViaVersion`.
+	/// Unlike a `ProjectName` there's no conversion from one to the other, so a project's display name can't be
+	/// passed where its slug is needed by mistake. Raw strings convert to either.
+	///
+	/// ```compile_fail
+	/// use hangar_api::api::ProjectRequest;
+	/// use hangar_api::object::ProjectName;
+	///
+	/// let name = ProjectName::from("Via Version");
+	/// let request = ProjectRequest::new(name);
+	/// ```
+	Slug
+);
+
+string_newtype!(
+	/// The display name of a project, which can differ from its `Slug`.
+	ProjectName
+);
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Namespace {
 	pub owner: String,
-	pub slug: String,
+	pub slug: Slug,
 }
 
 impl Namespace {
+	/// Gets the namespace as `owner/slug`, which identifies the project.
+	pub fn key(&self) -> String {
+		format!("{}/{}", self.owner, self.slug)
+	}
+
 	pub fn url(&self) -> String {
 		format!("https://hangar.papermc.io/{}/{}", self.owner, self.slug)
 	}
+
+	/// Creates a request for the user who owns the project.
+	pub fn owner_request(&self) -> UserRequest {
+		UserRequest::builder().name(self.owner.clone()).build()
+	}
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ProjectStats {
 	pub views: i64,
 	pub downloads: i64,
@@ -156,8 +653,73 @@ pub struct ProjectStats {
 	pub watchers: i64,
 }
 
+impl ProjectStats {
+	/// Works out ratios between the stats, e.g. for ranking projects by more than raw downloads.
+	///
+	/// ```
+	/// use hangar_api::object::ProjectStats;
+	///
+	/// let stats = ProjectStats {
+	///     views: 200,
+	///     downloads: 50,
+	///     recent_views: 0,
+	///     recent_downloads: 10,
+	///     stars: 5,
+	///     watchers: 1,
+	/// };
+	/// let derived = stats.derived();
+	/// assert_eq!(derived.downloads_per_view, Some(0.25));
+	/// assert_eq!(derived.stars_per_download, Some(0.1));
+	/// assert_eq!(derived.recent_download_share, Some(0.2));
+	/// assert_eq!(derived.recent_view_share, Some(0.0));
+	///
+	/// // brand new projects have nothing to divide by
+	/// let derived = ProjectStats { views: 0, downloads: 0, ..stats }.derived();
+	/// assert_eq!(derived.downloads_per_view, None);
+	/// assert_eq!(derived.stars_per_download, None);
+	/// assert_eq!(derived.trending_score(), 0.0);
+	/// ```
+	pub fn derived(&self) -> DerivedStats {
+		DerivedStats {
+			downloads_per_view: ratio(self.downloads, self.views),
+			stars_per_download: ratio(self.stars, self.downloads),
+			recent_download_share: ratio(self.recent_downloads, self.downloads),
+			recent_view_share: ratio(self.recent_views, self.views),
+		}
+	}
+}
+
+/// Divides two stats, or `None` if there's nothing to divide by.
+fn ratio(numerator: i64, denominator: i64) -> Option<f64> {
+	(denominator > 0).then(|| numerator as f64 / denominator as f64)
+}
+
+/// Ratios between a project's stats, see `ProjectStats::derived`. Each is `None` when the stat it's divided by is
+/// zero, rather than NaN or infinity. Hangar's recent stats cover the last 30 days.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivedStats {
+	/// `downloads / views`, how often a view turned into a download. Can be above 1, since downloads don't need a
+	/// view of the project's page
+	pub downloads_per_view: Option<f64>,
+	/// `stars / downloads`, how many of the people who downloaded the project liked it enough to star it
+	pub stars_per_download: Option<f64>,
+	/// `recent_downloads / downloads`, how much of the project's downloads were recent. Close to 1 for new or
+	/// trending projects and close to 0 for ones which aren't downloaded much anymore
+	pub recent_download_share: Option<f64>,
+	/// `recent_views / views`, how much of the project's views were recent
+	pub recent_view_share: Option<f64>,
+}
+
+impl DerivedStats {
+	/// Gets a score for ranking projects by how much they're trending, `recent_download_share` or 0 for projects
+	/// without any downloads.
+	pub fn trending_score(&self) -> f64 {
+		self.recent_download_share.unwrap_or(0.0)
+	}
+}
+
 /// The visibility of a project or version
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Visibility {
 	Public,
@@ -167,34 +729,55 @@ pub enum Visibility {
 	SoftDelete,
 }
 
-#[derive(Debug, Deserialize)]
+impl Visibility {
+	/// Checks whether content with this visibility is available to everyone. Only `Public` is, new content and
+	/// content waiting on review or changes is only shown to its members and staff, and soft deleted content is gone.
+	pub fn is_available(self) -> bool {
+		self == Self::Public
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct UserActions {
 	pub starred: bool,
 	pub watching: bool,
 	pub flagged: bool,
 }
 
-#[derive(Debug, Deserialize)]
+/// Projects which were never set up, like libraries only published for other projects, may leave their settings
+/// out or send nulls. Missing values are empty, or the defaults of `License` and `Donation`.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ProjectSettings {
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub links: Vec<Link>,
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub tags: Vec<ProjectTags>,
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub license: License,
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub keywords: Vec<String>,
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub sponsors: String,
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub donation: Donation,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Link {
 	pub id: i64,
 	/// Type of the link. Either SIDEBAR or TOP
 	#[serde(rename = "type")]
 	pub link_type: String,
 	pub title: Option<String>,
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub links: Vec<ActualLink>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ActualLink {
 	pub id: i64,
 	pub name: String,
@@ -202,7 +785,7 @@ pub struct ActualLink {
 	pub url: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Copy, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ProjectTags {
 	Addon,
@@ -210,25 +793,154 @@ pub enum ProjectTags {
 	SupportsFolia,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct License {
 	pub name: Option<String>,
 	pub url: Option<String>,
-	#[serde(rename = "type")]
+	/// The kind of license, like `MIT`. `Unspecified` if the project didn't set one.
+	#[serde(
+		rename = "type",
+		default = "License::unspecified",
+		deserialize_with = "license_type"
+	)]
 	pub license_type: String,
 }
 
-#[derive(Debug, Deserialize)]
+impl License {
+	/// The license type of projects which didn't set one, as Hangar shows it.
+	pub const UNSPECIFIED: &'static str = "Unspecified";
+
+	fn unspecified() -> String {
+		Self::UNSPECIFIED.to_string()
+	}
+
+	/// Checks whether the project set a license.
+	pub fn is_specified(&self) -> bool {
+		self.license_type != Self::UNSPECIFIED
+	}
+
+	/// Gets the license type, if it's one this crate knows.
+	pub fn known_type(&self) -> Option<LicenseType> {
+		self.license_type.parse().ok()
+	}
+}
+
+/// The license types Hangar lets projects pick from, as in `License::license_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LicenseType {
+	Unspecified,
+	Mit,
+	Apache2,
+	Gpl,
+	Lgpl,
+	Agpl,
+	Mpl,
+	/// A license not in the list, named in `License::name`
+	Other,
+}
+
+impl Display for LicenseType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let s = match self {
+			Self::Unspecified => License::UNSPECIFIED,
+			Self::Mit => "MIT",
+			Self::Apache2 => "Apache 2.0",
+			Self::Gpl => "GPL",
+			Self::Lgpl => "LGPL",
+			Self::Agpl => "AGPL",
+			Self::Mpl => "MPL",
+			Self::Other => "Other",
+		};
+		write!(f, "{s}")
+	}
+}
+
+impl LicenseType {
+	/// Every license type, in the order Hangar lists them.
+	pub const ALL: [Self; 8] = [
+		Self::Unspecified,
+		Self::Mit,
+		Self::Apache2,
+		Self::Gpl,
+		Self::Lgpl,
+		Self::Agpl,
+		Self::Mpl,
+		Self::Other,
+	];
+
+	/// Recognizes an SPDX license identifier like `GPL-3.0-or-later` as the license type Hangar lumps it under,
+	/// since only Apache's type names a version.
+	fn from_spdx(s: &str) -> Option<Self> {
+		let s = s.trim().to_ascii_lowercase();
+		let (family, version) = s.split_once(['-', ' ']).unwrap_or((&s, ""));
+		match family {
+			"mit" if version.is_empty() => Some(Self::Mit),
+			"apache" if version.starts_with("2.0") => Some(Self::Apache2),
+			"apache2" | "apache2.0" => Some(Self::Apache2),
+			"gpl" | "gplv2" | "gplv3" => Some(Self::Gpl),
+			"lgpl" | "lgplv2" | "lgplv3" => Some(Self::Lgpl),
+			"agpl" | "agplv3" => Some(Self::Agpl),
+			"mpl" => Some(Self::Mpl),
+			_ => None,
+		}
+	}
+}
+
+/// Parses the form Hangar shows (`Apache 2.0`) ignoring case, or an SPDX identifier (`Apache-2.0`, `GPL-3.0-only`).
+impl FromStr for LicenseType {
+	type Err = ParseEnumError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse_variant("license type", s, &Self::ALL).or_else(|err| Self::from_spdx(s).ok_or(err))
+	}
+}
+
+impl Default for License {
+	fn default() -> Self {
+		Self {
+			name: None,
+			url: None,
+			license_type: Self::unspecified(),
+		}
+	}
+}
+
+/// Deserializes a license type, treating null and empty types as `Unspecified`.
+fn license_type<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+	let license_type = Option::<String>::deserialize(deserializer)?;
+	Ok(license_type
+		.filter(|license_type| !license_type.trim().is_empty())
+		.unwrap_or_else(License::unspecified))
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Donation {
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub enable: bool,
+	#[serde(default, deserialize_with = "null_as_default")]
 	pub subject: String,
 }
 
-#[derive(Debug, Deserialize)]
+/// Deserializes a value which Hangar may send as null, using its default instead.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	T: Default + Deserialize<'de>,
+{
+	Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct Version {
 	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
 	pub created_at: OffsetDateTime,
+	/// The internal id of the version
+	#[serde(default)]
+	pub id: Option<i64>,
 	pub name: String,
 	pub visibility: Visibility,
 	pub description: String,
@@ -243,23 +955,124 @@ pub struct Version {
 	pub platform_dependencies_formatted: ByPlatform<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize)]
+impl Version {
+	/// Checks whether the version is available to everyone, see `Visibility::is_available`.
+	pub fn is_available(&self) -> bool {
+		self.visibility.is_available()
+	}
+
+	/// Checks whether the version is pinned, either by itself or as the latest version in a pinned channel.
+	/// Versions in a pinned channel but older than its latest version aren't pinned.
+	pub fn is_effectively_pinned(&self) -> bool {
+		self.pinned_status != PinnedStatus::None
+	}
+
+	/// Gets the download for the given platform, if the version supports it.
+	pub fn download_for(&self, platform: Platform) -> Option<&VersionDownloads> {
+		self.downloads.get(platform)
+	}
+
+	/// Gets the platforms this version has downloads for.
+	pub fn platforms(&self) -> Vec<Platform> {
+		self.downloads
+			.iter()
+			.map(|(platform, _)| platform)
+			.collect()
+	}
+
+	/// Gets the download for every platform this version supports.
+	pub fn all_downloads(&self) -> Vec<(Platform, &VersionDownloads)> {
+		self.downloads.iter().collect()
+	}
+
+	/// Gets the plugin dependencies of this version on the given platform which are Hangar projects, skipping
+	/// external ones.
+	pub fn hangar_dependencies(&self, platform: Platform) -> Vec<&VersionPluginDependencies> {
+		self.plugin_dependencies
+			.get(platform)
+			.into_iter()
+			.flatten()
+			.filter(|dependency| dependency.is_hangar())
+			.collect()
+	}
+
+	/// Gets the plugin dependencies of this version on the given platform which are downloaded from outside of
+	/// Hangar.
+	pub fn external_dependencies(&self, platform: Platform) -> Vec<&VersionPluginDependencies> {
+		self.plugin_dependencies
+			.get(platform)
+			.into_iter()
+			.flatten()
+			.filter(|dependency| !dependency.is_hangar())
+			.collect()
+	}
+
+	/// Gets every distinct file of this version along with the platforms it's offered for.
+	/// Files hosted on Hangar are the same if their sha256 hashes match, and external ones if their urls do.
+	pub fn unique_downloads(&self) -> Vec<(Vec<Platform>, &VersionDownloads)> {
+		let mut unique: Vec<(Vec<Platform>, &VersionDownloads)> = Vec::new();
+		for (platform, download) in self.downloads.iter() {
+			match unique
+				.iter_mut()
+				.find(|(_, other)| other.is_same_file(download))
+			{
+				Some((platforms, _)) => platforms.push(platform),
+				None => unique.push((vec![platform], download)),
+			}
+		}
+		unique
+	}
+
+	/// Gets the Minecraft versions this version supports on a platform, skipping any Hangar lists which can't be
+	/// parsed.
+	pub fn supported_versions(&self, platform: Platform) -> Vec<VersionRange> {
+		self.platform_dependencies
+			.get(platform)
+			.into_iter()
+			.flatten()
+			.filter_map(|version| version.parse().ok())
+			.collect()
+	}
+
+	/// Checks whether this version supports a Minecraft version on a platform.
+	pub fn supports(&self, platform: Platform, version: &MinecraftVersion) -> bool {
+		self.supported_versions(platform)
+			.iter()
+			.any(|range| range.contains(version))
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct VersionStats {
 	pub total_downloads: i64,
 	pub platform_downloads: ByPlatform<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Values for each platform, serialized as an object keyed by platform like `{"PAPER": ...}` without the missing
+/// platforms.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct ByPlatform<T> {
-	#[serde(rename = "PAPER")]
+	#[serde(rename = "PAPER", skip_serializing_if = "Option::is_none")]
 	pub paper: Option<T>,
-	#[serde(rename = "WATERFALL")]
+	#[serde(rename = "WATERFALL", skip_serializing_if = "Option::is_none")]
 	pub waterfall: Option<T>,
-	#[serde(rename = "VELOCITY")]
+	#[serde(rename = "VELOCITY", skip_serializing_if = "Option::is_none")]
 	pub velocity: Option<T>,
 }
 
+impl<T> Default for ByPlatform<T> {
+	fn default() -> Self {
+		Self {
+			paper: None,
+			waterfall: None,
+			velocity: None,
+		}
+	}
+}
+
 impl<T> ByPlatform<T> {
 	pub fn get(&self, platform: Platform) -> Option<&T> {
 		match platform {
@@ -269,6 +1082,24 @@ impl<T> ByPlatform<T> {
 		}
 	}
 
+	/// Gets the value for the given platform mutably, if present.
+	pub fn get_mut(&mut self, platform: Platform) -> Option<&mut T> {
+		self.slot(platform).as_mut()
+	}
+
+	/// Sets the value for the given platform, returning the previous one.
+	pub fn insert(&mut self, platform: Platform, value: T) -> Option<T> {
+		self.slot(platform).replace(value)
+	}
+
+	fn slot(&mut self, platform: Platform) -> &mut Option<T> {
+		match platform {
+			Platform::Paper => &mut self.paper,
+			Platform::Waterfall => &mut self.waterfall,
+			Platform::Velocity => &mut self.velocity,
+		}
+	}
+
 	pub fn iter(&self) -> impl Iterator<Item = (Platform, &T)> {
 		self.paper
 			.iter()
@@ -276,9 +1107,33 @@ impl<T> ByPlatform<T> {
 			.chain(self.waterfall.iter().map(|v| (Platform::Waterfall, v)))
 			.chain(self.velocity.iter().map(|v| (Platform::Velocity, v)))
 	}
+
+	/// Checks whether no platforms are present.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Gets how many platforms are present.
+	pub fn len(&self) -> usize {
+		self.iter().count()
+	}
+
+	/// Checks whether the given platform is present.
+	pub fn contains(&self, platform: Platform) -> bool {
+		self.get(platform).is_some()
+	}
+
+	/// Transforms the value for each present platform.
+	pub fn map<U>(self, f: impl Fn(T) -> U) -> ByPlatform<U> {
+		ByPlatform {
+			paper: self.paper.map(&f),
+			waterfall: self.waterfall.map(&f),
+			velocity: self.velocity.map(&f),
+		}
+	}
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ReviewState {
 	Unreviewed,
@@ -287,18 +1142,191 @@ pub enum ReviewState {
 	PartiallyReviewed,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Channel {
-	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
-	pub created_at: OffsetDateTime,
+/// The reviews of a version, oldest first.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ReviewHistory {
+	pub reviews: Vec<Review>,
+}
+
+impl ReviewHistory {
+	/// Gets the most recent review, if the version has been reviewed.
+	pub fn latest(&self) -> Option<&Review> {
+		self.reviews.last()
+	}
+}
+
+/// A single review of a version.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Review {
+	/// The name of the user who reviewed the version
+	pub reviewer: String,
+	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
+	pub created_at: OffsetDateTime,
+	/// The state the review left the version in
+	pub state: ReviewState,
+	/// The reviewer's message, if they left one
+	pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Channel {
+	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
+	pub created_at: OffsetDateTime,
 	pub name: String,
 	pub description: Option<String>,
-	pub color: String,
-	pub flags: Vec<ChannelFlags>,
+	pub color: Color,
+	pub flags: ChannelFlagSet,
+}
+
+/// A color sent by Hangar as a hex code like `#2A4BC2`, displayed in the same form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+	pub r: u8,
+	pub g: u8,
+	pub b: u8,
+}
+
+impl Display for Color {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+	}
+}
+
+/// The leading `#` is optional.
+impl FromStr for Color {
+	type Err = ParseColorError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let hex = s.strip_prefix('#').unwrap_or(s);
+		if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+			return Err(ParseColorError(s.to_string()));
+		}
+		let channel = |i| u8::from_str_radix(&hex[i..i + 2], 16).expect("checked to be hex");
+		Ok(Self {
+			r: channel(0),
+			g: channel(2),
+			b: channel(4),
+		})
+	}
+}
+
+impl TryFrom<String> for Color {
+	type Error = ParseColorError;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		value.parse()
+	}
+}
+
+impl From<Color> for String {
+	fn from(value: Color) -> Self {
+		value.to_string()
+	}
 }
 
-#[derive(Debug, Deserialize)]
+impl Channel {
+	/// Gets which of the well-known channels this is, if any.
+	pub fn common(&self) -> CommonChannel {
+		self.name.as_str().into()
+	}
+
+	/// Checks whether the channel has a flag.
+	pub fn has_flag(&self, flag: ChannelFlags) -> bool {
+		self.flags.contains(flag)
+	}
+
+	/// Checks whether the channel is pinned, which pins the latest version in it.
+	pub fn is_pinned(&self) -> bool {
+		self.has_flag(ChannelFlags::Pinned)
+	}
+
+	/// Checks whether the channel is frozen, so no more versions can be published in it.
+	pub fn is_frozen(&self) -> bool {
+		self.has_flag(ChannelFlags::Frozen)
+	}
+
+	/// Checks whether the channel is marked as having unstable versions, which Hangar warns about before
+	/// downloading.
+	pub fn is_unstable(&self) -> bool {
+		self.has_flag(ChannelFlags::Unstable)
+	}
+
+	/// Checks whether the channel's versions are hidden from version lists unless asked for, like with
+	/// `VersionsRequest::include_hidden_channels`.
+	pub fn hides_by_default(&self) -> bool {
+		self.has_flag(ChannelFlags::HideByDefault)
+	}
+}
+
+/// A well-known channel name, with any others kept as is in `Other`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CommonChannel {
+	Release,
+	Snapshot,
+	Alpha,
+	Beta,
+	Other(String),
+}
+
+impl CommonChannel {
+	/// Gets the name of the channel, e.g. `Release`.
+	pub fn as_str(&self) -> &str {
+		match self {
+			Self::Release => "Release",
+			Self::Snapshot => "Snapshot",
+			Self::Alpha => "Alpha",
+			Self::Beta => "Beta",
+			Self::Other(name) => name,
+		}
+	}
+}
+
+impl Display for CommonChannel {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// Well-known names are matched ignoring case, anything else becomes `Other`.
+impl FromStr for CommonChannel {
+	type Err = std::convert::Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(s.into())
+	}
+}
+
+impl From<&str> for CommonChannel {
+	fn from(value: &str) -> Self {
+		[Self::Release, Self::Snapshot, Self::Alpha, Self::Beta]
+			.into_iter()
+			.find(|channel| channel.as_str().eq_ignore_ascii_case(value))
+			.unwrap_or_else(|| Self::Other(value.to_string()))
+	}
+}
+
+impl From<String> for CommonChannel {
+	fn from(value: String) -> Self {
+		value.as_str().into()
+	}
+}
+
+impl From<CommonChannel> for String {
+	fn from(value: CommonChannel) -> Self {
+		match value {
+			CommonChannel::Other(name) => name,
+			channel => channel.as_str().to_string(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ChannelFlags {
 	Frozen,
@@ -308,15 +1336,109 @@ pub enum ChannelFlags {
 	HideByDefault,
 }
 
-#[derive(Debug, Deserialize)]
+impl ChannelFlags {
+	/// Every flag.
+	pub const ALL: [Self; 5] = [
+		Self::Frozen,
+		Self::Unstable,
+		Self::Pinned,
+		Self::SendsNotifications,
+		Self::HideByDefault,
+	];
+
+	fn bit(self) -> u8 {
+		1 << self as u8
+	}
+}
+
+/// The flags of a channel, sent by Hangar as a list like `["FROZEN", "PINNED"]` and kept as a bitset.
+///
+/// ```
+/// use hangar_api::object::{ChannelFlagSet, ChannelFlags};
+///
+/// let flags: ChannelFlagSet = serde_json::from_str(r#"["UNSTABLE", "HIDE_BY_DEFAULT", "UNSTABLE"]"#).unwrap();
+/// assert!(flags.contains(ChannelFlags::Unstable));
+/// assert!(flags.contains(ChannelFlags::HideByDefault));
+/// assert!(!flags.contains(ChannelFlags::Frozen));
+/// assert_eq!(flags.len(), 2);
+/// assert_eq!(
+///     flags.iter().collect::<Vec<_>>(),
+///     [ChannelFlags::Unstable, ChannelFlags::HideByDefault],
+/// );
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "Vec<ChannelFlags>")]
+pub struct ChannelFlagSet(u8);
+
+impl ChannelFlagSet {
+	/// Checks whether a flag is set.
+	pub fn contains(&self, flag: ChannelFlags) -> bool {
+		self.0 & flag.bit() != 0
+	}
+
+	/// Sets a flag.
+	pub fn insert(&mut self, flag: ChannelFlags) {
+		self.0 |= flag.bit();
+	}
+
+	/// Unsets a flag.
+	pub fn remove(&mut self, flag: ChannelFlags) {
+		self.0 &= !flag.bit();
+	}
+
+	/// Gets how many flags are set.
+	pub fn len(&self) -> usize {
+		self.0.count_ones() as usize
+	}
+
+	/// Checks whether no flags are set.
+	pub fn is_empty(&self) -> bool {
+		self.0 == 0
+	}
+
+	/// Iterates over the flags which are set, in the order of `ChannelFlags::ALL`.
+	pub fn iter(&self) -> impl Iterator<Item = ChannelFlags> {
+		let set = *self;
+		ChannelFlags::ALL
+			.into_iter()
+			.filter(move |flag| set.contains(*flag))
+	}
+}
+
+impl std::fmt::Debug for ChannelFlagSet {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_set().entries(self.iter()).finish()
+	}
+}
+
+impl FromIterator<ChannelFlags> for ChannelFlagSet {
+	fn from_iter<I: IntoIterator<Item = ChannelFlags>>(iter: I) -> Self {
+		let mut set = Self::default();
+		for flag in iter {
+			set.insert(flag);
+		}
+		set
+	}
+}
+
+impl From<Vec<ChannelFlags>> for ChannelFlagSet {
+	fn from(value: Vec<ChannelFlags>) -> Self {
+		value.into_iter().collect()
+	}
+}
+
+/// How a version is pinned to the top of its project's versions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum PinnedStatus {
 	None,
+	/// The version itself is pinned
 	Version,
+	/// The version is the latest one in a pinned channel
 	Channel,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
 pub enum VersionDownloads {
 	#[serde(rename_all = "camelCase")]
@@ -332,16 +1454,76 @@ pub enum VersionDownloads {
 	},
 }
 
-#[derive(Debug, Deserialize)]
+impl VersionDownloads {
+	/// Gets the url to download from, whether it's hosted on Hangar or externally.
+	pub fn url(&self) -> &str {
+		match self {
+			Self::Internal { download_url, .. } => download_url,
+			Self::External { external_url } => external_url,
+		}
+	}
+
+	/// Checks whether the download is hosted outside of Hangar.
+	pub fn is_external(&self) -> bool {
+		matches!(self, Self::External { .. })
+	}
+
+	/// Checks whether two downloads are the same file, by sha256 hash for files hosted on Hangar or by url otherwise.
+	pub fn is_same_file(&self, other: &Self) -> bool {
+		match (self.file_info(), other.file_info()) {
+			(Some(a), Some(b)) => a.sha256_hash.eq_ignore_ascii_case(&b.sha256_hash),
+			(None, None) => self.url() == other.url(),
+			_ => false,
+		}
+	}
+
+	/// Gets info on the file, which is only known for downloads hosted on Hangar.
+	pub fn file_info(&self) -> Option<&VersionDownloadsFileInfo> {
+		match self {
+			Self::Internal { file_info, .. } => Some(file_info),
+			Self::External { .. } => None,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct VersionDownloadsFileInfo {
 	pub name: String,
 	pub size_bytes: i64,
 	pub sha256_hash: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[cfg(feature = "sha2")]
+impl VersionDownloadsFileInfo {
+	/// Verifies downloaded bytes match the size and sha256 hash of this file.
+	pub fn verify(&self, bytes: &[u8]) -> Result<(), crate::error::IntegrityError> {
+		use sha2::{Digest, Sha256};
+
+		if i64::try_from(bytes.len()) != Ok(self.size_bytes) {
+			return Err(crate::error::IntegrityError::Size {
+				expected: self.size_bytes,
+				actual: bytes.len(),
+			});
+		}
+		let actual: String = Sha256::digest(bytes)
+			.iter()
+			.map(|b| format!("{b:02x}"))
+			.collect();
+		if !actual.eq_ignore_ascii_case(&self.sha256_hash) {
+			return Err(crate::error::IntegrityError::Hash {
+				expected: self.sha256_hash.clone(),
+				actual,
+			});
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub struct VersionPluginDependencies {
 	/// Name of the plugin dependency. For non-external dependencies, this should be the Hangar project name
 	pub name: String,
@@ -352,3 +1534,1143 @@ pub struct VersionPluginDependencies {
 	/// Server platform
 	pub platform: Platform,
 }
+
+impl VersionPluginDependencies {
+	/// Creates a dependency on a Hangar project by its name.
+	pub fn hangar(name: impl Into<String>, required: bool, platform: Platform) -> Self {
+		Self {
+			name: name.into(),
+			required,
+			external_url: None,
+			platform,
+		}
+	}
+
+	/// Creates a dependency downloaded from outside of Hangar.
+	pub fn external(
+		name: impl Into<String>,
+		url: impl Into<String>,
+		required: bool,
+		platform: Platform,
+	) -> Self {
+		Self {
+			name: name.into(),
+			required,
+			external_url: Some(url.into()),
+			platform,
+		}
+	}
+
+	/// Checks the dependency has a name, and an http(s) url if it's external.
+	pub fn validate(&self) -> Result<(), DependencyError> {
+		if self.name.trim().is_empty() {
+			return Err(DependencyError::MissingName);
+		}
+		if let Some(url) = &self.external_url {
+			if !(url.starts_with("https://") || url.starts_with("http://")) {
+				return Err(DependencyError::InvalidUrl {
+					name: self.name.clone(),
+					url: url.clone(),
+				});
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks whether the dependency is a Hangar project rather than downloaded from an external url.
+	pub fn is_hangar(&self) -> bool {
+		self.external_url.is_none()
+	}
+
+	/// Gets the name of the Hangar project the dependency refers to, or `None` for external dependencies.
+	/// Search for it with `ProjectsRequest` to find the project, since projects can be renamed.
+	pub fn hangar_slug(&self) -> Option<&str> {
+		self.is_hangar().then_some(self.name.as_str())
+	}
+}
+
+/// Groups plugin dependencies by platform in the shape versions list them in, e.g. for uploading a version or
+/// writing a dependency manifest.
+///
+/// ```
+/// use hangar_api::object::{Platform, PluginDependenciesBuilder};
+///
+/// let dependencies = PluginDependenciesBuilder::new()
+///     .hangar(Platform::Paper, "LuckPerms", true)
+///     .external(Platform::Paper, "Vault", "https://example.com/vault.jar", false)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&dependencies).unwrap(),
+///     concat!(
+///         r#"{"PAPER":["#,
+///         r#"{"name":"LuckPerms","required":true,"externalUrl":null,"platform":"PAPER"},"#,
+///         r#"{"name":"Vault","required":false,"externalUrl":"https://example.com/vault.jar","platform":"PAPER"}"#,
+///         r#"]}"#,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PluginDependenciesBuilder {
+	dependencies: Vec<VersionPluginDependencies>,
+}
+
+impl PluginDependenciesBuilder {
+	/// Creates a builder with no dependencies.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a dependency, grouped under its own platform.
+	pub fn dependency(mut self, dependency: VersionPluginDependencies) -> Self {
+		self.dependencies.push(dependency);
+		self
+	}
+
+	/// Adds a dependency on a Hangar project for a platform.
+	pub fn hangar(self, platform: Platform, name: impl Into<String>, required: bool) -> Self {
+		self.dependency(VersionPluginDependencies::hangar(name, required, platform))
+	}
+
+	/// Adds a dependency downloaded from outside of Hangar for a platform.
+	pub fn external(
+		self,
+		platform: Platform,
+		name: impl Into<String>,
+		url: impl Into<String>,
+		required: bool,
+	) -> Self {
+		self.dependency(VersionPluginDependencies::external(
+			name, url, required, platform,
+		))
+	}
+
+	/// Validates the dependencies and groups them by platform, keeping the order they were added in.
+	/// Fails if a dependency is invalid or listed twice for a platform.
+	pub fn build(self) -> Result<ByPlatform<Vec<VersionPluginDependencies>>, DependencyError> {
+		let mut grouped: ByPlatform<Vec<VersionPluginDependencies>> = ByPlatform::default();
+		for dependency in self.dependencies {
+			dependency.validate()?;
+			let platform = dependency.platform;
+			let list = grouped.slot(platform).get_or_insert_with(Vec::new);
+			if list
+				.iter()
+				.any(|other| other.name.eq_ignore_ascii_case(&dependency.name))
+			{
+				return Err(DependencyError::Duplicate {
+					name: dependency.name,
+					platform,
+				});
+			}
+			list.push(dependency);
+		}
+		Ok(grouped)
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ProjectMember {
+	/// The name of the member
+	pub user: String,
+	/// The roles the member has in the project
+	pub roles: Vec<Role>,
+}
+
+impl ProjectMember {
+	/// Checks whether the member has the given role in the project.
+	pub fn has_role(&self, role: ProjectRole) -> bool {
+		self.roles.iter().any(|r| r.project_role() == role)
+	}
+}
+
+/// A role a member can have in a project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectRole {
+	Owner,
+	Admin,
+	Developer,
+	Editor,
+	Supporter,
+	/// A role this crate doesn't know about yet
+	Unknown,
+}
+
+impl ProjectRole {
+	/// Gets the project role with the given title.
+	pub fn from_title(title: &str) -> Self {
+		match title {
+			"Owner" => Self::Owner,
+			"Admin" => Self::Admin,
+			"Developer" => Self::Developer,
+			"Editor" => Self::Editor,
+			"Supporter" => Self::Supporter,
+			_ => Self::Unknown,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct User {
+	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
+	pub created_at: OffsetDateTime,
+	/// The internal id of the user, which unlike their name never changes
+	#[serde(default)]
+	pub id: Option<i64>,
+	/// The unique name of the user
+	pub name: String,
+	/// The user's tagline, frequently absent
+	pub tagline: Option<String>,
+	/// The user's global roles
+	pub roles: Vec<Role>,
+	/// How many projects the user owns
+	pub project_count: i64,
+	/// Whether the user is locked
+	pub locked: bool,
+	/// The names the user had before, if they've been renamed
+	#[serde(default)]
+	pub name_history: Vec<NameChange>,
+	/// The url to the user's avatar
+	pub avatar_url: String,
+	/// Whether the user is an organization rather than a person
+	#[serde(default)]
+	pub is_organization: bool,
+}
+
+/// A role a user has, either globally or in a project or organization.
+/// Users, staff, and project members all share this type.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct Role {
+	/// The name of the role, e.g. `Hangar Admin`
+	pub title: String,
+	/// The color of the role
+	pub color: Color,
+	/// Where the role is ranked among staff roles, lower being higher up. Only global roles have a rank
+	#[serde(default)]
+	pub rank: Option<i64>,
+	/// What the role applies to
+	pub category: RoleCategory,
+	/// The permissions the role grants, if Hangar sent them
+	#[serde(default, deserialize_with = "null_as_default")]
+	pub permissions: Vec<NamedPermission>,
+}
+
+impl Role {
+	/// The title of the global role of Hangar's admins.
+	pub const ADMIN_TITLE: &'static str = "Hangar Admin";
+
+	/// Gets which project role this is, for roles from a project's members.
+	pub fn project_role(&self) -> ProjectRole {
+		ProjectRole::from_title(&self.title)
+	}
+
+	/// Checks whether this is the owner role of a project.
+	pub fn is_project_owner(&self) -> bool {
+		self.category == RoleCategory::Project && self.project_role() == ProjectRole::Owner
+	}
+
+	/// Checks whether this is the global role of Hangar's admins.
+	pub fn is_admin(&self) -> bool {
+		self.category == RoleCategory::Global && self.title == Self::ADMIN_TITLE
+	}
+
+	/// Orders roles by rank, highest first, with roles without a rank last. Use with `sort_by`.
+	pub fn by_rank(a: &Self, b: &Self) -> std::cmp::Ordering {
+		match (a.rank, b.rank) {
+			(Some(a), Some(b)) => a.cmp(&b),
+			(Some(_), None) => std::cmp::Ordering::Less,
+			(None, Some(_)) => std::cmp::Ordering::Greater,
+			(None, None) => std::cmp::Ordering::Equal,
+		}
+	}
+}
+
+/// What a role applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RoleCategory {
+	Global,
+	Project,
+	Organization,
+	/// A category this crate doesn't know about yet
+	#[serde(other)]
+	Unknown,
+}
+
+/// What a set of permissions applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionType {
+	Global,
+	Project,
+	Organization,
+	/// A type this crate doesn't know about yet
+	#[serde(other)]
+	Unknown,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct NameChange {
+	/// The name before the change
+	pub old_name: String,
+	/// The name after the change
+	pub new_name: String,
+	/// When the name was changed
+	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
+	pub date: OffsetDateTime,
+}
+
+/// A permission a user can have, globally or in a project or organization
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum NamedPermission {
+	ViewPublicInfo,
+	EditOwnUserSettings,
+	EditApiKeys,
+	EditSubjectSettings,
+	ManageSubjectMembers,
+	IsSubjectOwner,
+	IsSubjectMember,
+	CreateProject,
+	EditPage,
+	DeleteProject,
+	CreateVersion,
+	EditVersion,
+	DeleteVersion,
+	EditChannels,
+	CreateOrganization,
+	DeleteOrganization,
+	ModNotesAndFlags,
+	SeeHidden,
+	IsStaff,
+	Reviewer,
+	ViewHealth,
+	ViewIp,
+	ViewStats,
+	ViewLogs,
+	ManualValueChanges,
+	RestoreVersion,
+	RestoreProject,
+	HardDeleteProject,
+	HardDeleteVersion,
+	EditAllUserSettings,
+	/// A permission this crate doesn't know about yet
+	Unknown(String),
+}
+
+impl NamedPermission {
+	/// Every permission this crate knows about.
+	pub const ALL: [Self; 30] = [
+		Self::ViewPublicInfo,
+		Self::EditOwnUserSettings,
+		Self::EditApiKeys,
+		Self::EditSubjectSettings,
+		Self::ManageSubjectMembers,
+		Self::IsSubjectOwner,
+		Self::IsSubjectMember,
+		Self::CreateProject,
+		Self::EditPage,
+		Self::DeleteProject,
+		Self::CreateVersion,
+		Self::EditVersion,
+		Self::DeleteVersion,
+		Self::EditChannels,
+		Self::CreateOrganization,
+		Self::DeleteOrganization,
+		Self::ModNotesAndFlags,
+		Self::SeeHidden,
+		Self::IsStaff,
+		Self::Reviewer,
+		Self::ViewHealth,
+		Self::ViewIp,
+		Self::ViewStats,
+		Self::ViewLogs,
+		Self::ManualValueChanges,
+		Self::RestoreVersion,
+		Self::RestoreProject,
+		Self::HardDeleteProject,
+		Self::HardDeleteVersion,
+		Self::EditAllUserSettings,
+	];
+
+	/// Gets the name Hangar uses for this permission, e.g. `view_public_info`.
+	pub fn as_str(&self) -> &str {
+		match self {
+			Self::ViewPublicInfo => "view_public_info",
+			Self::EditOwnUserSettings => "edit_own_user_settings",
+			Self::EditApiKeys => "edit_api_keys",
+			Self::EditSubjectSettings => "edit_subject_settings",
+			Self::ManageSubjectMembers => "manage_subject_members",
+			Self::IsSubjectOwner => "is_subject_owner",
+			Self::IsSubjectMember => "is_subject_member",
+			Self::CreateProject => "create_project",
+			Self::EditPage => "edit_page",
+			Self::DeleteProject => "delete_project",
+			Self::CreateVersion => "create_version",
+			Self::EditVersion => "edit_version",
+			Self::DeleteVersion => "delete_version",
+			Self::EditChannels => "edit_channels",
+			Self::CreateOrganization => "create_organization",
+			Self::DeleteOrganization => "delete_organization",
+			Self::ModNotesAndFlags => "mod_notes_and_flags",
+			Self::SeeHidden => "see_hidden",
+			Self::IsStaff => "is_staff",
+			Self::Reviewer => "reviewer",
+			Self::ViewHealth => "view_health",
+			Self::ViewIp => "view_ip",
+			Self::ViewStats => "view_stats",
+			Self::ViewLogs => "view_logs",
+			Self::ManualValueChanges => "manual_value_changes",
+			Self::RestoreVersion => "restore_version",
+			Self::RestoreProject => "restore_project",
+			Self::HardDeleteProject => "hard_delete_project",
+			Self::HardDeleteVersion => "hard_delete_version",
+			Self::EditAllUserSettings => "edit_all_user_settings",
+			Self::Unknown(name) => name,
+		}
+	}
+}
+
+impl Display for NamedPermission {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+/// Parses the name Hangar uses for a permission. Names this crate doesn't know become `Unknown`.
+impl FromStr for NamedPermission {
+	type Err = std::convert::Infallible;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(s.into())
+	}
+}
+
+impl From<&str> for NamedPermission {
+	fn from(value: &str) -> Self {
+		match value {
+			"view_public_info" => Self::ViewPublicInfo,
+			"edit_own_user_settings" => Self::EditOwnUserSettings,
+			"edit_api_keys" => Self::EditApiKeys,
+			"edit_subject_settings" => Self::EditSubjectSettings,
+			"manage_subject_members" => Self::ManageSubjectMembers,
+			"is_subject_owner" => Self::IsSubjectOwner,
+			"is_subject_member" => Self::IsSubjectMember,
+			"create_project" => Self::CreateProject,
+			"edit_page" => Self::EditPage,
+			"delete_project" => Self::DeleteProject,
+			"create_version" => Self::CreateVersion,
+			"edit_version" => Self::EditVersion,
+			"delete_version" => Self::DeleteVersion,
+			"edit_channels" => Self::EditChannels,
+			"create_organization" => Self::CreateOrganization,
+			"delete_organization" => Self::DeleteOrganization,
+			"mod_notes_and_flags" => Self::ModNotesAndFlags,
+			"see_hidden" => Self::SeeHidden,
+			"is_staff" => Self::IsStaff,
+			"reviewer" => Self::Reviewer,
+			"view_health" => Self::ViewHealth,
+			"view_ip" => Self::ViewIp,
+			"view_stats" => Self::ViewStats,
+			"view_logs" => Self::ViewLogs,
+			"manual_value_changes" => Self::ManualValueChanges,
+			"restore_version" => Self::RestoreVersion,
+			"restore_project" => Self::RestoreProject,
+			"hard_delete_project" => Self::HardDeleteProject,
+			"hard_delete_version" => Self::HardDeleteVersion,
+			"edit_all_user_settings" => Self::EditAllUserSettings,
+			name => Self::Unknown(name.to_string()),
+		}
+	}
+}
+
+impl From<String> for NamedPermission {
+	fn from(value: String) -> Self {
+		value.as_str().into()
+	}
+}
+
+impl From<NamedPermission> for String {
+	fn from(value: NamedPermission) -> Self {
+		value.as_str().to_string()
+	}
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct ApiKeyInfo {
+	#[serde(deserialize_with = "time::serde::rfc3339::deserialize")]
+	pub created_at: OffsetDateTime,
+	/// The name of the key
+	pub name: String,
+	/// The start of the key, identifying it without giving it away
+	pub token_identifier: String,
+	/// The permissions the key has
+	pub permissions: Vec<NamedPermission>,
+	/// The last time the key was used, if ever
+	#[serde(
+		default,
+		deserialize_with = "time::serde::rfc3339::option::deserialize"
+	)]
+	pub last_used: Option<OffsetDateTime>,
+}
+
+/// A page of a project
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct Page {
+	/// The markdown contents of the page, exactly as Hangar sent them including any CRLF line endings
+	pub contents: String,
+}
+
+/// The stats of a project on a single day
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+pub struct DayProjectStats {
+	pub views: i64,
+	pub downloads: i64,
+}
+
+/// The downloads of a version on each platform on a single day
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct DayVersionStats {
+	pub downloads: ByPlatform<i64>,
+}
+
+impl DayVersionStats {
+	/// Gets the downloads across every platform.
+	pub fn total_downloads(&self) -> i64 {
+		self.downloads.iter().map(|(_, downloads)| downloads).sum()
+	}
+}
+
+/// The stats of a project or version on a single day as a flat row, for exporting to spreadsheets.
+/// Views are only known for projects and downloads per platform only for versions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayStatsRecord {
+	/// The day, written as `yyyy-mm-dd`
+	pub date: Date,
+	/// The downloads across every platform
+	pub downloads: i64,
+	pub views: Option<i64>,
+	pub paper_downloads: Option<i64>,
+	pub waterfall_downloads: Option<i64>,
+	pub velocity_downloads: Option<i64>,
+}
+
+impl DayStatsRecord {
+	/// Creates a record with no downloads or views for a day.
+	fn empty(date: Date) -> Self {
+		Self {
+			date,
+			downloads: 0,
+			views: None,
+			paper_downloads: None,
+			waterfall_downloads: None,
+			velocity_downloads: None,
+		}
+	}
+
+	/// Turns the response of a `ProjectStatsRequest` into records in chronological order.
+	/// Hangar leaves out days without any stats, and with `fill_gaps` those between the first and last day are added
+	/// back as zeroes.
+	pub fn from_project_stats(
+		stats: &BTreeMap<Date, DayProjectStats>,
+		fill_gaps: bool,
+	) -> Vec<Self> {
+		let records = stats.iter().map(|(date, stats)| Self {
+			downloads: stats.downloads,
+			views: Some(stats.views),
+			..Self::empty(*date)
+		});
+		fill(records, fill_gaps, |date| Self {
+			views: Some(0),
+			..Self::empty(date)
+		})
+	}
+
+	/// Turns the response of a `VersionStatsRequest` into records in chronological order.
+	/// Hangar leaves out days without any stats, and with `fill_gaps` those between the first and last day are added
+	/// back as zeroes.
+	pub fn from_version_stats(
+		stats: &BTreeMap<Date, DayVersionStats>,
+		fill_gaps: bool,
+	) -> Vec<Self> {
+		let records = stats.iter().map(|(date, stats)| Self {
+			downloads: stats.total_downloads(),
+			paper_downloads: stats.downloads.paper,
+			waterfall_downloads: stats.downloads.waterfall,
+			velocity_downloads: stats.downloads.velocity,
+			..Self::empty(*date)
+		});
+		fill(records, fill_gaps, Self::empty)
+	}
+
+	/// Writes records as csv, with a header row naming each field.
+	#[cfg(feature = "export")]
+	pub fn to_csv<W: std::io::Write>(records: &[Self], writer: W) -> crate::Result<()> {
+		let mut writer = csv::Writer::from_writer(writer);
+		for record in records {
+			writer.serialize(record)?;
+		}
+		writer.flush().map_err(csv::Error::from)?;
+		Ok(())
+	}
+}
+
+/// Collects records in chronological order, optionally filling in missing days between them.
+fn fill(
+	records: impl Iterator<Item = DayStatsRecord>,
+	fill_gaps: bool,
+	empty: impl Fn(Date) -> DayStatsRecord,
+) -> Vec<DayStatsRecord> {
+	let mut filled: Vec<DayStatsRecord> = Vec::new();
+	for record in records {
+		if fill_gaps {
+			let mut next = filled.last().and_then(|last| last.date.next_day());
+			while let Some(date) = next.filter(|date| *date < record.date) {
+				filled.push(empty(date));
+				next = date.next_day();
+			}
+		}
+		filled.push(record);
+	}
+	filled
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn projects_sort_serializes_in_its_default_direction() {
+		assert_eq!(
+			serde_json::to_string(&ProjectsSort::Views).unwrap(),
+			r#""-views""#
+		);
+		assert_eq!(
+			serde_json::to_string(&ProjectsSort::RecentDownloads).unwrap(),
+			r#""-recent-downloads""#
+		);
+		assert_eq!(
+			serde_json::to_string(&ProjectsSort::Slug).unwrap(),
+			r#""slug""#
+		);
+		for (value, sort) in [
+			(r#""views""#, ProjectsSort::Views),
+			(r#""-views""#, ProjectsSort::Views),
+			(r#""recent-views""#, ProjectsSort::RecentViews),
+		] {
+			assert_eq!(serde_json::from_str::<ProjectsSort>(value).unwrap(), sort);
+		}
+	}
+
+	#[test]
+	fn projects_sort_round_trips() {
+		for sort in [
+			ProjectsSort::Views,
+			ProjectsSort::Downloads,
+			ProjectsSort::Newest,
+			ProjectsSort::Stars,
+			ProjectsSort::Updated,
+			ProjectsSort::RecentDownloads,
+			ProjectsSort::RecentViews,
+			ProjectsSort::Slug,
+		] {
+			let json = serde_json::to_string(&sort).unwrap();
+			assert_eq!(serde_json::from_str::<ProjectsSort>(&json).unwrap(), sort);
+		}
+		assert!(serde_json::from_str::<ProjectsSort>(r#""-slugs""#).is_err());
+	}
+
+	#[test]
+	fn projects_sort_displays_its_label() {
+		assert_eq!(ProjectsSort::Views.to_string(), "Most Views");
+		assert_eq!(ProjectsSort::Newest.to_string(), "Newest");
+		assert_eq!(ProjectsSort::Updated.to_string(), "Recently Updated");
+	}
+
+	#[test]
+	fn projects_sort_query_values_follow_the_direction() {
+		assert_eq!(
+			ProjectsSort::Downloads.query_value(SortDirection::Descending),
+			"-downloads"
+		);
+		assert_eq!(
+			ProjectsSort::Downloads.query_value(SortDirection::Ascending),
+			"downloads"
+		);
+		assert_eq!(
+			ProjectsSort::Slug.query_value(ProjectsSort::Slug.default_direction()),
+			"slug"
+		);
+		assert_eq!(
+			ProjectsSort::Newest.query_value(ProjectsSort::Newest.default_direction()),
+			"-newest"
+		);
+	}
+
+	#[test]
+	fn named_permissions_round_trip() {
+		let mut names = std::collections::HashSet::new();
+		for permission in &NamedPermission::ALL {
+			assert!(
+				names.insert(permission.as_str()),
+				"{permission} is repeated"
+			);
+			assert!(!matches!(permission, NamedPermission::Unknown(_)));
+			let json = serde_json::to_string(&permission).unwrap();
+			assert_eq!(json, format!("\"{permission}\""));
+			assert_eq!(
+				serde_json::from_str::<NamedPermission>(&json).unwrap(),
+				*permission
+			);
+			assert_eq!(
+				permission.to_string().parse::<NamedPermission>(),
+				Ok(permission.clone())
+			);
+		}
+		let unknown: NamedPermission = "upload_screenshots".parse().unwrap();
+		assert_eq!(
+			unknown,
+			NamedPermission::Unknown("upload_screenshots".to_string())
+		);
+		assert_eq!(unknown.to_string(), "upload_screenshots");
+		assert!(!NamedPermission::ALL.contains(&unknown));
+	}
+
+	#[test]
+	fn unknown_named_permissions_keep_their_name() {
+		let permission: NamedPermission = serde_json::from_str(r#""launch_rockets""#).unwrap();
+		assert_eq!(
+			permission,
+			NamedPermission::Unknown("launch_rockets".to_string())
+		);
+		assert_eq!(
+			serde_json::to_string(&permission).unwrap(),
+			r#""launch_rockets""#
+		);
+	}
+
+	#[test]
+	fn colors_round_trip() {
+		let color: Color = serde_json::from_str(r##""#2a4bc2""##).unwrap();
+		assert_eq!(
+			color,
+			Color {
+				r: 0x2a,
+				g: 0x4b,
+				b: 0xc2
+			}
+		);
+		assert_eq!(serde_json::to_string(&color).unwrap(), r##""#2A4BC2""##);
+		assert_eq!(
+			serde_json::from_str::<Color>(&serde_json::to_string(&color).unwrap()).unwrap(),
+			color
+		);
+	}
+
+	#[test]
+	fn colors_parse_without_the_hash() {
+		assert_eq!(
+			serde_json::from_str::<Color>(r#""2A4BC2""#).unwrap(),
+			"#2A4BC2".parse().unwrap()
+		);
+	}
+
+	#[test]
+	fn colors_reject_anything_but_six_hex_digits() {
+		for value in ["#2A4BC", "#2A4BC2F", "#2A4BG2", "", "#", "#2A4BÇ"] {
+			assert_eq!(
+				value.parse::<Color>(),
+				Err(ParseColorError(value.to_string())),
+				"{value}"
+			);
+			assert!(serde_json::from_value::<Color>(value.into()).is_err());
+		}
+	}
+
+	fn paper_only() -> ByPlatform<&'static str> {
+		ByPlatform {
+			paper: Some("paper.jar"),
+			..ByPlatform::default()
+		}
+	}
+
+	#[test]
+	fn by_platform_with_only_paper() {
+		let downloads = paper_only();
+		assert!(!downloads.is_empty());
+		assert_eq!(downloads.len(), 1);
+		assert!(downloads.contains(Platform::Paper));
+		assert!(!downloads.contains(Platform::Velocity));
+		assert_eq!(downloads.get(Platform::Paper), Some(&"paper.jar"));
+		assert_eq!(downloads.get(Platform::Waterfall), None);
+		assert_eq!(
+			downloads.iter().collect::<Vec<_>>(),
+			[(Platform::Paper, &"paper.jar")]
+		);
+		let lengths = downloads.map(str::len);
+		assert_eq!(lengths.paper, Some(9));
+		assert_eq!(lengths.len(), 1);
+		assert!(ByPlatform::<()>::default().is_empty());
+	}
+
+	#[test]
+	fn by_platform_serializes_only_present_platforms() {
+		assert_eq!(
+			serde_json::to_string(&paper_only()).unwrap(),
+			r#"{"PAPER":"paper.jar"}"#
+		);
+		let mut downloads = paper_only();
+		assert_eq!(
+			downloads.insert(Platform::Paper, "new.jar"),
+			Some("paper.jar")
+		);
+		assert_eq!(downloads.insert(Platform::Velocity, "proxy.jar"), None);
+		assert_eq!(downloads.len(), 2);
+	}
+
+	fn version(json: &str) -> Version {
+		serde_json::from_str(json).unwrap()
+	}
+
+	fn internal_version() -> Version {
+		version(include_str!("../tests/fixtures/version_internal.json"))
+	}
+
+	#[test]
+	fn version_platforms_match_the_download_keys() {
+		let mut version = internal_version();
+		assert_eq!(version.platforms(), [Platform::Paper, Platform::Velocity]);
+		for platform in version.platforms() {
+			assert!(version.download_for(platform).is_some());
+		}
+		assert!(version.download_for(Platform::Waterfall).is_none());
+		version.downloads.paper = None;
+		assert_eq!(version.platforms(), [Platform::Velocity]);
+		assert!(version.download_for(Platform::Paper).is_none());
+	}
+
+	#[test]
+	fn relative_avatar_urls_resolve_against_hangar() {
+		assert_eq!(
+			absolute_url("/avatars/project/8.webp?v=1"),
+			"https://hangar.papermc.io/avatars/project/8.webp?v=1"
+		);
+		assert_eq!(
+			absolute_url("avatars/user/12.webp"),
+			"https://hangar.papermc.io/avatars/user/12.webp"
+		);
+		assert_eq!(
+			absolute_url("//hangarcdn.papermc.io/avatars/project/8.webp"),
+			"https://hangarcdn.papermc.io/avatars/project/8.webp"
+		);
+	}
+
+	fn named_project(name: &str, slug: &str) -> Project {
+		let mut project: serde_json::Value =
+			serde_json::from_str(include_str!("../tests/fixtures/project.json")).unwrap();
+		project["name"] = name.into();
+		project["namespace"]["slug"] = slug.into();
+		serde_json::from_value(project).unwrap()
+	}
+
+	#[test]
+	fn projects_are_named_by_their_display_name_or_slug() {
+		let project = named_project("Cool Plugin!", "CoolPlugin");
+		for name in [
+			"Cool Plugin!",
+			"cool plugin!",
+			"CoolPlugin",
+			"coolplugin",
+			"  CoolPlugin ",
+			"cool plugin",
+			"cool-plugin",
+			"Cool_Plugin",
+		] {
+			assert!(project.is_named(name), "{name}");
+		}
+		for name in ["coolplugins", "cool", "Cool Plugin 2", "", "  ", "!!"] {
+			assert!(!project.is_named(name), "{name}");
+		}
+	}
+
+	#[test]
+	fn punctuation_only_matches_the_display_name() {
+		let project = named_project("???", "Question");
+		assert!(project.is_named("???"));
+		assert!(!project.is_named("!!!"));
+	}
+
+	#[test]
+	fn project_avatar_urls_are_resolved_only_when_relative() {
+		let project: Project =
+			serde_json::from_str(include_str!("../tests/fixtures/project.json")).unwrap();
+		assert_eq!(project.avatar_url_absolute(), project.avatar_url);
+		let relative = Project {
+			avatar_url: "/avatars/project/8.webp?v=1".to_string(),
+			..project
+		};
+		assert_eq!(
+			relative.avatar_url_absolute(),
+			"https://hangar.papermc.io/avatars/project/8.webp?v=1"
+		);
+		assert_eq!(relative.avatar_url, "/avatars/project/8.webp?v=1");
+		assert_eq!(
+			absolute_url("http://localhost:3333/avatars/project/1.webp"),
+			"http://localhost:3333/avatars/project/1.webp"
+		);
+	}
+
+	#[test]
+	fn dependencies_serialize_grouped_by_platform() {
+		let dependencies = PluginDependenciesBuilder::new()
+			.hangar(Platform::Velocity, "LuckPerms", true)
+			.hangar(Platform::Paper, "LuckPerms", true)
+			.external(
+				Platform::Paper,
+				"ProtocolLib",
+				"https://github.com/dmulloy2/ProtocolLib/releases",
+				false,
+			)
+			.build()
+			.unwrap();
+		let json = serde_json::to_string_pretty(&dependencies).unwrap();
+		assert_eq!(
+			json,
+			r#"{
+  "PAPER": [
+    {
+      "name": "LuckPerms",
+      "required": true,
+      "externalUrl": null,
+      "platform": "PAPER"
+    },
+    {
+      "name": "ProtocolLib",
+      "required": false,
+      "externalUrl": "https://github.com/dmulloy2/ProtocolLib/releases",
+      "platform": "PAPER"
+    }
+  ],
+  "VELOCITY": [
+    {
+      "name": "LuckPerms",
+      "required": true,
+      "externalUrl": null,
+      "platform": "VELOCITY"
+    }
+  ]
+}"#
+		);
+	}
+
+	#[test]
+	fn no_dependencies_serialize_as_an_empty_object() {
+		let dependencies = PluginDependenciesBuilder::new().build().unwrap();
+		assert_eq!(serde_json::to_string(&dependencies).unwrap(), "{}");
+	}
+
+	#[test]
+	fn dependencies_serialize_like_hangar_sends_them() {
+		let fixture: serde_json::Value =
+			serde_json::from_str(include_str!("../tests/fixtures/version_internal.json")).unwrap();
+		let version = internal_version();
+		assert_eq!(
+			serde_json::to_value(&version.plugin_dependencies).unwrap(),
+			fixture["pluginDependencies"]
+		);
+	}
+
+	#[test]
+	fn invalid_dependencies_are_rejected() {
+		let build = |dependency| {
+			PluginDependenciesBuilder::new()
+				.dependency(dependency)
+				.build()
+				.unwrap_err()
+		};
+		assert_eq!(
+			build(VersionPluginDependencies::hangar(
+				" ",
+				true,
+				Platform::Paper
+			)),
+			DependencyError::MissingName
+		);
+		for url in [
+			"",
+			"github.com/dmulloy2/ProtocolLib",
+			"ftp://example.com/a.jar",
+		] {
+			assert_eq!(
+				build(VersionPluginDependencies::external(
+					"ProtocolLib",
+					url,
+					true,
+					Platform::Paper
+				)),
+				DependencyError::InvalidUrl {
+					name: "ProtocolLib".to_string(),
+					url: url.to_string(),
+				}
+			);
+		}
+	}
+
+	#[test]
+	fn dependencies_are_only_listed_once_per_platform() {
+		let err = PluginDependenciesBuilder::new()
+			.hangar(Platform::Paper, "LuckPerms", true)
+			.hangar(Platform::Paper, "luckperms", false)
+			.build()
+			.unwrap_err();
+		assert_eq!(
+			err,
+			DependencyError::Duplicate {
+				name: "luckperms".to_string(),
+				platform: Platform::Paper,
+			}
+		);
+		assert!(PluginDependenciesBuilder::new()
+			.hangar(Platform::Paper, "LuckPerms", true)
+			.hangar(Platform::Waterfall, "LuckPerms", true)
+			.build()
+			.is_ok());
+	}
+
+	#[test]
+	fn dependencies_split_into_hangar_and_external() {
+		let mut version = internal_version();
+		version.plugin_dependencies.paper = Some(vec![
+			VersionPluginDependencies::hangar("LuckPerms", true, Platform::Paper),
+			VersionPluginDependencies::external(
+				"ProtocolLib",
+				"https://github.com/dmulloy2/ProtocolLib/releases",
+				false,
+				Platform::Paper,
+			),
+			VersionPluginDependencies::hangar("PlaceholderAPI", false, Platform::Paper),
+		]);
+		let hangar: Vec<_> = version
+			.hangar_dependencies(Platform::Paper)
+			.into_iter()
+			.filter_map(VersionPluginDependencies::hangar_slug)
+			.collect();
+		assert_eq!(hangar, ["LuckPerms", "PlaceholderAPI"]);
+		let external = version.external_dependencies(Platform::Paper);
+		assert_eq!(external.len(), 1);
+		assert_eq!(external[0].name, "ProtocolLib");
+		assert!(!external[0].is_hangar());
+		assert_eq!(external[0].hangar_slug(), None);
+		assert!(version.hangar_dependencies(Platform::Velocity).is_empty());
+		assert!(version.external_dependencies(Platform::Velocity).is_empty());
+	}
+
+	#[test]
+	fn channels_with_several_flags() {
+		let channel: Channel = serde_json::from_str(
+			r##"{
+				"createdAt": "2023-01-09T11:02:18.526173Z",
+				"name": "Legacy",
+				"description": "Old versions which are no longer updated",
+				"color": "#A8A8A8",
+				"flags": ["FROZEN", "UNSTABLE", "HIDE_BY_DEFAULT"]
+			}"##,
+		)
+		.unwrap();
+		assert!(channel.is_frozen());
+		assert!(channel.is_unstable());
+		assert!(channel.hides_by_default());
+		assert!(!channel.is_pinned());
+		assert!(!channel.has_flag(ChannelFlags::SendsNotifications));
+		assert_eq!(channel.flags.len(), 3);
+		assert_eq!(
+			channel.flags.iter().collect::<Vec<_>>(),
+			[
+				ChannelFlags::Frozen,
+				ChannelFlags::Unstable,
+				ChannelFlags::HideByDefault
+			]
+		);
+		assert_eq!(channel.common(), CommonChannel::Other("Legacy".to_string()));
+	}
+
+	#[test]
+	fn channel_flag_sets_can_be_changed() {
+		let mut flags = internal_version().channel.flags;
+		assert_eq!(
+			flags.iter().collect::<Vec<_>>(),
+			[ChannelFlags::Pinned, ChannelFlags::SendsNotifications]
+		);
+		flags.remove(ChannelFlags::Pinned);
+		flags.insert(ChannelFlags::Frozen);
+		flags.insert(ChannelFlags::Frozen);
+		assert_eq!(
+			flags,
+			[ChannelFlags::Frozen, ChannelFlags::SendsNotifications]
+				.into_iter()
+				.collect()
+		);
+		assert!(ChannelFlagSet::default().is_empty());
+	}
+
+	fn project_stats(views: i64, downloads: i64) -> ProjectStats {
+		ProjectStats {
+			views,
+			downloads,
+			recent_views: views / 2,
+			recent_downloads: downloads / 4,
+			stars: 4,
+			watchers: 1,
+		}
+	}
+
+	#[test]
+	fn derived_stats_without_views() {
+		let derived = project_stats(0, 40).derived();
+		assert_eq!(derived.downloads_per_view, None);
+		assert_eq!(derived.recent_view_share, None);
+		assert_eq!(derived.stars_per_download, Some(0.1));
+		assert_eq!(derived.recent_download_share, Some(0.25));
+		assert_eq!(derived.trending_score(), 0.25);
+	}
+
+	#[test]
+	fn derived_stats_without_downloads() {
+		let derived = project_stats(80, 0).derived();
+		assert_eq!(derived.downloads_per_view, Some(0.0));
+		assert_eq!(derived.recent_view_share, Some(0.5));
+		assert_eq!(derived.stars_per_download, None);
+		assert_eq!(derived.recent_download_share, None);
+		assert_eq!(derived.trending_score(), 0.0);
+	}
+
+	#[test]
+	fn derived_stats_are_never_nan_or_infinite() {
+		for (views, downloads) in [(0, 0), (0, 1), (1, 0), (-5, 10), (10, -5)] {
+			let derived = project_stats(views, downloads).derived();
+			for ratio in [
+				derived.downloads_per_view,
+				derived.stars_per_download,
+				derived.recent_download_share,
+				derived.recent_view_share,
+			]
+			.into_iter()
+			.flatten()
+			{
+				assert!(ratio.is_finite(), "{views} views, {downloads} downloads");
+			}
+			assert!(derived.trending_score().is_finite());
+		}
+	}
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use web_time::Instant;
+
+/// Headers which may hold how many requests are left before being rate limited, in order of preference.
+/// Hangar sends `X-Rate-Limit-Remaining`, the others are accepted for proxies and self-hosted instances.
+pub const REMAINING_HEADERS: &[&str] = &["X-Rate-Limit-Remaining", "X-RateLimit-Remaining"];
+/// Headers which may hold how many requests can be made in total before being rate limited.
+pub const LIMIT_HEADERS: &[&str] = &["X-Rate-Limit-Limit", "X-RateLimit-Limit"];
+/// Headers which may hold how many seconds are left until requests are allowed again.
+/// Hangar sends `X-Rate-Limit-Retry-After-Seconds` once requests are being rejected.
+pub const RESET_HEADERS: &[&str] = &["X-Rate-Limit-Retry-After-Seconds", "X-RateLimit-Reset"];
+
+/// The rate limit state Hangar reported in a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+	/// How many requests can be made in total, if known
+	pub limit: Option<u64>,
+	/// How many requests are left before being rate limited
+	pub remaining: u64,
+	/// How long after the response was received until requests are allowed again, if known
+	pub reset_after: Option<Duration>,
+	/// When the response was received
+	pub observed_at: Instant,
+}
+
+impl RateLimit {
+	/// Parses the rate limit headers of a response, if it has any.
+	/// Responses which were rate limited count as having no requests left even without the headers.
+	pub fn from_headers(status: u16, headers: &HeaderMap) -> Option<Self> {
+		let remaining = header_value(headers, REMAINING_HEADERS);
+		let reset_after = header_value(headers, RESET_HEADERS).map(Duration::from_secs);
+		let remaining = match (remaining, status) {
+			(Some(remaining), _) => remaining,
+			(None, 429) => 0,
+			(None, _) => return None,
+		};
+		Some(Self {
+			limit: header_value(headers, LIMIT_HEADERS),
+			remaining,
+			reset_after,
+			observed_at: Instant::now(),
+		})
+	}
+
+	/// Checks whether there are no requests left.
+	pub fn is_exhausted(&self) -> bool {
+		self.remaining == 0
+	}
+
+	/// Gets how long is left until requests are allowed again, if known.
+	pub fn time_until_reset(&self) -> Option<Duration> {
+		Some(self.reset_after?.saturating_sub(self.observed_at.elapsed()))
+	}
+}
+
+/// Gets the first of the given headers which is present and a valid number.
+fn header_value(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+	names.iter().find_map(|name| {
+		headers
+			.get(*name)
+			.and_then(|v| v.to_str().ok())
+			.and_then(|v| v.trim().parse().ok())
+	})
+}
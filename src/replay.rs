@@ -0,0 +1,274 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_LENGTH, SET_COOKIE};
+use serde::{Deserialize, Serialize};
+
+use crate::api::SECRET_PARAMS;
+use crate::backend::{stable_hash, url_key, HttpBackend, HttpRequest, HttpResponse};
+use crate::error::{Error, Result};
+
+/// The environment variable which makes `CassetteBackend::from_env` record rather than replay.
+pub const RECORD_VAR: &str = "HANGAR_RECORD";
+
+/// What identifies a request in a recording. Secret query parameters are redacted, so requests match whichever
+/// api key they were sent with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RequestId {
+	method: String,
+	url: String,
+	query: Vec<(String, String)>,
+}
+
+impl RequestId {
+	fn new(request: &HttpRequest) -> Self {
+		let mut query: Vec<(String, String)> = request
+			.query
+			.iter()
+			.map(|(name, value)| {
+				let value = if SECRET_PARAMS.contains(&name.as_str()) {
+					"REDACTED".to_string()
+				} else {
+					value.clone()
+				};
+				(name.clone(), value)
+			})
+			.collect();
+		query.sort();
+		Self {
+			method: request.method.to_string(),
+			url: request.url.clone(),
+			query,
+		}
+	}
+
+	/// Gets the path of the file the response to this request is recorded in.
+	fn path(&self, dir: &Path) -> PathBuf {
+		let key = self.to_string();
+		dir.join(format!("{:016x}.json", stable_hash(&key)))
+	}
+}
+
+impl std::fmt::Display for RequestId {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.query.is_empty() {
+			write!(f, "{} {}", self.method, self.url)
+		} else {
+			write!(f, "{} {}", self.method, url_key(&self.url, &self.query))
+		}
+	}
+}
+
+/// A request and its response as stored in a file. Bodies are stored as json when they are json, so recordings
+/// can be read and edited by hand.
+#[derive(Debug, Serialize, Deserialize)]
+struct Recording {
+	request: RequestId,
+	status: u16,
+	/// The final url of the response
+	response_url: String,
+	headers: Vec<(String, String)>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	json: Option<serde_json::Value>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	text: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	bytes: Option<Vec<u8>>,
+}
+
+impl Recording {
+	fn new(request: RequestId, response: &HttpResponse) -> Self {
+		let mut recording = Self {
+			request,
+			status: response.status,
+			response_url: response.url.clone(),
+			headers: response
+				.headers
+				.iter()
+				// bodies stored as json may not be stored byte for byte, so the length is left out too
+				.filter(|(name, _)| **name != SET_COOKIE && **name != CONTENT_LENGTH)
+				.filter_map(|(name, value)| {
+					Some((name.to_string(), value.to_str().ok()?.to_string()))
+				})
+				.collect(),
+			json: None,
+			text: None,
+			bytes: None,
+		};
+		if let Ok(json) = serde_json::from_slice(&response.body) {
+			recording.json = Some(json);
+		} else if let Ok(text) = std::str::from_utf8(&response.body) {
+			recording.text = Some(text.to_string());
+		} else {
+			recording.bytes = Some(response.body.clone());
+		}
+		recording
+	}
+
+	fn into_response(self) -> Result<HttpResponse> {
+		let mut headers = HeaderMap::new();
+		for (name, value) in self.headers {
+			let name = HeaderName::try_from(name)
+				.map_err(|err| Error::Replay(format!("invalid recorded header: {err}")))?;
+			let value = HeaderValue::try_from(value)
+				.map_err(|err| Error::Replay(format!("invalid recorded header: {err}")))?;
+			headers.append(name, value);
+		}
+		let body = match (self.json, self.text, self.bytes) {
+			(Some(json), _, _) => serde_json::to_vec(&json)
+				.map_err(|err| Error::Replay(format!("invalid recorded body: {err}")))?,
+			(_, Some(text), _) => text.into_bytes(),
+			(_, _, Some(bytes)) => bytes,
+			_ => Vec::new(),
+		};
+		Ok(HttpResponse {
+			status: self.status,
+			url: self.response_url,
+			headers,
+			body,
+		})
+	}
+}
+
+/// A backend which sends requests with another backend, recording every response to a directory for a
+/// `ReplayBackend` to serve later.
+///
+/// Each request gets its own json file named after the request, replacing any earlier recording of it. Request
+/// headers aren't recorded, so the `Authorization` header never ends up in a recording, and secret query
+/// parameters like api keys are redacted.
+#[derive(Debug, Clone)]
+pub struct RecordingBackend<B> {
+	inner: B,
+	dir: PathBuf,
+}
+
+impl<B> RecordingBackend<B> {
+	/// Creates a backend recording the responses `inner` gets to `dir`, which is created when the first response
+	/// is recorded.
+	pub fn new(inner: B, dir: impl Into<PathBuf>) -> Self {
+		Self {
+			inner,
+			dir: dir.into(),
+		}
+	}
+
+	/// Gets the backend requests are sent with.
+	pub fn inner(&self) -> &B {
+		&self.inner
+	}
+
+	/// Gets the directory responses are recorded to.
+	pub fn dir(&self) -> &Path {
+		&self.dir
+	}
+
+	fn record(&self, request: RequestId, response: &HttpResponse) -> std::io::Result<()> {
+		let path = request.path(&self.dir);
+		let recording = Recording::new(request, response);
+		fs::create_dir_all(&self.dir)?;
+		fs::write(path, serde_json::to_vec_pretty(&recording)?)
+	}
+}
+
+impl<B: HttpBackend> HttpBackend for RecordingBackend<B> {
+	async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+		let id = RequestId::new(&request);
+		let response = self.inner.execute(request).await?;
+		self.record(id, &response).map_err(|err| {
+			Error::Replay(format!("failed to record to {}: {err}", self.dir.display()))
+		})?;
+		Ok(response)
+	}
+}
+
+/// A backend serving the responses recorded by a `RecordingBackend`, without any network.
+///
+/// Requests are matched by method, url, and query parameters in any order. Requests which weren't recorded fail
+/// with `Error::Replay` naming the request, rather than getting a made up response.
+#[derive(Debug, Clone)]
+pub struct ReplayBackend {
+	dir: PathBuf,
+}
+
+impl ReplayBackend {
+	/// Creates a backend serving the responses recorded in `dir`.
+	pub fn new(dir: impl Into<PathBuf>) -> Self {
+		Self { dir: dir.into() }
+	}
+
+	/// Gets the directory responses are served from.
+	pub fn dir(&self) -> &Path {
+		&self.dir
+	}
+
+	fn replay(&self, request: &HttpRequest) -> Result<HttpResponse> {
+		let id = RequestId::new(request);
+		let path = id.path(&self.dir);
+		let missing = || Error::Replay(format!("no recording of {id} in {}", self.dir.display()));
+		let contents = fs::read(&path).map_err(|_| missing())?;
+		let recording: Recording = serde_json::from_slice(&contents)
+			.map_err(|err| Error::Replay(format!("invalid recording {}: {err}", path.display())))?;
+		if recording.request != id {
+			return Err(missing());
+		}
+		recording.into_response()
+	}
+}
+
+impl HttpBackend for ReplayBackend {
+	async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+		self.replay(&request)
+	}
+}
+
+/// A backend which either records or replays responses, for integration tests which run against real Hangar
+/// responses without needing the network.
+///
+/// The usual workflow is to run the tests once with `HANGAR_RECORD=1` to record responses from Hangar, commit the
+/// recordings, and replay them everywhere else:
+///
+/// ```no_run
+/// # fn run() -> hangar_api::error::Result<()> {
+/// use hangar_api::backend::ReqwestBackend;
+/// use hangar_api::client::HangarClient;
+/// use hangar_api::replay::CassetteBackend;
+///
+/// let backend = CassetteBackend::from_env(ReqwestBackend::default(), "tests/cassettes");
+/// let client = HangarClient::builder().build_with_backend(backend)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub enum CassetteBackend<B> {
+	Record(RecordingBackend<B>),
+	Replay(ReplayBackend),
+}
+
+impl<B> CassetteBackend<B> {
+	/// Records the responses `inner` gets to `dir` if `RECORD_VAR` is set to anything but `0` or an empty string,
+	/// otherwise replays the responses recorded there.
+	pub fn from_env(inner: B, dir: impl Into<PathBuf>) -> Self {
+		let record = std::env::var(RECORD_VAR).is_ok_and(|value| !value.is_empty() && value != "0");
+		if record {
+			Self::Record(RecordingBackend::new(inner, dir))
+		} else {
+			Self::Replay(ReplayBackend::new(dir))
+		}
+	}
+
+	/// Checks whether responses are being recorded rather than replayed.
+	pub fn is_recording(&self) -> bool {
+		matches!(self, Self::Record(_))
+	}
+}
+
+impl<B: HttpBackend> HttpBackend for CassetteBackend<B> {
+	async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+		match self {
+			Self::Record(backend) => backend.execute(request).await,
+			Self::Replay(backend) => backend.execute(request).await,
+		}
+	}
+}
@@ -0,0 +1,314 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use typed_builder::TypedBuilder;
+
+use crate::api::{ProjectsRequest, ProjectsResponse, VersionsRequest, VersionsResponse};
+use crate::object::{Pagination, Platform, Version, VersionPluginDependencies};
+
+/// Options for resolving the plugin dependencies of a version.
+#[derive(Debug, Clone, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct ResolveOptions {
+	/// How many levels of dependencies of dependencies are followed, counting the version's own dependencies as 1
+	#[builder(default = 8)]
+	pub max_depth: usize,
+	/// Whether dependencies which aren't required are resolved too
+	#[builder(default = false)]
+	pub include_optional: bool,
+	/// A Minecraft version like `1.20.4` which resolved versions must support
+	#[builder(default, setter(strip_option))]
+	pub minecraft_version: Option<String>,
+}
+
+impl Default for ResolveOptions {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+/// The result of resolving the plugin dependencies of a version.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyReport {
+	/// Dependencies found on Hangar, in the order they were resolved. Each can be downloaded by passing its version
+	/// to `HangarClient::download` with the platform it was resolved for
+	pub hangar: Vec<ResolvedDependency>,
+	/// Dependencies downloaded from outside of Hangar, which aren't followed further
+	pub external: Vec<ExternalDependency>,
+	/// Dependencies which couldn't be resolved
+	pub unresolved: Vec<UnresolvedDependency>,
+	/// Which dependency lists which, including dependencies listed again after they were already resolved
+	pub edges: Vec<DependencyEdge>,
+}
+
+impl DependencyReport {
+	/// Checks whether every required dependency was resolved.
+	pub fn is_complete(&self) -> bool {
+		!self.unresolved.iter().any(|dependency| dependency.required)
+	}
+
+	/// Gets the names of the dependencies a dependency lists, or the version's own dependencies for `None`.
+	pub fn dependencies_of(&self, name: Option<&str>) -> Vec<&str> {
+		self.edges
+			.iter()
+			.filter(|edge| match (&edge.from, name) {
+				(Some(from), Some(name)) => from.eq_ignore_ascii_case(name),
+				(None, None) => true,
+				_ => false,
+			})
+			.map(|edge| edge.to.as_str())
+			.collect()
+	}
+
+	/// Finds the cycles among the dependencies, like a plugin depending on a library which depends on the plugin
+	/// again. Each cycle is listed by the names of its dependencies, starting from the one reached first.
+	/// Cycles don't stop resolving, since every dependency is only resolved once.
+	pub fn cycles(&self) -> Vec<Vec<String>> {
+		let mut graph: HashMap<String, Vec<&DependencyEdge>> = HashMap::new();
+		for edge in &self.edges {
+			if let Some(from) = &edge.from {
+				graph.entry(from.to_lowercase()).or_default().push(edge);
+			}
+		}
+		let mut cycles = Vec::new();
+		let mut done = HashSet::new();
+		for root in self.dependencies_of(None) {
+			let mut path = Vec::new();
+			find_cycles(&graph, root, &mut path, &mut done, &mut cycles);
+		}
+		cycles
+	}
+}
+
+/// Walks the dependency graph depth first from a dependency, recording a cycle whenever a dependency on the
+/// current path is reached again.
+fn find_cycles<'a>(
+	graph: &HashMap<String, Vec<&'a DependencyEdge>>,
+	name: &'a str,
+	path: &mut Vec<&'a str>,
+	done: &mut HashSet<String>,
+	cycles: &mut Vec<Vec<String>>,
+) {
+	let key = name.to_lowercase();
+	if let Some(start) = path
+		.iter()
+		.position(|other| other.eq_ignore_ascii_case(name))
+	{
+		cycles.push(path[start..].iter().map(|name| name.to_string()).collect());
+		return;
+	}
+	if done.contains(&key) {
+		return;
+	}
+	path.push(name);
+	for edge in graph.get(&key).into_iter().flatten() {
+		find_cycles(graph, &edge.to, path, done, cycles);
+	}
+	path.pop();
+	done.insert(key);
+}
+
+/// A dependency listed by a version, either the version being resolved or a resolved dependency's version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyEdge {
+	/// The name of the dependency listing it, or `None` for the version being resolved
+	pub from: Option<String>,
+	/// The name the dependency was listed under
+	pub to: String,
+	/// Whether the dependency is required
+	pub required: bool,
+}
+
+/// A dependency resolved to a version of a Hangar project.
+#[derive(Debug, Clone)]
+pub struct ResolvedDependency {
+	/// The name the dependency was listed under
+	pub name: String,
+	/// The slug of the project the dependency was resolved to
+	pub slug: String,
+	/// The latest compatible version of the project
+	pub version: Version,
+	/// Whether the dependency is required
+	pub required: bool,
+	/// How many levels of dependencies were followed to get here, starting from 1
+	pub depth: usize,
+}
+
+/// A dependency downloaded from outside of Hangar.
+#[derive(Debug, Clone)]
+pub struct ExternalDependency {
+	/// The name the dependency was listed under
+	pub name: String,
+	/// The url to download the dependency from
+	pub url: String,
+	/// Whether the dependency is required
+	pub required: bool,
+}
+
+/// A dependency which couldn't be resolved.
+#[derive(Debug, Clone)]
+pub struct UnresolvedDependency {
+	/// The name the dependency was listed under
+	pub name: String,
+	/// Whether the dependency is required
+	pub required: bool,
+	/// Why the dependency couldn't be resolved
+	pub reason: UnresolvedReason,
+}
+
+/// Why a dependency couldn't be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnresolvedReason {
+	/// No Hangar project has the dependency's name
+	NotFound,
+	/// The project has no available version for the platform, or the requested Minecraft version
+	NoCompatibleVersion,
+	/// The dependency is nested deeper than `ResolveOptions::max_depth`
+	MaxDepth,
+}
+
+/// Walks the dependencies of a version one at a time, leaving the requests for each step to the client.
+/// Dependencies are resolved breadth first and every name only once, so cycles end when they get back to a
+/// dependency which was already seen.
+pub(crate) struct Resolver {
+	platform: Platform,
+	options: ResolveOptions,
+	/// Dependencies still to be looked up, with their depth and the name of the dependency listing them
+	queue: VecDeque<(VersionPluginDependencies, usize, Option<String>)>,
+	visited: HashSet<String>,
+	report: DependencyReport,
+}
+
+impl Resolver {
+	pub(crate) fn new(version: &Version, platform: Platform, options: ResolveOptions) -> Self {
+		let mut resolver = Self {
+			platform,
+			options,
+			queue: VecDeque::new(),
+			visited: HashSet::new(),
+			report: DependencyReport::default(),
+		};
+		resolver.enqueue(version, 1, None);
+		resolver
+	}
+
+	/// Queues the dependencies of a version for the platform.
+	fn enqueue(&mut self, version: &Version, depth: usize, parent: Option<&str>) {
+		let dependencies = version
+			.plugin_dependencies
+			.get(self.platform)
+			.into_iter()
+			.flatten();
+		for dependency in dependencies {
+			self.queue
+				.push_back((dependency.clone(), depth, parent.map(str::to_string)));
+		}
+	}
+
+	/// Gets the next dependency which needs to be looked up on Hangar, recording external and skipped
+	/// dependencies along the way.
+	pub(crate) fn next(&mut self) -> Option<(VersionPluginDependencies, usize)> {
+		while let Some((dependency, depth, parent)) = self.queue.pop_front() {
+			if !dependency.required && !self.options.include_optional {
+				continue;
+			}
+			self.report.edges.push(DependencyEdge {
+				from: parent,
+				to: dependency.name.clone(),
+				required: dependency.required,
+			});
+			if !self.visited.insert(dependency.name.to_lowercase()) {
+				continue;
+			}
+			if let Some(url) = &dependency.external_url {
+				self.report.external.push(ExternalDependency {
+					name: dependency.name.clone(),
+					url: url.clone(),
+					required: dependency.required,
+				});
+				continue;
+			}
+			if depth > self.options.max_depth {
+				self.unresolved(&dependency, UnresolvedReason::MaxDepth);
+				continue;
+			}
+			return Some((dependency, depth));
+		}
+		None
+	}
+
+	/// Creates the request to search for the project a dependency refers to.
+	pub(crate) fn project_request(dependency: &VersionPluginDependencies) -> ProjectsRequest {
+		ProjectsRequest::builder()
+			.query(dependency.name.clone())
+			.prioritize_exact_match(true)
+			.pagination(Pagination::page(0, 1))
+			.build()
+	}
+
+	/// Finds the slug of the project a dependency refers to, recording it as unresolved if there isn't one.
+	pub(crate) fn find_project(
+		&mut self,
+		dependency: &VersionPluginDependencies,
+		projects: ProjectsResponse,
+	) -> Option<String> {
+		let project = projects
+			.result
+			.into_iter()
+			.find(|project| project.name.eq_ignore_ascii_case(&dependency.name));
+		match project {
+			Some(project) => Some(project.namespace.slug.into_string()),
+			None => {
+				self.unresolved(dependency, UnresolvedReason::NotFound);
+				None
+			}
+		}
+	}
+
+	/// Creates the request for the newest versions of a project on the platform.
+	pub(crate) fn versions_request(&self, slug: &str) -> VersionsRequest {
+		VersionsRequest::builder()
+			.slug(slug)
+			.pagination(Pagination::page(0, Pagination::MAX_LIMIT))
+			.platform(self.platform)
+			.platform_version(self.options.minecraft_version.clone())
+			.build()
+	}
+
+	/// Resolves a dependency to the newest available version with a download for the platform, queueing its own
+	/// dependencies.
+	pub(crate) fn resolve(
+		&mut self,
+		dependency: VersionPluginDependencies,
+		depth: usize,
+		slug: String,
+		versions: VersionsResponse,
+	) {
+		let version = versions.result.into_iter().find(|version| {
+			version.is_available() && version.download_for(self.platform).is_some()
+		});
+		let Some(version) = version else {
+			self.unresolved(&dependency, UnresolvedReason::NoCompatibleVersion);
+			return;
+		};
+		self.enqueue(&version, depth + 1, Some(&dependency.name));
+		self.report.hangar.push(ResolvedDependency {
+			name: dependency.name,
+			slug,
+			version,
+			required: dependency.required,
+			depth,
+		});
+	}
+
+	fn unresolved(&mut self, dependency: &VersionPluginDependencies, reason: UnresolvedReason) {
+		self.report.unresolved.push(UnresolvedDependency {
+			name: dependency.name.clone(),
+			required: dependency.required,
+			reason,
+		});
+	}
+
+	pub(crate) fn finish(self) -> DependencyReport {
+		self.report
+	}
+}
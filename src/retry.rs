@@ -0,0 +1,85 @@
+use std::{
+	collections::hash_map::RandomState,
+	hash::{BuildHasher, Hasher},
+	time::Duration,
+};
+
+use typed_builder::TypedBuilder;
+
+use crate::error::Error;
+
+/// Policy for retrying idempotent requests which failed for transient reasons.
+/// Rate limiting is handled separately, see `HangarClientBuilder::rate_limit_retries`.
+#[derive(Debug, Clone, TypedBuilder)]
+#[builder(field_defaults(setter(into)))]
+pub struct RetryPolicy {
+	/// The maximum number of attempts made for a request, including the first
+	#[builder(default = 3)]
+	pub max_attempts: u32,
+	/// The delay before the first retry, doubled for every retry after it
+	#[builder(default = Duration::from_millis(500))]
+	pub base_delay: Duration,
+	/// The longest delay between retries, including jitter
+	#[builder(default = Duration::from_secs(30))]
+	pub max_delay: Duration,
+	/// Whether to add up to half the delay again as random jitter
+	#[builder(default = true)]
+	pub jitter: bool,
+	/// Response statuses which are considered transient
+	#[builder(default = vec![500, 502, 503, 504])]
+	pub retry_statuses: Vec<u16>,
+	/// Whether connection failures and timeouts are considered transient
+	#[builder(default = true)]
+	pub retry_connection_errors: bool,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self::builder().build()
+	}
+}
+
+impl RetryPolicy {
+	/// A policy which never retries.
+	pub fn none() -> Self {
+		Self::builder().max_attempts(1u32).build()
+	}
+
+	/// Whether the given error should be retried under this policy.
+	pub fn is_retryable(&self, error: &Error) -> bool {
+		match error {
+			Error::Http(err) => self.retry_connection_errors && is_connection_error(err),
+			Error::Api { status, .. } | Error::Unavailable { status, .. } => {
+				self.retry_statuses.contains(status)
+			}
+			_ => false,
+		}
+	}
+
+	/// Gets the delay before the given retry, starting from 1. The delay never exceeds `max_delay`.
+	pub fn delay(&self, retry: u32) -> Duration {
+		let delay = self
+			.base_delay
+			.saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+		let delay = if self.jitter {
+			let random = RandomState::new().build_hasher().finish();
+			delay + delay.mul_f64((random % 1000) as f64 / 2000.0)
+		} else {
+			delay
+		};
+		delay.min(self.max_delay)
+	}
+}
+
+/// Whether an http error came from failing to connect or timing out.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connection_error(err: &reqwest::Error) -> bool {
+	err.is_connect() || err.is_timeout()
+}
+
+/// Whether an http error came from failing to connect. Browsers don't tell us why a fetch failed, so any request
+/// error counts.
+#[cfg(target_arch = "wasm32")]
+fn is_connection_error(err: &reqwest::Error) -> bool {
+	err.is_request()
+}
@@ -0,0 +1,195 @@
+use crate::api::{
+	ProjectRequest, ProjectsRequest, ProjectsResponse, VersionRequest, VersionsRequest,
+	VersionsResponse,
+};
+use crate::backend::{HttpBackend, MaybeSend, MaybeSync};
+use crate::client::HangarClient;
+use crate::error::Result;
+use crate::object::{Project, Version};
+
+/// The boxed future returned by `HangarApi`'s methods.
+#[cfg(not(target_arch = "wasm32"))]
+pub type ApiFuture<'a, T> = futures::future::BoxFuture<'a, Result<T>>;
+/// The boxed future returned by `HangarApi`'s methods. On wasm futures can't be sent between threads.
+#[cfg(target_arch = "wasm32")]
+pub type ApiFuture<'a, T> = futures::future::LocalBoxFuture<'a, Result<T>>;
+
+/// The core read methods of a Hangar client, for code which wants to depend on something it can swap out rather
+/// than on `HangarClient` itself, e.g. to use a `MockHangarApi` in tests. Unlike the client it can be used as a
+/// trait object like `Arc<dyn HangarApi>`, so its methods return boxed futures.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use hangar_api::client::HangarClient;
+/// use hangar_api::object::Project;
+/// use hangar_api::service::HangarApi;
+///
+/// async fn describe(api: &dyn HangarApi, slug: &str) -> hangar_api::Result<String> {
+///     let project: Project = api.project(slug).await?;
+///     Ok(format!("{} has {} downloads", project.namespace.key(), project.stats.downloads))
+/// }
+///
+/// # fn run() -> hangar_api::Result<()> {
+/// let api: Arc<dyn HangarApi> = Arc::new(HangarClient::builder().build()?);
+/// # let _ = describe(&*api, "Maintenance");
+/// # Ok(())
+/// # }
+/// ```
+pub trait HangarApi: MaybeSend + MaybeSync {
+	/// Gets a project by its slug, like a `ProjectRequest`.
+	fn project<'a>(&'a self, slug: &'a str) -> ApiFuture<'a, Project>;
+
+	/// Searches for projects, like sending the `ProjectsRequest`.
+	fn search<'a>(&'a self, request: &'a ProjectsRequest) -> ApiFuture<'a, ProjectsResponse>;
+
+	/// Gets a page of a project's versions, like sending the `VersionsRequest`.
+	fn versions<'a>(&'a self, request: &'a VersionsRequest) -> ApiFuture<'a, VersionsResponse>;
+
+	/// Gets a version of a project by name, like a `VersionRequest`.
+	fn version<'a>(&'a self, slug: &'a str, name: &'a str) -> ApiFuture<'a, Version>;
+}
+
+impl<B: HttpBackend> HangarApi for HangarClient<B> {
+	fn project<'a>(&'a self, slug: &'a str) -> ApiFuture<'a, Project> {
+		Box::pin(async move { self.send(&ProjectRequest::new(slug)).await })
+	}
+
+	fn search<'a>(&'a self, request: &'a ProjectsRequest) -> ApiFuture<'a, ProjectsResponse> {
+		Box::pin(self.send(request))
+	}
+
+	fn versions<'a>(&'a self, request: &'a VersionsRequest) -> ApiFuture<'a, VersionsResponse> {
+		Box::pin(self.send(request))
+	}
+
+	fn version<'a>(&'a self, slug: &'a str, name: &'a str) -> ApiFuture<'a, Version> {
+		Box::pin(async move { self.send(&VersionRequest::new(slug, name)).await })
+	}
+}
+
+/// An in-memory `HangarApi` serving the projects and versions it's given, for testing code which depends on the
+/// trait without any network or http responses to write.
+///
+/// Slugs are matched ignoring case like Hangar does, and anything which wasn't added is `Error::NotFound`, except
+/// searches and version listings, which come back empty.
+/// Searches only filter by the request's query, matching it against each project's name, slug and description,
+/// and version listings only by channel and platform. Both are paged like Hangar pages them.
+#[cfg(feature = "test-util")]
+#[derive(Debug, Clone, Default)]
+pub struct MockHangarApi {
+	projects: Vec<Project>,
+	/// The versions of each project, keyed by lowercase slug, in the order they were added
+	versions: std::collections::HashMap<String, Vec<Version>>,
+}
+
+#[cfg(feature = "test-util")]
+impl MockHangarApi {
+	/// Creates an api with no projects.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a project, replacing any project with the same slug.
+	pub fn with_project(mut self, project: Project) -> Self {
+		let slug = project.namespace.slug.clone();
+		self.projects
+			.retain(|other| !other.namespace.slug.eq_ignore_ascii_case(&slug));
+		self.projects.push(project);
+		self
+	}
+
+	/// Adds a version of the project with the given slug. Versions are listed newest first, so add them oldest
+	/// first.
+	pub fn with_version(mut self, slug: &str, version: Version) -> Self {
+		let versions = self.versions.entry(slug.to_lowercase()).or_default();
+		versions.retain(|other| other.name != version.name);
+		versions.push(version);
+		self
+	}
+
+	fn find_project(&self, slug: &str) -> Option<&Project> {
+		self.projects
+			.iter()
+			.find(|project| project.namespace.slug.eq_ignore_ascii_case(slug))
+	}
+
+	fn project_versions(&self, slug: &str) -> impl Iterator<Item = &Version> {
+		self.versions
+			.get(&slug.to_lowercase())
+			.into_iter()
+			.flat_map(|versions| versions.iter().rev())
+	}
+}
+
+/// Gets the page of items a pagination asks for, counting all of them like Hangar does.
+#[cfg(feature = "test-util")]
+fn page<T>(items: Vec<T>, pagination: &crate::object::Pagination) -> crate::api::Paginated<T> {
+	let count = items.len();
+	let offset = usize::try_from(pagination.offset).unwrap_or(0);
+	let limit = usize::try_from(pagination.limit).unwrap_or(0);
+	crate::api::Paginated {
+		pagination: crate::api::PaginationResponse {
+			limit: pagination.limit,
+			offset: pagination.offset,
+			count: i64::try_from(count).unwrap_or(i64::MAX),
+		},
+		result: items.into_iter().skip(offset).take(limit).collect(),
+	}
+}
+
+#[cfg(feature = "test-util")]
+impl HangarApi for MockHangarApi {
+	fn project<'a>(&'a self, slug: &'a str) -> ApiFuture<'a, Project> {
+		let project = self
+			.find_project(slug)
+			.cloned()
+			.ok_or(crate::Error::NotFound);
+		Box::pin(std::future::ready(project))
+	}
+
+	fn search<'a>(&'a self, request: &'a ProjectsRequest) -> ApiFuture<'a, ProjectsResponse> {
+		let query = request.query.as_deref().map(str::to_lowercase);
+		let matches = |project: &&Project| {
+			query.as_deref().is_none_or(|query| {
+				[
+					&*project.name,
+					&*project.namespace.slug,
+					project.description.as_str(),
+				]
+				.iter()
+				.any(|field| field.to_lowercase().contains(query))
+			})
+		};
+		let projects = self.projects.iter().filter(matches).cloned().collect();
+		Box::pin(std::future::ready(Ok(page(projects, &request.pagination))))
+	}
+
+	fn versions<'a>(&'a self, request: &'a VersionsRequest) -> ApiFuture<'a, VersionsResponse> {
+		let versions = self
+			.project_versions(&request.slug)
+			.filter(|version| {
+				request
+					.channel
+					.as_deref()
+					.is_none_or(|channel| version.channel.name.eq_ignore_ascii_case(channel))
+			})
+			.filter(|version| {
+				request
+					.platform
+					.is_none_or(|platform| version.download_for(platform).is_some())
+			})
+			.cloned()
+			.collect();
+		Box::pin(std::future::ready(Ok(page(versions, &request.pagination))))
+	}
+
+	fn version<'a>(&'a self, slug: &'a str, name: &'a str) -> ApiFuture<'a, Version> {
+		let version = self
+			.project_versions(slug)
+			.find(|version| version.name == name)
+			.cloned()
+			.ok_or(crate::Error::NotFound);
+		Box::pin(std::future::ready(version))
+	}
+}
@@ -0,0 +1,181 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use futures::{Stream, StreamExt};
+
+use crate::api::{LatestReleaseRequest, LatestVersionRequest, VersionsRequest};
+use crate::backend::HttpBackend;
+use crate::client::{sleep, HangarClient};
+use crate::error::{Error, Result};
+use crate::object::Pagination;
+
+/// Watches projects for new versions, remembering the latest version seen for each.
+///
+/// The latest version is looked up with the latest release endpoint, or the latest version endpoint if a channel is
+/// set. Projects which have never released are checked through the first page of their versions instead.
+#[derive(Debug, Clone)]
+pub struct UpdateWatcher {
+	/// The latest version seen of each project by slug, if any
+	seen: BTreeMap<String, Option<String>>,
+	channel: Option<String>,
+	concurrency: usize,
+}
+
+impl Default for UpdateWatcher {
+	fn default() -> Self {
+		Self {
+			seen: BTreeMap::new(),
+			channel: None,
+			concurrency: 4,
+		}
+	}
+}
+
+/// A project which published a new version since it was last checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateEvent {
+	/// The slug of the project
+	pub slug: String,
+	/// The name of the latest version seen before
+	pub previous: String,
+	/// The name of the new latest version
+	pub new_version: String,
+}
+
+/// A project which couldn't be checked.
+#[derive(Debug)]
+pub struct WatchFailure {
+	/// The slug of the project
+	pub slug: String,
+	/// Why checking the project failed
+	pub error: Error,
+}
+
+/// The result of checking every watched project once.
+#[derive(Debug, Default)]
+pub struct UpdateCheck {
+	/// The projects with new versions
+	pub events: Vec<UpdateEvent>,
+	/// The projects which couldn't be checked, which keep their previous state
+	pub failures: Vec<WatchFailure>,
+}
+
+impl UpdateWatcher {
+	/// Creates a watcher with no projects.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a project to watch, with the name of the latest version already seen if there is one.
+	/// Projects without a version seen don't give an event on their first check, which only records their latest
+	/// version.
+	pub fn project(mut self, slug: impl Into<String>, last_seen: Option<String>) -> Self {
+		self.seen.insert(slug.into(), last_seen);
+		self
+	}
+
+	/// Sets the channel to watch for new versions in, rather than the default release channel.
+	pub fn channel(mut self, channel: impl Into<String>) -> Self {
+		self.channel = Some(channel.into());
+		self
+	}
+
+	/// Sets how many projects are checked at once. Defaults to 4.
+	pub fn concurrency(mut self, concurrency: usize) -> Self {
+		self.concurrency = concurrency.max(1);
+		self
+	}
+
+	/// Gets the latest version seen of each project by slug, to persist between runs.
+	pub fn state(&self) -> &BTreeMap<String, Option<String>> {
+		&self.seen
+	}
+
+	/// Checks every project once, updating the latest versions seen.
+	pub async fn check<B: HttpBackend>(&mut self, client: &HangarClient<B>) -> UpdateCheck {
+		let slugs: Vec<String> = self.seen.keys().cloned().collect();
+		let watcher = &*self;
+		let results: Vec<_> = futures::stream::iter(slugs)
+			.map(|slug| async move {
+				let latest = watcher.latest(client, &slug).await;
+				(slug, latest)
+			})
+			.buffer_unordered(self.concurrency)
+			.collect()
+			.await;
+		let mut check = UpdateCheck::default();
+		for (slug, latest) in results {
+			let latest = match latest {
+				Ok(Some(latest)) => latest,
+				Ok(None) => continue,
+				Err(error) => {
+					check.failures.push(WatchFailure { slug, error });
+					continue;
+				}
+			};
+			let seen = self.seen.entry(slug.clone()).or_default();
+			match seen.replace(latest.clone()) {
+				Some(previous) if previous != latest => check.events.push(UpdateEvent {
+					slug,
+					previous,
+					new_version: latest,
+				}),
+				_ => {}
+			}
+		}
+		check.events.sort_by(|a, b| a.slug.cmp(&b.slug));
+		check
+	}
+
+	/// Checks every project repeatedly, waiting `interval` between checks, yielding the result of each check.
+	pub fn watch<B: HttpBackend>(
+		self,
+		client: &HangarClient<B>,
+		interval: Duration,
+	) -> impl Stream<Item = UpdateCheck> + '_ {
+		futures::stream::unfold((self, true), move |(mut watcher, first)| async move {
+			if !first {
+				sleep(interval).await;
+			}
+			let check = watcher.check(client).await;
+			Some((check, (watcher, false)))
+		})
+	}
+
+	/// Gets the name of the latest version of a project, or `None` if it has no versions.
+	async fn latest<B: HttpBackend>(
+		&self,
+		client: &HangarClient<B>,
+		slug: &str,
+	) -> Result<Option<String>> {
+		let latest = match &self.channel {
+			Some(channel) => {
+				let request = LatestVersionRequest::builder()
+					.slug(slug)
+					.channel(channel.clone())
+					.build();
+				client.send_uncached(&request).await
+			}
+			None => {
+				let request = LatestReleaseRequest::builder().slug(slug).build();
+				client.send_uncached(&request).await
+			}
+		};
+		match latest {
+			Ok(latest) => Ok(Some(latest)),
+			Err(err) if err.is_not_found() => {
+				let request = VersionsRequest::builder()
+					.slug(slug)
+					.pagination(Pagination::page(0, 1))
+					.channel_opt(self.channel.clone())
+					.build();
+				let versions = client.send_uncached(&request).await?;
+				Ok(versions
+					.result
+					.into_iter()
+					.next()
+					.map(|version| version.name))
+			}
+			Err(err) => Err(err),
+		}
+	}
+}
@@ -0,0 +1,1333 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use std::sync::Arc;
+
+use futures::{StreamExt, TryStreamExt};
+use hangar_api::api::{
+	CreateKeyRequest, HangarRequest, HttpMethod, KeysRequest, MainPageRequest, ProjectMatch,
+	ProjectRequest, ProjectStatsRequest, ProjectsRequest, VersionRequest, VersionsRequest,
+};
+use hangar_api::auth::{AuthToken, JwtToken};
+use hangar_api::backend::{HttpBackend, HttpRequest, HttpResponse, MockBackend};
+use hangar_api::client::{HangarClient, DEFAULT_USER_AGENT};
+use hangar_api::etag::{EtagEntry, EtagStore, MemoryEtagStore};
+use hangar_api::object::{
+	Category, ChannelFlags, NamedPermission, Pagination, ProjectsSort, SortDirection, VersionsSort,
+};
+use hangar_api::{decode_response, Error};
+use reqwest::Method;
+use time::OffsetDateTime;
+
+use common::{client, API};
+
+fn main_page(slug: &str) -> MainPageRequest {
+	MainPageRequest::builder().slug(slug).build()
+}
+
+#[tokio::test(start_paused = true)]
+async fn transient_errors_which_run_out_of_retries_are_wrapped() {
+	let url = format!("{API}/pages/main/Test");
+	let client = client(MockBackend::new().respond(&url, 503, "busy"));
+	let err = client.send(&main_page("Test")).await.unwrap_err();
+	match err {
+		Error::Retried { attempts, source } => {
+			assert_eq!(attempts, 3);
+			assert!(
+				matches!(*source, Error::Api { status: 503, .. }),
+				"{source:?}"
+			);
+		}
+		err => panic!("expected Retried, got {err:?}"),
+	}
+	assert_eq!(client.backend().requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn not_found_after_a_retry_is_not_wrapped() {
+	let url = format!("{API}/pages/main/Test");
+	let client = client(
+		MockBackend::new()
+			.respond(&url, 503, "busy")
+			.respond(&url, 404, ""),
+	);
+	let err = client.send(&main_page("Test")).await.unwrap_err();
+	assert!(matches!(err, Error::NotFound), "{err:?}");
+	assert!(err.is_not_found());
+}
+
+#[tokio::test(start_paused = true)]
+async fn exists_is_false_for_not_found_after_a_retry() {
+	let url = format!("{API}/projects/Test");
+	let client = client(
+		MockBackend::new()
+			.respond(&url, 503, "busy")
+			.respond(&url, 404, ""),
+	);
+	assert!(!client.exists(&ProjectRequest::new("Test")).await.unwrap());
+}
+
+#[tokio::test(start_paused = true)]
+async fn running_out_of_rate_limit_retries_gives_rate_limited() {
+	let url = format!("{API}/pages/main/Test");
+	let client =
+		client(MockBackend::new().respond_with_headers(&url, 429, &[("Retry-After", "2")], ""));
+	let err = client.send(&main_page("Test")).await.unwrap_err();
+	assert!(matches!(err, Error::RateLimited { .. }), "{err:?}");
+	// the first attempt and the default 3 retries
+	assert_eq!(client.backend().requests().len(), 4);
+}
+
+#[test]
+fn retried_errors_are_looked_through() {
+	let err = Error::Retried {
+		attempts: 2,
+		source: Box::new(Error::NotFound),
+	};
+	assert!(err.is_not_found());
+	assert!(matches!(err.root(), Error::NotFound));
+	assert!(!Error::Cancelled.is_not_found());
+}
+
+fn token(token: &str) -> AuthToken {
+	AuthToken {
+		token: JwtToken::new(token),
+		expires_at: OffsetDateTime::now_utc() + time::Duration::hours(1),
+	}
+}
+
+fn if_none_match(client: &HangarClient<MockBackend>) -> Vec<bool> {
+	client
+		.backend()
+		.requests()
+		.iter()
+		.map(|request| {
+			request
+				.headers
+				.iter()
+				.any(|(name, _)| name.eq_ignore_ascii_case("If-None-Match"))
+		})
+		.collect()
+}
+
+#[tokio::test]
+async fn etags_are_not_shared_between_users() {
+	let url = format!("{API}/pages/main/Test");
+	let client = common::builder()
+		.etag_store(MemoryEtagStore::new())
+		.build_with_backend(MockBackend::new().respond_with_headers(
+			&url,
+			200,
+			&[("ETag", "\"1\"")],
+			"page",
+		))
+		.unwrap();
+	client.set_auth_token(Some(token("alice")));
+	client.send(&main_page("Test")).await.unwrap();
+	client.set_auth_token(Some(token("bob")));
+	client.send(&main_page("Test")).await.unwrap();
+	client.set_auth_token(Some(token("alice")));
+	client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(if_none_match(&client), [false, false, true]);
+}
+
+/// An etag store shared between clients.
+#[derive(Clone)]
+struct SharedStore(Arc<MemoryEtagStore>);
+
+impl EtagStore for SharedStore {
+	fn get(&self, key: &str) -> Option<EtagEntry> {
+		self.0.get(key)
+	}
+
+	fn put(&self, key: &str, entry: EtagEntry) {
+		self.0.put(key, entry)
+	}
+}
+
+#[tokio::test]
+async fn etags_are_not_shared_between_languages() {
+	let url = format!("{API}/pages/main/Test");
+	let backend =
+		|| MockBackend::new().respond_with_headers(&url, 200, &[("ETag", "\"1\"")], "page");
+	let store = SharedStore(Arc::new(MemoryEtagStore::new()));
+	let english = common::builder()
+		.etag_store(store.clone())
+		.build_with_backend(backend())
+		.unwrap();
+	let german = common::builder()
+		.language("de")
+		.etag_store(store.clone())
+		.build_with_backend(backend())
+		.unwrap();
+	english.send(&main_page("Test")).await.unwrap();
+	german.send(&main_page("Test")).await.unwrap();
+	assert_eq!(if_none_match(&german), [false]);
+	assert_eq!(store.0.len(), 2);
+}
+
+#[tokio::test]
+async fn cached_responses_are_not_shared_between_users() {
+	let url = format!("{API}/pages/main/Test");
+	let client = common::builder()
+		.cache(std::time::Duration::from_secs(60), 16)
+		.build_with_backend(MockBackend::new().respond(&url, 200, "page"))
+		.unwrap();
+	client.set_auth_token(Some(token("alice")));
+	client.send(&main_page("Test")).await.unwrap();
+	client.set_auth_token(Some(token("bob")));
+	client.send(&main_page("Test")).await.unwrap();
+	client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(client.backend().requests().len(), 2);
+}
+
+fn user_agents(client: &HangarClient<MockBackend>) -> Vec<String> {
+	client
+		.backend()
+		.requests()
+		.iter()
+		.map(|request| {
+			request
+				.headers
+				.iter()
+				.find(|(name, _)| name.eq_ignore_ascii_case("User-Agent"))
+				.map(|(_, value)| value.clone())
+				.unwrap_or_default()
+		})
+		.collect()
+}
+
+#[tokio::test]
+async fn requests_identify_the_crate_by_default() {
+	let url = format!("{API}/pages/main/Test");
+	let client = client(MockBackend::new().respond(&url, 200, "page"));
+	client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(user_agents(&client), [DEFAULT_USER_AGENT]);
+	assert!(DEFAULT_USER_AGENT.starts_with("hangar-api-rs/"));
+}
+
+#[tokio::test]
+async fn the_user_agent_can_be_overridden() {
+	let url = format!("{API}/pages/main/Test");
+	let client = common::builder()
+		.user_agent("my-launcher/1.0")
+		.build_with_backend(
+			MockBackend::new()
+				.respond(
+					&format!("{API}/authenticate"),
+					200,
+					r#"{"token":"jwt","expiresIn":3600}"#,
+				)
+				.respond(&url, 200, "page"),
+		)
+		.unwrap();
+	client.authenticate("key").await.unwrap();
+	client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(user_agents(&client), ["my-launcher/1.0", "my-launcher/1.0"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn transient_failures_are_retried_until_one_succeeds() {
+	let url = format!("{API}/pages/main/Test");
+	let client = client(
+		MockBackend::new()
+			.respond(&url, 503, "busy")
+			.respond(&url, 500, "oops")
+			.respond(&url, 200, "page"),
+	);
+	let page = client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(page.contents, "page");
+	assert_eq!(client.backend().requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn client_errors_are_not_retried() {
+	let url = format!("{API}/pages/main/Test");
+	let client = client(MockBackend::new().respond(&url, 400, "bad"));
+	let err = client.send(&main_page("Test")).await.unwrap_err();
+	assert!(matches!(err, Error::Api { status: 400, .. }), "{err:?}");
+	assert_eq!(client.backend().requests().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limited_requests_wait_for_retry_after() {
+	let url = format!("{API}/pages/main/Test");
+	let client = client(
+		MockBackend::new()
+			.respond_with_headers(&url, 429, &[("Retry-After", "5")], "")
+			.respond(&url, 200, "page"),
+	);
+	let start = tokio::time::Instant::now();
+	client.send(&main_page("Test")).await.unwrap();
+	assert!(start.elapsed() >= std::time::Duration::from_secs(5));
+	assert_eq!(client.backend().requests().len(), 2);
+}
+
+/// A backend which takes a while to respond, so requests overlap.
+struct SlowBackend(MockBackend);
+
+impl HttpBackend for SlowBackend {
+	async fn execute(&self, request: HttpRequest) -> hangar_api::Result<HttpResponse> {
+		tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+		self.0.execute(request).await
+	}
+}
+
+fn coalescing_client(backend: MockBackend) -> HangarClient<SlowBackend> {
+	common::builder()
+		.coalesce_requests(true)
+		.build_with_backend(SlowBackend(backend))
+		.unwrap()
+}
+
+#[tokio::test(start_paused = true)]
+async fn identical_concurrent_requests_are_sent_once() {
+	let url = format!("{API}/pages/main/Test");
+	let client = coalescing_client(MockBackend::new().respond(&url, 200, "page"));
+	let request = main_page("Test");
+	let pages = futures::future::join_all((0..10).map(|_| client.send(&request))).await;
+	for page in pages {
+		assert_eq!(page.unwrap().contents, "page");
+	}
+	assert_eq!(client.backend().0.requests().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn coalesced_requests_all_get_the_error() {
+	let url = format!("{API}/pages/main/Test");
+	let client = coalescing_client(MockBackend::new().respond(&url, 404, ""));
+	let request = main_page("Test");
+	let results = futures::future::join_all((0..5).map(|_| client.send(&request))).await;
+	for result in results {
+		assert!(result.unwrap_err().is_not_found());
+	}
+	assert_eq!(client.backend().0.requests().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn different_requests_are_not_coalesced() {
+	let client = coalescing_client(
+		MockBackend::new()
+			.respond(&format!("{API}/pages/main/One"), 200, "one")
+			.respond(&format!("{API}/pages/main/Two"), 200, "two"),
+	);
+	let (one, two) = futures::future::join(
+		client.send(&main_page("One")),
+		client.send(&main_page("Two")),
+	)
+	.await;
+	assert_eq!(one.unwrap().contents, "one");
+	assert_eq!(two.unwrap().contents, "two");
+	assert_eq!(client.backend().0.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn exists_is_true_for_ok() {
+	let url = format!("{API}/projects/Test");
+	let client = client(MockBackend::new().respond(&url, 200, ""));
+	assert!(client.exists(&ProjectRequest::new("Test")).await.unwrap());
+	let requests = client.backend().requests();
+	assert_eq!(requests.len(), 1);
+	assert_eq!(requests[0].method, Method::HEAD);
+}
+
+#[tokio::test]
+async fn exists_is_false_for_not_found() {
+	let url = format!("{API}/projects/Test/versions/1.0");
+	let client = client(MockBackend::new().respond(&url, 404, ""));
+	assert!(!client
+		.exists(&VersionRequest::new("Test", "1.0"))
+		.await
+		.unwrap());
+}
+
+#[tokio::test]
+async fn exists_is_an_error_for_forbidden() {
+	let url = format!("{API}/projects/Test");
+	let client = client(MockBackend::new().respond(&url, 403, ""));
+	let err = client
+		.exists(&ProjectRequest::new("Test"))
+		.await
+		.unwrap_err();
+	assert!(matches!(err, Error::Unauthorized { .. }), "{err:?}");
+}
+
+#[tokio::test]
+async fn exists_falls_back_to_get_without_head_support() {
+	let url = format!("{API}/projects/Test");
+	let client = client(
+		MockBackend::new()
+			.respond(&url, 405, "")
+			.respond(&url, 200, "{}"),
+	);
+	assert!(client.exists(&ProjectRequest::new("Test")).await.unwrap());
+	let methods: Vec<_> = client
+		.backend()
+		.requests()
+		.into_iter()
+		.map(|request| request.method)
+		.collect();
+	assert_eq!(methods, [Method::HEAD, Method::GET]);
+}
+
+const MAINTENANCE_PAGE: &str = "<!DOCTYPE html>\n<html>\n  <head><title>Hangar | Maintenance</title></head>\n  <body>We'll be back soon</body>\n</html>";
+
+#[tokio::test(start_paused = true)]
+async fn html_pages_with_ok_statuses_are_unavailable() {
+	let url = format!("{API}/projects/Test");
+	let client = client(MockBackend::new().respond(&url, 200, MAINTENANCE_PAGE));
+	let err = client.send(&ProjectRequest::new("Test")).await.unwrap_err();
+	match err {
+		Error::Unavailable { status, snippet } => {
+			assert_eq!(status, 200);
+			assert!(
+				snippet.starts_with("<!DOCTYPE html> <html> <head>"),
+				"{snippet}"
+			);
+		}
+		err => panic!("expected Unavailable, got {err:?}"),
+	}
+	// a 200 isn't retried
+	assert_eq!(client.backend().requests().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn html_pages_with_unavailable_statuses_are_retried() {
+	let url = format!("{API}/projects/Test");
+	let client = client(MockBackend::new().respond_with_headers(
+		&url,
+		503,
+		&[("Content-Type", "text/html; charset=utf-8")],
+		"<p>Service unavailable</p>",
+	));
+	let err = client.send(&ProjectRequest::new("Test")).await.unwrap_err();
+	assert!(
+		matches!(err.root(), Error::Unavailable { status: 503, .. }),
+		"{err:?}"
+	);
+	assert!(matches!(err, Error::Retried { attempts: 3, .. }), "{err:?}");
+	assert_eq!(client.backend().requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn html_pages_are_retried_until_the_api_is_back() {
+	let url = format!("{API}/pages/main/Test");
+	let client = client(
+		MockBackend::new()
+			.respond(&url, 503, MAINTENANCE_PAGE)
+			.respond(&url, 200, "page"),
+	);
+	let page = client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(page.contents, "page");
+}
+
+fn cached_client(ttl_ms: u64, backend: MockBackend) -> HangarClient<MockBackend> {
+	common::builder()
+		.cache(std::time::Duration::from_millis(ttl_ms), 16)
+		.build_with_backend(backend)
+		.unwrap()
+}
+
+#[tokio::test]
+async fn cached_responses_skip_the_network_within_the_ttl() {
+	let url = format!("{API}/pages/main/Test");
+	let client = cached_client(60_000, MockBackend::new().respond(&url, 200, "page"));
+	client.send(&main_page("Test")).await.unwrap();
+	let page = client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(page.contents, "page");
+	assert_eq!(client.backend().requests().len(), 1);
+}
+
+#[tokio::test]
+async fn cached_responses_expire_after_the_ttl() {
+	let url = format!("{API}/pages/main/Test");
+	let client = cached_client(20, MockBackend::new().respond(&url, 200, "page"));
+	client.send(&main_page("Test")).await.unwrap();
+	std::thread::sleep(std::time::Duration::from_millis(50));
+	client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(client.backend().requests().len(), 2);
+}
+
+#[tokio::test]
+async fn invalidating_a_project_busts_its_cached_responses() {
+	let page = format!("{API}/pages/main/Test");
+	let other = format!("{API}/pages/main/Other");
+	let client = cached_client(
+		60_000,
+		MockBackend::new()
+			.respond(&page, 200, "old")
+			.respond(&page, 200, "new")
+			.respond(&other, 200, "other"),
+	);
+	client.send(&main_page("Test")).await.unwrap();
+	client.send(&main_page("Other")).await.unwrap();
+	client.invalidate_project("test");
+	assert_eq!(
+		client.send(&main_page("Test")).await.unwrap().contents,
+		"new"
+	);
+	client.send(&main_page("Other")).await.unwrap();
+	assert_eq!(client.backend().requests().len(), 3);
+}
+
+#[tokio::test]
+async fn searches_matching_nothing_are_empty_rather_than_not_found() {
+	let client = client(MockBackend::new().respond(
+		&format!("{API}/projects"),
+		200,
+		r#"{"pagination":{"limit":25,"offset":0,"count":0},"result":[]}"#,
+	));
+	let request = ProjectsRequest::builder()
+		.query("nothing matches this".to_string())
+		.pagination(Pagination::default())
+		.build();
+	let projects = client.send(&request).await.unwrap();
+	assert!(projects.is_empty());
+	assert!(!projects.has_matches());
+}
+
+#[tokio::test]
+async fn unknown_slugs_are_not_found() {
+	let client = client(MockBackend::new().respond(
+		&format!("{API}/projects/Missing"),
+		404,
+		r#"{"message":"Not Found","messageArgs":[],"isHangarApiException":true,"httpError":{"statusCode":404,"statusPhrase":"Not Found"}}"#,
+	));
+	let err = client
+		.send(&ProjectRequest::new("Missing"))
+		.await
+		.unwrap_err();
+	assert!(matches!(err, Error::NotFound), "{err:?}");
+}
+
+fn checking_client(backend: MockBackend) -> HangarClient<MockBackend> {
+	common::builder()
+		.check_permissions(true)
+		.build_with_backend(backend)
+		.unwrap()
+}
+
+fn paths(client: &HangarClient<MockBackend>) -> Vec<String> {
+	client
+		.backend()
+		.requests()
+		.into_iter()
+		.map(|request| request.url.trim_start_matches(API).to_string())
+		.collect()
+}
+
+const GLOBAL_PERMISSIONS: &str = r#"{
+	"type": "global",
+	"permissionBinString": "11",
+	"permissions": ["view_public_info", "edit_own_user_settings"]
+}"#;
+
+#[tokio::test]
+async fn anonymous_requests_needing_a_permission_fail_without_being_sent() {
+	let client = checking_client(MockBackend::new());
+	let err = client.send(&KeysRequest::default()).await.unwrap_err();
+	assert!(
+		matches!(
+			&err,
+			Error::MissingPermission {
+				permission: NamedPermission::EditApiKeys
+			}
+		),
+		"{err:?}"
+	);
+	assert!(client.backend().requests().is_empty());
+}
+
+#[tokio::test]
+async fn anonymous_requests_for_public_info_are_sent_when_checking_permissions() {
+	let url = format!("{API}/pages/main/Test");
+	let client = checking_client(MockBackend::new().respond(&url, 200, "page"));
+	client.send(&main_page("Test")).await.unwrap();
+	assert_eq!(paths(&client), ["/pages/main/Test"]);
+}
+
+#[tokio::test]
+async fn permissions_are_not_checked_by_default() {
+	let url = format!("{API}/keys");
+	let client = client(MockBackend::new().respond(&url, 401, ""));
+	let err = client.send(&KeysRequest::default()).await.unwrap_err();
+	assert!(!matches!(err, Error::MissingPermission { .. }), "{err:?}");
+	assert_eq!(paths(&client), ["/keys"]);
+}
+
+#[tokio::test]
+async fn requests_are_checked_against_the_users_permissions() {
+	let client = checking_client(MockBackend::new().respond(
+		&format!("{API}/permissions"),
+		200,
+		GLOBAL_PERMISSIONS,
+	));
+	client.set_auth_token(Some(token("alice")));
+	let err = client.send(&KeysRequest::default()).await.unwrap_err();
+	assert!(
+		matches!(
+			&err,
+			Error::MissingPermission {
+				permission: NamedPermission::EditApiKeys
+			}
+		),
+		"{err:?}"
+	);
+	assert_eq!(paths(&client), ["/permissions"]);
+}
+
+#[tokio::test]
+async fn project_permissions_are_checked_in_the_project() {
+	let permissions = include_str!("fixtures/permissions.json");
+	let stats = format!("{API}/projects/Maintenance/stats");
+	let client = checking_client(
+		MockBackend::new()
+			.respond(&format!("{API}/permissions"), 200, GLOBAL_PERMISSIONS)
+			.respond(
+				&format!("{API}/permissions?project=Maintenance"),
+				200,
+				permissions,
+			)
+			.respond(&stats, 200, "{}"),
+	);
+	client.set_auth_token(Some(token("alice")));
+	let day = time::Date::from_calendar_date(2024, time::Month::June, 1).unwrap();
+	let request = ProjectStatsRequest::new("Maintenance", day, day).unwrap();
+	client.send(&request).await.unwrap();
+	let requests = client.backend().requests();
+	assert_eq!(
+		requests[0].query,
+		[("project".to_string(), "Maintenance".to_string())]
+	);
+	assert_eq!(
+		paths(&client),
+		["/permissions", "/projects/Maintenance/stats"]
+	);
+}
+
+#[tokio::test]
+async fn permissions_are_fetched_once_per_token() {
+	let client = checking_client(
+		MockBackend::new()
+			.respond(
+				&format!("{API}/permissions"),
+				200,
+				include_str!("fixtures/permissions.json"),
+			)
+			.respond(
+				&format!("{API}/keys"),
+				200,
+				include_str!("fixtures/api_keys.json"),
+			),
+	);
+	client.set_auth_token(Some(token("alice")));
+	client.send(&KeysRequest::default()).await.unwrap();
+	client.send(&KeysRequest::default()).await.unwrap();
+	client.set_auth_token(Some(token("bob")));
+	client.send(&KeysRequest::default()).await.unwrap();
+	assert_eq!(
+		paths(&client),
+		["/permissions", "/keys", "/keys", "/permissions", "/keys"]
+	);
+}
+
+/// Gets the url a request is sent to, including its query.
+fn url_with_query(request: &impl HangarRequest) -> String {
+	reqwest::Url::parse_with_params(&request.url(), request.query().unwrap())
+		.unwrap()
+		.to_string()
+}
+
+fn oldest_first(offset: i64) -> VersionsRequest {
+	VersionsRequest::builder()
+		.slug("Maintenance")
+		.pagination(Pagination { limit: 2, offset })
+		.sort(VersionsSort::CreatedAt)
+		.direction(SortDirection::Ascending)
+		.build()
+}
+
+fn versions_page(offset: i64, names: &[&str]) -> String {
+	let version: serde_json::Value =
+		serde_json::from_str(include_str!("fixtures/version_internal.json")).unwrap();
+	let result: Vec<serde_json::Value> = names
+		.iter()
+		.map(|name| {
+			let mut version = version.clone();
+			version["name"] = serde_json::json!(name);
+			version
+		})
+		.collect();
+	serde_json::json!({
+		"pagination": {"limit": 2, "offset": offset, "count": 3},
+		"result": result,
+	})
+	.to_string()
+}
+
+#[tokio::test]
+async fn versions_stream_oldest_first_across_pages() {
+	let client = client(
+		MockBackend::new()
+			.respond(
+				&url_with_query(&oldest_first(0)),
+				200,
+				versions_page(0, &["1.0.0", "1.1.0"]),
+			)
+			.respond(
+				&url_with_query(&oldest_first(2)),
+				200,
+				versions_page(2, &["2.0.0"]),
+			),
+	);
+	let names: Vec<String> = client
+		.paginate(oldest_first(0))
+		.map_ok(|version| version.name)
+		.try_collect()
+		.await
+		.unwrap();
+	assert_eq!(names, ["1.0.0", "1.1.0", "2.0.0"]);
+	for request in client.backend().requests() {
+		assert!(
+			request
+				.query
+				.contains(&("sort".to_string(), "createdAt".to_string())),
+			"{:?}",
+			request.query
+		);
+	}
+}
+
+#[tokio::test]
+async fn rejected_sorts_are_errors() {
+	let client = client(MockBackend::new().respond(
+		&url_with_query(&oldest_first(0)),
+		400,
+		include_str!("fixtures/errors/bad_request.json"),
+	));
+	let err = client.send(&oldest_first(0)).await.unwrap_err();
+	assert!(matches!(err, Error::Api { status: 400, .. }), "{err:?}");
+}
+
+#[tokio::test]
+#[ignore = "sends requests to hangar.papermc.io"]
+async fn live_versions_stream_oldest_first() {
+	let client = HangarClient::builder().build().unwrap();
+	let request = VersionsRequest::builder()
+		.slug("Maintenance")
+		.pagination(Pagination::page(0, 5))
+		.sort(VersionsSort::CreatedAt)
+		.direction(SortDirection::Ascending)
+		.build();
+	let versions: Vec<_> = client
+		.paginate(request)
+		.take(12)
+		.try_collect()
+		.await
+		.unwrap();
+	assert!(!versions.is_empty());
+	assert!(versions
+		.windows(2)
+		.all(|pair| pair[0].created_at <= pair[1].created_at));
+}
+
+fn project_json(id: i64, slug: &str) -> serde_json::Value {
+	let mut project: serde_json::Value =
+		serde_json::from_str(include_str!("fixtures/project.json")).unwrap();
+	project["id"] = serde_json::json!(id);
+	project["name"] = serde_json::json!(slug);
+	project["namespace"]["slug"] = serde_json::json!(slug);
+	project
+}
+
+fn by_downloads(offset: i64) -> ProjectsRequest {
+	ProjectsRequest::builder()
+		.pagination(Pagination { limit: 2, offset })
+		.build()
+}
+
+fn projects_page(offset: i64, projects: &[(i64, &str)]) -> String {
+	let result: Vec<serde_json::Value> = projects
+		.iter()
+		.map(|(id, slug)| project_json(*id, slug))
+		.collect();
+	serde_json::json!({
+		"pagination": {"limit": 2, "offset": offset, "count": 4},
+		"result": result,
+	})
+	.to_string()
+}
+
+async fn paginated_slugs(first: &[(i64, &str)], second: &[(i64, &str)]) -> Vec<String> {
+	let client = client(
+		MockBackend::new()
+			.respond(
+				&url_with_query(&by_downloads(0)),
+				200,
+				projects_page(0, first),
+			)
+			.respond(
+				&url_with_query(&by_downloads(2)),
+				200,
+				projects_page(2, second),
+			),
+	);
+	client
+		.paginate(by_downloads(0))
+		.map_ok(|project| project.namespace.slug.into_string())
+		.try_collect()
+		.await
+		.unwrap()
+}
+
+#[tokio::test]
+async fn projects_moving_to_a_later_page_are_yielded_once() {
+	let slugs = paginated_slugs(
+		&[(1, "Maintenance"), (2, "ViaVersion")],
+		&[(2, "ViaVersion"), (3, "packetevents")],
+	)
+	.await;
+	assert_eq!(slugs, ["Maintenance", "ViaVersion", "packetevents"]);
+}
+
+#[tokio::test]
+async fn projects_renamed_between_pages_are_yielded_once() {
+	let slugs = paginated_slugs(
+		&[(1, "Maintenance"), (2, "ViaVersion")],
+		&[(2, "ViaVersion-Renamed"), (3, "packetevents")],
+	)
+	.await;
+	assert_eq!(slugs, ["Maintenance", "ViaVersion", "packetevents"]);
+}
+
+#[tokio::test]
+async fn refreshing_a_renamed_project_reports_its_new_slug() {
+	let client = client(MockBackend::new().respond(
+		&format!("{API}/projects/OldMaintenance"),
+		200,
+		project_json(8, "Maintenance").to_string(),
+	));
+	let refreshed = client.refresh_project("OldMaintenance").await.unwrap();
+	assert!(refreshed.is_renamed());
+	assert_eq!(refreshed.previous_slug.as_deref(), Some("OldMaintenance"));
+	assert_eq!(refreshed.project.namespace.slug.as_str(), "Maintenance");
+}
+
+#[tokio::test]
+async fn refreshing_a_project_with_the_same_slug_is_not_a_rename() {
+	let client = client(MockBackend::new().respond(
+		&format!("{API}/projects/maintenance"),
+		200,
+		project_json(8, "Maintenance").to_string(),
+	));
+	let refreshed = client.refresh_project("maintenance").await.unwrap();
+	assert!(!refreshed.is_renamed());
+}
+
+#[tokio::test]
+async fn refreshing_a_deleted_project_is_gone() {
+	let client = client(
+		MockBackend::new()
+			.respond(
+				&format!("{API}/projects/Maintenance"),
+				200,
+				project_json(8, "Maintenance").to_string(),
+			)
+			.respond(
+				&format!("{API}/projects/Deleted"),
+				404,
+				include_str!("fixtures/errors/not_found.json"),
+			),
+	);
+	let results = client.refresh_projects(["Deleted", "Maintenance"]).await;
+	match &results[0] {
+		Err(Error::Gone { slug }) => assert_eq!(slug, "Deleted"),
+		result => panic!("expected Gone, got {result:?}"),
+	}
+	assert!(results[1].is_ok(), "{:?}", results[1]);
+}
+
+fn user_projects(member: Option<&str>, owner: Option<&str>) -> ProjectsRequest {
+	ProjectsRequest::builder()
+		.pagination(Pagination::page(0, Pagination::MAX_LIMIT))
+		.sort(ProjectsSort::Downloads)
+		.member(member.map(str::to_string))
+		.owner(owner.map(str::to_string))
+		.build()
+}
+
+fn user_projects_client() -> HangarClient<MockBackend> {
+	client(
+		MockBackend::new()
+			.respond(
+				&url_with_query(&user_projects(Some("kennytv"), None)),
+				200,
+				include_str!("fixtures/projects_member.json"),
+			)
+			.respond(
+				&url_with_query(&user_projects(None, Some("kennytv"))),
+				200,
+				include_str!("fixtures/projects_owner.json"),
+			),
+	)
+}
+
+#[tokio::test]
+async fn projects_for_a_user_include_organization_projects() {
+	let client = user_projects_client();
+	let projects = client
+		.projects_for_user("kennytv", ProjectsSort::Downloads)
+		.await
+		.unwrap();
+	let keys: Vec<String> = projects
+		.iter()
+		.map(|project| project.namespace.key())
+		.collect();
+	assert_eq!(
+		keys,
+		[
+			"ViaVersion/ViaVersion",
+			"kennytv/Maintenance",
+			"kennytv/Sneaky"
+		]
+	);
+	let mut queries: Vec<Vec<(String, String)>> = client
+		.backend()
+		.requests()
+		.into_iter()
+		.map(|request| {
+			request
+				.query
+				.into_iter()
+				.filter(|(key, _)| key == "member" || key == "owner")
+				.collect()
+		})
+		.collect();
+	queries.sort();
+	assert_eq!(
+		queries,
+		[
+			vec![("member".to_string(), "kennytv".to_string())],
+			vec![("owner".to_string(), "kennytv".to_string())],
+		]
+	);
+}
+
+#[tokio::test]
+async fn projects_for_a_user_keep_the_requested_sort() {
+	let client = user_projects_client();
+	let projects = client
+		.projects_for_user("kennytv", ProjectsSort::Downloads)
+		.await
+		.unwrap();
+	let downloads: Vec<i64> = projects
+		.iter()
+		.map(|project| project.stats.downloads)
+		.collect();
+	assert_eq!(downloads, [482_913, 21_377, 3_105]);
+}
+
+fn method(method: HttpMethod) -> Method {
+	match method {
+		HttpMethod::Get => Method::GET,
+		HttpMethod::Post => Method::POST,
+		method => panic!("unexpected method {method}"),
+	}
+}
+
+/// Sends a request with the client and checks that building it with `to_parts` and parsing the response with
+/// `decode_response` gives the same request and result.
+async fn assert_parts_match_the_client<R>(
+	request: R,
+	auth: Option<AuthToken>,
+	status: u16,
+	body: &[u8],
+) where
+	R: HangarRequest,
+	R::Response: std::fmt::Debug,
+{
+	let mut parts = request.to_parts(API).unwrap();
+	let client = client(MockBackend::new().respond(&parts.full_url(), status, body));
+	if let Some(token) = &auth {
+		client.set_auth_token(Some(token.clone()));
+		parts.authorize(token);
+	}
+	let sent = client.send(&request).await;
+	let requests = client.backend().requests();
+	let [http] = requests.as_slice() else {
+		panic!("expected one request, got {requests:?}");
+	};
+	assert_eq!(http.method, method(parts.method));
+	assert_eq!(http.url, parts.url);
+	assert_eq!(http.query, parts.query);
+	assert_eq!(http.body, parts.body);
+	for header in &parts.headers {
+		assert!(
+			http.headers
+				.iter()
+				.any(|(name, value)| name.eq_ignore_ascii_case(&header.0) && *value == header.1),
+			"{header:?} wasn't sent"
+		);
+	}
+	let decoded = decode_response::<R>(status, body);
+	assert_eq!(format!("{decoded:?}"), format!("{sent:?}"));
+}
+
+#[tokio::test]
+async fn parts_match_the_client_for_projects() {
+	assert_parts_match_the_client(
+		ProjectRequest::new("Maintenance"),
+		None,
+		200,
+		include_bytes!("fixtures/project.json"),
+	)
+	.await;
+}
+
+#[tokio::test]
+async fn parts_match_the_client_for_searches() {
+	let request = ProjectsRequest::builder()
+		.query("maintenance & motd".to_string())
+		.categories(vec![Category::AdminTools, Category::Chat])
+		.pagination(Pagination::page(1, 2))
+		.build();
+	assert_parts_match_the_client(request, None, 200, include_bytes!("fixtures/projects.json"))
+		.await;
+}
+
+#[tokio::test]
+async fn parts_match_the_client_for_translated_pages() {
+	assert_parts_match_the_client(
+		main_page("Maintenance").with_language("de"),
+		None,
+		200,
+		include_bytes!("fixtures/page.md"),
+	)
+	.await;
+}
+
+#[tokio::test]
+async fn parts_match_the_client_for_authorized_requests() {
+	assert_parts_match_the_client(
+		KeysRequest::default(),
+		Some(token("alice")),
+		200,
+		include_bytes!("fixtures/api_keys.json"),
+	)
+	.await;
+}
+
+#[tokio::test]
+async fn parts_match_the_client_for_json_bodies() {
+	let request = CreateKeyRequest::builder()
+		.name("ci")
+		.permissions(vec![NamedPermission::CreateVersion])
+		.build();
+	assert_parts_match_the_client(
+		request,
+		Some(token("alice")),
+		201,
+		b"9b3e5c1a-0000-4000-8000-000000000000.secret",
+	)
+	.await;
+}
+
+#[tokio::test]
+async fn parts_match_the_client_for_errors() {
+	for (status, body) in [
+		(404, &include_bytes!("fixtures/errors/not_found.json")[..]),
+		(403, include_bytes!("fixtures/errors/forbidden.json")),
+		(400, include_bytes!("fixtures/errors/bad_request.json")),
+	] {
+		assert_parts_match_the_client(ProjectRequest::new("Maintenance"), None, status, body).await;
+	}
+}
+
+/// A client which finds the given projects, by display name and slug, searching for `name`.
+fn searching_client(name: &str, projects: &[(&str, &str)]) -> HangarClient<MockBackend> {
+	let result: Vec<serde_json::Value> = projects
+		.iter()
+		.enumerate()
+		.map(|(id, (name, slug))| {
+			let mut project = project_json(id as i64 + 1, slug);
+			project["name"] = serde_json::json!(name);
+			project
+		})
+		.collect();
+	let page = serde_json::json!({
+		"pagination": {"limit": 10, "offset": 0, "count": result.len()},
+		"result": result,
+	});
+	client(MockBackend::new().respond(
+		&url_with_query(&ProjectsRequest::named(name)),
+		200,
+		page.to_string(),
+	))
+}
+
+#[tokio::test]
+async fn finding_a_project_by_slug_gives_the_exact_match() {
+	let client = searching_client(
+		"cool plugin",
+		&[
+			("Cool Plugin!", "CoolPlugin"),
+			("CoolPlugins", "CoolPlugins"),
+		],
+	);
+	match client.find_project("cool plugin").await.unwrap() {
+		ProjectMatch::Exact(project) => assert_eq!(project.namespace.slug, "CoolPlugin"),
+		found => panic!("expected Exact, got {found:?}"),
+	}
+	let project = client.find_project_exact("cool plugin").await.unwrap();
+	assert_eq!(project.unwrap().namespace.slug, "CoolPlugin");
+	let query = &client.backend().requests()[0].query;
+	assert!(query.contains(&("prioritizeExactMatch".to_string(), "true".to_string())));
+	assert!(query.contains(&("query".to_string(), "cool plugin".to_string())));
+}
+
+#[tokio::test]
+async fn finding_a_near_miss_gives_the_candidates() {
+	let client = searching_client(
+		"coolplugin",
+		&[
+			("CoolPlugins", "CoolPlugins"),
+			("Cool Plugin 2", "CoolPlugin2"),
+		],
+	);
+	match client.find_project("coolplugin").await.unwrap() {
+		ProjectMatch::Ambiguous(projects) => {
+			let slugs: Vec<&str> = projects
+				.iter()
+				.map(|project| project.namespace.slug.as_str())
+				.collect();
+			assert_eq!(slugs, ["CoolPlugins", "CoolPlugin2"]);
+		}
+		found => panic!("expected Ambiguous, got {found:?}"),
+	}
+	assert!(client
+		.find_project_exact("coolplugin")
+		.await
+		.unwrap()
+		.is_none());
+}
+
+#[tokio::test]
+async fn finding_nothing_gives_not_found() {
+	let client = searching_client("NotOnHangar", &[]);
+	assert!(matches!(
+		client.find_project("NotOnHangar").await.unwrap(),
+		ProjectMatch::NotFound
+	));
+	assert!(client
+		.find_project_exact("NotOnHangar")
+		.await
+		.unwrap()
+		.is_none());
+}
+
+#[tokio::test]
+async fn secrets_are_redacted_from_the_clients_debug_output() {
+	let api_key = "9b3e5c1a-0000-4000-8000-000000000000.hangar-secret";
+	let jwt = "eyJhbGciOiJIUzI1NiJ9.e30.c2lnbmF0dXJl";
+	let client = common::builder()
+		.api_key(api_key)
+		.build_with_backend(MockBackend::new())
+		.unwrap();
+	client.set_auth_token(Some(token(jwt)));
+	let debug = format!("{client:?}");
+	assert!(debug.contains("ApiKey(****)"), "{debug}");
+	assert!(!debug.contains(api_key), "{debug}");
+	assert!(!debug.contains(jwt), "{debug}");
+}
+
+/// A channel as Hangar sends it with a version: its name, creation date, color and flags.
+type ChannelJson<'a> = (&'a str, &'a str, &'a str, &'a [&'a str]);
+
+fn channel_versions_page(offset: i64, channels: &[ChannelJson]) -> String {
+	let version: serde_json::Value =
+		serde_json::from_str(include_str!("fixtures/version_internal.json")).unwrap();
+	let result: Vec<serde_json::Value> = channels
+		.iter()
+		.enumerate()
+		.map(|(i, (name, created_at, color, flags))| {
+			let mut version = version.clone();
+			version["name"] = serde_json::json!(format!("{offset}.{i}"));
+			version["channel"] = serde_json::json!({
+				"createdAt": created_at,
+				"name": name,
+				"description": null,
+				"color": color,
+				"flags": flags,
+			});
+			version
+		})
+		.collect();
+	serde_json::json!({
+		"pagination": {"limit": Pagination::MAX_LIMIT, "offset": offset, "count": Pagination::MAX_LIMIT + 2},
+		"result": result,
+	})
+	.to_string()
+}
+
+fn channel_versions(offset: i64) -> VersionsRequest {
+	VersionsRequest::builder()
+		.slug("Maintenance")
+		.pagination(Pagination {
+			limit: Pagination::MAX_LIMIT,
+			offset,
+		})
+		.include_hidden_channels(true)
+		.build()
+}
+
+#[tokio::test]
+async fn project_channels_are_deduplicated_across_pages() {
+	const RELEASE: ChannelJson = (
+		"Release",
+		"2022-12-21T16:58:43.141217Z",
+		"#009600",
+		&["PINNED", "SENDS_NOTIFICATIONS"],
+	);
+	const SNAPSHOT: ChannelJson = ("Snapshot", "2023-03-02T10:11:12Z", "#FFC800", &["UNSTABLE"]);
+	const INTERNAL: ChannelJson = (
+		"Internal",
+		"2024-01-15T08:00:00Z",
+		"#AAAAAA",
+		&["HIDE_BY_DEFAULT", "FROZEN"],
+	);
+	let client = client(
+		MockBackend::new()
+			.respond(
+				&url_with_query(&channel_versions(0)),
+				200,
+				channel_versions_page(0, &[SNAPSHOT, RELEASE, SNAPSHOT]),
+			)
+			.respond(
+				&url_with_query(&channel_versions(Pagination::MAX_LIMIT)),
+				200,
+				channel_versions_page(Pagination::MAX_LIMIT, &[INTERNAL, RELEASE]),
+			),
+	);
+	let channels = client.project_channels("Maintenance").await.unwrap();
+	let found: Vec<(&str, String, Vec<ChannelFlags>)> = channels
+		.iter()
+		.map(|channel| {
+			let flags = ChannelFlags::ALL
+				.into_iter()
+				.filter(|flag| channel.flags.contains(*flag))
+				.collect();
+			(channel.name.as_str(), channel.color.to_string(), flags)
+		})
+		.collect();
+	assert_eq!(
+		found,
+		[
+			(
+				"Release",
+				"#009600".to_string(),
+				vec![ChannelFlags::Pinned, ChannelFlags::SendsNotifications]
+			),
+			(
+				"Snapshot",
+				"#FFC800".to_string(),
+				vec![ChannelFlags::Unstable]
+			),
+			(
+				"Internal",
+				"#AAAAAA".to_string(),
+				vec![ChannelFlags::Frozen, ChannelFlags::HideByDefault]
+			),
+		]
+	);
+	assert_eq!(channels[1].created_at.year(), 2023);
+	let requests = client.backend().requests();
+	assert_eq!(requests.len(), 2);
+	for request in requests {
+		assert!(request
+			.query
+			.contains(&("includeHiddenChannels".to_string(), "true".to_string())));
+	}
+}
+
+fn with_value(query: &[(String, String)], name: &str) -> Vec<String> {
+	query
+		.iter()
+		.filter(|(key, _)| key == name)
+		.map(|(_, value)| value.clone())
+		.collect()
+}
+
+#[tokio::test]
+async fn extra_params_are_sent_after_the_typed_ones() {
+	let client = client(MockBackend::new().respond(
+		&format!("{API}/projects"),
+		200,
+		projects_page(0, &[(1, "WorldEdit")]),
+	));
+	let request = ProjectsRequest::builder()
+		.query("worldedit".to_string())
+		.pagination(Pagination::page(0, 10))
+		.build()
+		.with_extra_params([
+			("member", "EngineHub"),
+			("query", "ignored"),
+			("limit", "25"),
+			("tag", "a"),
+			("tag", "b"),
+		]);
+	let projects = client.send(&request).await.unwrap();
+	assert_eq!(projects.result[0].namespace.slug, "WorldEdit");
+	let query = &client.backend().requests()[0].query;
+	assert_eq!(with_value(query, "member"), ["EngineHub"]);
+	assert_eq!(with_value(query, "tag"), ["a", "b"]);
+	// typed parameters aren't overridden
+	assert_eq!(with_value(query, "query"), ["worldedit"]);
+	assert_eq!(with_value(query, "limit"), ["10"]);
+	let extra = query.iter().position(|(key, _)| key == "member").unwrap();
+	assert!(query[..extra].iter().any(|(key, _)| key == "query"));
+}
+
+#[tokio::test]
+async fn extra_params_are_sent_for_every_page() {
+	let client = client(
+		MockBackend::new()
+			.respond(
+				&url_with_query(&by_downloads(0).with_extra_params([("member", "EngineHub")])),
+				200,
+				projects_page(0, &[(1, "WorldEdit"), (2, "WorldGuard")]),
+			)
+			.respond(
+				&url_with_query(&by_downloads(2).with_extra_params([("member", "EngineHub")])),
+				200,
+				projects_page(2, &[(3, "CraftBook"), (4, "CommandHelper")]),
+			),
+	);
+	let slugs: Vec<String> = client
+		.paginate(by_downloads(0).with_extra_params([("member", "EngineHub")]))
+		.map_ok(|project| project.namespace.slug.to_string())
+		.try_collect()
+		.await
+		.unwrap();
+	assert_eq!(
+		slugs,
+		["WorldEdit", "WorldGuard", "CraftBook", "CommandHelper"]
+	);
+	for request in client.backend().requests() {
+		assert_eq!(with_value(&request.query, "member"), ["EngineHub"]);
+	}
+}
+
+#[tokio::test]
+async fn requests_whose_query_cant_be_serialized_are_not_sent() {
+	let client = client(MockBackend::new());
+	let date = time::Date::MIN;
+	let request = ProjectStatsRequest::new("Maintenance", date, date).unwrap();
+	match client.send(&request).await.unwrap_err() {
+		Error::InvalidRequest(message) => {
+			assert!(message.contains("couldn't be serialized"), "{message}")
+		}
+		err => panic!("expected InvalidRequest, got {err:?}"),
+	}
+	assert!(client.backend().requests().is_empty());
+}
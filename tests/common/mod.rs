@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use hangar_api::backend::MockBackend;
+use hangar_api::client::{HangarClient, HangarClientBuilder};
+use hangar_api::retry::RetryPolicy;
+
+/// The url requests are sent to by default.
+pub const API: &str = "https://hangar.papermc.io/api/v1";
+
+/// Gets a client builder which retries without waiting, so tests don't sleep.
+pub fn builder() -> HangarClientBuilder {
+	HangarClient::builder().retry(
+		RetryPolicy::builder()
+			.base_delay(Duration::ZERO)
+			.jitter(false)
+			.build(),
+	)
+}
+
+/// Builds a client serving the mock's responses.
+pub fn client(backend: MockBackend) -> HangarClient<MockBackend> {
+	builder()
+		.build_with_backend(backend)
+		.expect("valid client config")
+}
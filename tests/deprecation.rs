@@ -0,0 +1,101 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use hangar_api::api::MainPageRequest;
+use hangar_api::backend::MockBackend;
+use hangar_api::deprecation::Deprecation;
+
+use common::{builder, API};
+
+/// Collects everything logged, to count the warnings.
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Logs {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.0.lock().unwrap().extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
+const DEPRECATED: &[(&str, &str)] = &[
+	("Deprecation", "@1688169599"),
+	("Sunset", "Wed, 01 Jan 2070 00:00:00 GMT"),
+	("Warning", "299 - \"Deprecated API\""),
+];
+
+#[tokio::test]
+async fn deprecated_endpoints_are_warned_about_once_and_exposed() {
+	let logs = Logs::default();
+	let writer = logs.clone();
+	let subscriber = tracing_subscriber::fmt()
+		.with_writer(move || writer.clone())
+		.with_ansi(false)
+		.finish();
+	let _guard = tracing::subscriber::set_default(subscriber);
+
+	let backend = MockBackend::new()
+		.respond_with_headers(
+			&format!("{API}/pages/main/First"),
+			200,
+			DEPRECATED,
+			"# First",
+		)
+		.respond_with_headers(
+			&format!("{API}/pages/main/Second"),
+			200,
+			DEPRECATED,
+			"# Second",
+		);
+	let seen = Arc::new(Mutex::new(Vec::new()));
+	let hook_seen = seen.clone();
+	let client = builder()
+		.on_deprecation(move |url: &str, deprecation: &Deprecation| {
+			hook_seen
+				.lock()
+				.unwrap()
+				.push((url.to_string(), deprecation.clone()));
+		})
+		.build_with_backend(backend)
+		.unwrap();
+
+	let first = client
+		.send_with_meta(&MainPageRequest::builder().slug("First").build())
+		.await
+		.unwrap();
+	for _ in 0..2 {
+		client
+			.send(&MainPageRequest::builder().slug("Second").build())
+			.await
+			.unwrap();
+	}
+
+	let deprecation = first.deprecation().unwrap();
+	assert!(deprecation.deprecated);
+	assert_eq!(deprecation.since.unwrap().unix_timestamp(), 1688169599);
+	assert_eq!(deprecation.sunset.unwrap().unix_timestamp(), 3155760000);
+	assert_eq!(deprecation.warnings, ["299 - \"Deprecated API\""]);
+	assert!(deprecation.time_until_sunset().unwrap().as_secs() > 0);
+
+	// the hook sees every response, the log only the first for the endpoint
+	let seen = seen.lock().unwrap();
+	assert_eq!(seen.len(), 3);
+	assert_eq!(seen[0].0, format!("{API}/pages/main/First"));
+	assert_eq!(seen[1].1, deprecation);
+	let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+	assert_eq!(
+		logs.matches("the hangar api sent deprecation notices")
+			.count(),
+		1,
+		"{logs}"
+	);
+	assert!(logs.contains("/pages/main/{slug}"), "{logs}");
+}
@@ -0,0 +1,73 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::BTreeMap;
+
+use hangar_api::object::{DayProjectStats, DayStatsRecord, DayVersionStats};
+use time::Date;
+
+fn parse<T: serde::de::DeserializeOwned>(name: &str) -> T {
+	let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+	let json =
+		std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("failed to read {path}: {err}"));
+	serde_json::from_str(&json).unwrap_or_else(|err| panic!("failed to deserialize {name}: {err}"))
+}
+
+fn csv(records: &[DayStatsRecord]) -> String {
+	let mut out = Vec::new();
+	DayStatsRecord::to_csv(records, &mut out).expect("csv");
+	String::from_utf8(out).expect("utf-8")
+}
+
+#[test]
+fn project_stats_as_csv() {
+	let stats: BTreeMap<Date, DayProjectStats> = parse("project_stats.json");
+	assert_eq!(
+		csv(&DayStatsRecord::from_project_stats(&stats, false)),
+		"\
+date,downloads,views,paper_downloads,waterfall_downloads,velocity_downloads
+2024-06-01,64,187,,,
+2024-06-02,71,203,,,
+2024-06-03,58,164,,,
+2024-06-05,3,0,,,
+"
+	);
+}
+
+#[test]
+fn project_stats_as_csv_with_gaps_filled() {
+	let stats: BTreeMap<Date, DayProjectStats> = parse("project_stats.json");
+	assert_eq!(
+		csv(&DayStatsRecord::from_project_stats(&stats, true)),
+		"\
+date,downloads,views,paper_downloads,waterfall_downloads,velocity_downloads
+2024-06-01,64,187,,,
+2024-06-02,71,203,,,
+2024-06-03,58,164,,,
+2024-06-04,0,0,,,
+2024-06-05,3,0,,,
+"
+	);
+}
+
+#[test]
+fn version_stats_as_csv_with_gaps_filled() {
+	let stats: BTreeMap<Date, DayVersionStats> = parse("version_stats.json");
+	assert_eq!(
+		csv(&DayStatsRecord::from_version_stats(&stats, true)),
+		"\
+date,downloads,views,paper_downloads,waterfall_downloads,velocity_downloads
+2024-06-01,53,,41,,12
+2024-06-02,46,,37,,9
+2024-06-03,0,,,,
+2024-06-04,2,,2,,
+"
+	);
+}
+
+#[test]
+fn no_stats_write_nothing() {
+	assert_eq!(
+		csv(&DayStatsRecord::from_project_stats(&BTreeMap::new(), true)),
+		""
+	);
+}
@@ -0,0 +1,540 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::BTreeMap;
+
+use hangar_api::api::{
+	CompactProjectsResponse, HangarRequest, LatestReleaseRequest, MainPageRequest, MembersResponse,
+	PartialRequest, PermissionsResponse, ProjectsRequest, ProjectsResponse, UsersResponse,
+	VersionsResponse,
+};
+use hangar_api::object::{
+	ApiKeyInfo, Category, DayProjectStats, DayStatsRecord, DayVersionStats, License, LicenseType,
+	NamedPermission, PermissionType, PinnedStatus, Platform, Project, ProjectCompact, ProjectRole,
+	ProjectTags, ReviewHistory, ReviewState, Role, RoleCategory, User, Version, VersionDownloads,
+};
+use hangar_api::Error;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, Month, OffsetDateTime};
+
+/// Reads a response captured from Hangar, from `tests/fixtures`.
+fn fixture(name: &str) -> String {
+	let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+	std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("failed to read {path}: {err}"))
+}
+
+/// Deserializes a fixture, including the path to the field which failed in the panic message.
+fn parse<T: serde::de::DeserializeOwned>(name: &str) -> T {
+	let json = fixture(name);
+	let deserializer = &mut serde_json::Deserializer::from_str(&json);
+	serde_path_to_error::deserialize(deserializer)
+		.unwrap_or_else(|err| panic!("failed to deserialize {name}: {err}"))
+}
+
+#[test]
+fn project() {
+	let project: Project = parse("project.json");
+	assert_eq!(project.id, Some(8));
+	assert_eq!(project.name, "Maintenance");
+	assert_eq!(project.namespace.key(), "kennytv/Maintenance");
+	assert_eq!(project.category, Category::AdminTools);
+	assert_eq!(project.stats.downloads, 21377);
+	assert!(project.is_available());
+	assert_eq!(project.settings.tags, [ProjectTags::SupportsFolia]);
+	assert_eq!(project.settings.license.license_type, "GPL");
+	assert_eq!(project.settings.links.len(), 2);
+	assert_eq!(project.settings.links[1].links[0].url, None);
+	assert!(project.admin.is_none());
+}
+
+#[test]
+fn projects() {
+	let projects: ProjectsResponse = parse("projects.json");
+	assert_eq!(projects.pagination.count, 1204);
+	let slugs: Vec<_> = projects
+		.iter()
+		.map(|project| project.namespace.slug.as_str())
+		.collect();
+	assert_eq!(slugs, ["Maintenance", "packetevents"]);
+	assert!(projects.result[1].settings.links.is_empty());
+}
+
+#[test]
+fn version_with_internal_downloads() {
+	let version: Version = parse("version_internal.json");
+	assert_eq!(version.name, "4.2.1");
+	assert_eq!(version.platforms(), [Platform::Paper, Platform::Velocity]);
+	let download = version.download_for(Platform::Paper).unwrap();
+	assert!(!download.is_external());
+	let file = download.file_info().unwrap();
+	assert_eq!(file.name, "Maintenance-4.2.1.jar");
+	assert_eq!(file.size_bytes, 612534);
+	assert!(download.url().ends_with("/PAPER/Maintenance-4.2.1.jar"));
+	assert_eq!(version.unique_downloads().len(), 1);
+	assert_eq!(version.hangar_dependencies(Platform::Paper).len(), 1);
+	assert_eq!(version.external_dependencies(Platform::Paper).len(), 1);
+	assert!(version.channel.is_pinned());
+}
+
+#[test]
+fn version_with_external_downloads() {
+	let version: Version = parse("version_external.json");
+	assert_eq!(version.platforms(), [Platform::Paper, Platform::Waterfall]);
+	for (_, download) in version.all_downloads() {
+		assert!(matches!(download, VersionDownloads::External { .. }));
+		assert!(download.file_info().is_none());
+	}
+	assert!(version
+		.download_for(Platform::Waterfall)
+		.unwrap()
+		.url()
+		.ends_with("/downloads/bungeecord"));
+	assert!(version.plugin_dependencies.is_empty());
+	assert!(version.channel.hides_by_default());
+}
+
+#[test]
+fn versions() {
+	let versions: VersionsResponse = parse("versions.json");
+	assert_eq!(versions.len(), 2);
+	assert_eq!(versions.pagination.count, 2);
+}
+
+#[test]
+fn main_page() {
+	let page = MainPageRequest::parse_response(fixture("page.md").as_bytes()).unwrap();
+	assert!(page.contents.starts_with("# Maintenance\r\n\r\n"));
+	assert_eq!(page.contents, fixture("page.md"));
+}
+
+#[test]
+fn latest_release() {
+	let name = LatestReleaseRequest::parse_response(fixture("latest_release.txt").as_bytes());
+	assert_eq!(name.unwrap(), "4.2.1");
+}
+
+#[test]
+fn user() {
+	let user: User = parse("user.json");
+	assert_eq!(user.id, Some(12));
+	assert_eq!(user.name, "kennytv");
+	assert_eq!(user.tagline.as_deref(), Some("ViaVersion and Paper dev"));
+	assert_eq!(user.roles.len(), 2);
+	assert!(user.roles[0].is_admin());
+	assert_eq!(user.roles[0].color.to_string(), "#DC0000");
+	assert_eq!(user.name_history[0].old_name, "KennyTV");
+	assert!(!user.is_organization);
+}
+
+#[test]
+fn organization() {
+	let user: User = parse("organization.json");
+	assert!(user.is_organization);
+	assert_eq!(user.tagline, None);
+	assert_eq!(user.roles[0].rank, None);
+	assert!(user.name_history.is_empty());
+}
+
+#[test]
+fn api_keys() {
+	let keys: Vec<ApiKeyInfo> = parse("api_keys.json");
+	assert_eq!(keys.len(), 2);
+	assert!(keys[0].last_used.is_some());
+	assert!(keys[1].last_used.is_none());
+}
+
+#[test]
+fn members() {
+	let members: MembersResponse = parse("members.json");
+	assert_eq!(members.pagination.count, 3);
+	let owner = &members.result[0];
+	assert_eq!(owner.user, "kennytv");
+	assert!(owner.has_role(ProjectRole::Owner));
+	assert!(owner.roles[0].is_project_owner());
+	let developer = &members.result[1];
+	assert!(developer.has_role(ProjectRole::Developer));
+	assert!(developer.has_role(ProjectRole::Editor));
+	assert!(!developer.has_role(ProjectRole::Owner));
+	assert_eq!(
+		members.result[2].roles[0].project_role(),
+		ProjectRole::Supporter
+	);
+}
+
+#[test]
+fn permissions() {
+	let permissions: PermissionsResponse = parse("permissions.json");
+	assert_eq!(permissions.permission_type, PermissionType::Project);
+	assert_eq!(permissions.permissions.len(), 12);
+	for permission in [
+		NamedPermission::ViewPublicInfo,
+		NamedPermission::EditPage,
+		NamedPermission::CreateVersion,
+		NamedPermission::EditChannels,
+	] {
+		assert!(
+			permissions.permissions.contains(&permission),
+			"{permission}"
+		);
+	}
+	assert!(!permissions
+		.permissions
+		.contains(&NamedPermission::IsSubjectOwner));
+	// permissions added to Hangar after this crate are kept by name
+	assert_eq!(
+		permissions.permissions.last(),
+		Some(&NamedPermission::Unknown("upload_screenshots".to_string()))
+	);
+	assert!(permissions
+		.permission_bin_string
+		.bytes()
+		.all(|bit| bit == b'0' || bit == b'1'));
+}
+
+fn day(day: u8) -> Date {
+	Date::from_calendar_date(2024, Month::June, day).unwrap()
+}
+
+#[test]
+fn project_stats() {
+	let stats: BTreeMap<Date, DayProjectStats> = parse("project_stats.json");
+	assert_eq!(stats.len(), 4);
+	assert_eq!(stats[&day(1)].views, 187);
+	assert_eq!(stats[&day(2)].downloads, 71);
+	assert_eq!(stats.keys().next(), Some(&day(1)));
+	assert_eq!(stats.keys().last(), Some(&day(5)));
+	let records = DayStatsRecord::from_project_stats(&stats, true);
+	assert_eq!(records.len(), 5);
+	assert_eq!(records[3].date, day(4));
+	assert_eq!((records[3].downloads, records[3].views), (0, Some(0)));
+}
+
+#[test]
+fn version_stats() {
+	let stats: BTreeMap<Date, DayVersionStats> = parse("version_stats.json");
+	assert_eq!(stats.len(), 3);
+	assert_eq!(stats[&day(1)].downloads.velocity, Some(12));
+	assert_eq!(stats[&day(2)].total_downloads(), 46);
+	assert_eq!(stats[&day(4)].downloads.velocity, None);
+}
+
+#[test]
+fn projects_of_a_member_and_owner() {
+	let member: ProjectsResponse = parse("projects_member.json");
+	let owned: ProjectsResponse = parse("projects_owner.json");
+	let keys = |page: &ProjectsResponse| -> Vec<String> {
+		page.result
+			.iter()
+			.map(|project| project.namespace.key())
+			.collect()
+	};
+	assert_eq!(
+		keys(&member),
+		["ViaVersion/ViaVersion", "kennytv/Maintenance"]
+	);
+	assert_eq!(keys(&owned), ["kennytv/Maintenance", "kennytv/Sneaky"]);
+}
+
+#[test]
+fn users() {
+	let users: UsersResponse = parse("users.json");
+	assert_eq!(users.pagination.count, 48);
+	let names: Vec<_> = users.iter().map(|user| user.name.as_str()).collect();
+	assert_eq!(names, ["kennytv", "GeyserMC", "Steve_"]);
+	let roles = &users.result[0].roles;
+	assert_eq!(roles[1].title, "Paper Developer");
+	assert_eq!(roles[1].rank, Some(50));
+	assert_eq!(roles[1].category, RoleCategory::Global);
+	let steve = &users.result[2];
+	assert!(steve.locked);
+	assert!(steve.roles.is_empty());
+	assert_eq!(steve.tagline.as_deref(), Some(""));
+	assert_eq!(steve.project_count, 0);
+}
+
+#[test]
+fn starred_projects() {
+	let starred: CompactProjectsResponse = parse("starred.json");
+	assert_eq!(starred.pagination.count, 2);
+	let project = &starred.result[0];
+	assert_eq!(project.id, Some(8));
+	assert_eq!(project.namespace.key(), "kennytv/Maintenance");
+	assert_eq!(project.stats.stars, 64);
+	assert_eq!(project.category, Category::AdminTools);
+	assert_eq!(
+		project.avatar_url_absolute(),
+		"https://hangarcdn.papermc.io/avatars/project/8.webp?v=1"
+	);
+	// compact projects leave out what `Project` has on top
+	let json: serde_json::Value = serde_json::from_str(&fixture("starred.json")).unwrap();
+	for field in ["description", "settings", "userActions"] {
+		assert!(json["result"][0].get(field).is_none(), "{field}");
+	}
+}
+
+#[test]
+fn full_projects_convert_to_compact_ones() {
+	let projects: ProjectsResponse = parse("projects.json");
+	let starred: CompactProjectsResponse = parse("starred.json");
+	for (project, listed) in projects.result.into_iter().zip(starred.result) {
+		let compact = ProjectCompact::from(project);
+		assert_eq!(compact.id, listed.id);
+		assert_eq!(compact.name, listed.name);
+		assert_eq!(compact.namespace.key(), listed.namespace.key());
+		assert_eq!(compact.stats, listed.stats);
+		assert_eq!(compact.category, listed.category);
+		assert_eq!(compact.last_updated, listed.last_updated);
+		assert_eq!(compact.avatar_url, listed.avatar_url);
+	}
+}
+
+#[test]
+fn project_with_null_settings() {
+	let project: Project = parse("project_no_license.json");
+	let settings = &project.settings;
+	assert_eq!(settings.license, License::default());
+	assert!(!settings.license.is_specified());
+	assert_eq!(settings.license.license_type, License::UNSPECIFIED);
+	assert_eq!(
+		settings.license.known_type(),
+		Some(LicenseType::Unspecified)
+	);
+	assert!(settings.links.is_empty());
+	assert!(settings.keywords.is_empty());
+	assert_eq!(settings.sponsors, "");
+	assert!(!settings.donation.enable);
+	assert_eq!(settings.tags, [ProjectTags::Library]);
+}
+
+#[test]
+fn project_with_an_empty_license_type() {
+	let project: Project = parse("project_empty_license.json");
+	let license = &project.settings.license;
+	assert!(!license.is_specified());
+	assert_eq!(license.license_type, License::UNSPECIFIED);
+	assert_eq!(license.name.as_deref(), Some(""));
+	assert_eq!(license.url, None);
+}
+
+#[test]
+fn project_with_admin_info() {
+	let project: Project = parse("project_admin.json");
+	let admin = project.admin.expect("admin info");
+	assert_eq!(admin.flag_count, 1);
+	assert!(admin.has_open_flags());
+	assert_eq!(admin.notes.len(), 2);
+	assert_eq!(admin.notes[0].id, 31);
+	assert_eq!(admin.notes[0].user_name, "Machine_Maker");
+	assert!(admin.notes[0].created_at < admin.notes[1].created_at);
+}
+
+#[test]
+fn project_without_admin_info() {
+	let public: Project = parse("project.json");
+	let admin: Project = parse("project_admin.json");
+	assert!(public.admin.is_none());
+	assert_eq!(public.namespace.key(), admin.namespace.key());
+	assert_eq!(public.stats, admin.stats);
+}
+
+fn names<'a>(versions: impl IntoIterator<Item = &'a Version>) -> Vec<&'a str> {
+	versions
+		.into_iter()
+		.map(|version| version.name.as_str())
+		.collect()
+}
+
+#[test]
+fn pinned_versions_come_first() {
+	let versions: VersionsResponse = parse("versions_pinned.json");
+	let statuses: Vec<_> = versions
+		.iter()
+		.map(|version| version.pinned_status)
+		.collect();
+	assert_eq!(
+		statuses,
+		[
+			PinnedStatus::None,
+			PinnedStatus::Channel,
+			PinnedStatus::None,
+			PinnedStatus::Version,
+			PinnedStatus::None,
+		]
+	);
+	assert_eq!(
+		names(versions.pinned_first()),
+		["4.2.1", "4.0.0-LTS", "5.0.0-SNAPSHOT+3", "4.2.0", "3.9.0"]
+	);
+	let pinned: Vec<_> = versions
+		.iter()
+		.filter(|version| version.is_effectively_pinned())
+		.collect();
+	assert_eq!(names(pinned), ["4.2.1", "4.0.0-LTS"]);
+}
+
+#[test]
+fn versions_published_after_a_time() {
+	let versions: VersionsResponse = parse("versions_pinned.json");
+	let at = |time: &str| OffsetDateTime::parse(time, &Rfc3339).unwrap();
+	assert_eq!(
+		names(versions.published_after(at("2024-05-01T00:00:00Z"))),
+		["5.0.0-SNAPSHOT+3", "4.2.1", "4.2.0"]
+	);
+	// 4.2.0 was published at exactly this time, so it was already there
+	assert_eq!(
+		names(versions.published_after(at("2024-05-01T16:20:00Z"))),
+		["5.0.0-SNAPSHOT+3", "4.2.1"]
+	);
+	assert_eq!(
+		names(versions.published_after(at("2024-05-01T16:19:59Z"))),
+		["5.0.0-SNAPSHOT+3", "4.2.1", "4.2.0"]
+	);
+	assert!(versions
+		.published_after(at("2024-07-01T10:00:00Z"))
+		.is_empty());
+	assert_eq!(
+		versions.published_after(at("2020-01-01T00:00:00Z")).len(),
+		5
+	);
+}
+
+#[test]
+fn a_corrupted_project_is_named_in_the_error() {
+	let json = fixture("projects_corrupt.json");
+	match ProjectsRequest::parse_response(json.as_bytes()).unwrap_err() {
+		Error::Deserialize { path, item, source } => {
+			assert_eq!(path, "result[17].settings.donation.enable");
+			assert_eq!(item.as_deref(), Some("CoolPlugin"));
+			assert!(
+				source.to_string().contains("expected a boolean"),
+				"{source}"
+			);
+		}
+		err => panic!("expected Deserialize, got {err:?}"),
+	}
+}
+
+#[test]
+fn a_partial_page_keeps_the_other_projects() {
+	let json = fixture("projects_corrupt.json");
+	let page = PartialRequest::<ProjectsRequest>::parse_response(json.as_bytes()).unwrap();
+	assert!(!page.is_complete());
+	assert_eq!(page.pagination.limit, 25);
+	assert_eq!(page.items.len(), 24);
+	assert!(page
+		.items
+		.iter()
+		.all(|project| project.namespace.slug != "CoolPlugin"));
+	assert_eq!(page.items[17].namespace.slug, "WorldEdit");
+	let [error] = &page.errors[..] else {
+		panic!("expected one error, got {:?}", page.errors);
+	};
+	assert_eq!(error.index, 17);
+	assert_eq!(error.name.as_deref(), Some("CoolPlugin"));
+	match &error.error {
+		Error::Deserialize { path, item, .. } => {
+			assert_eq!(path, "result[17].settings.donation.enable");
+			assert_eq!(item.as_deref(), Some("CoolPlugin"));
+		}
+		err => panic!("expected Deserialize, got {err:?}"),
+	}
+}
+
+#[test]
+fn reviews() {
+	let history: ReviewHistory = parse("reviews.json");
+	let reviews: Vec<_> = history
+		.reviews
+		.iter()
+		.map(|review| {
+			(
+				review.reviewer.as_str(),
+				review.created_at.date(),
+				review.state,
+				review.message.as_deref(),
+			)
+		})
+		.collect();
+	assert_eq!(
+		reviews,
+		[
+			(
+				"Machine_Maker",
+				day(2),
+				ReviewState::UnderReview,
+				Some("Looking into the bundled libraries")
+			),
+			(
+				"Machine_Maker",
+				day(3),
+				ReviewState::PartiallyReviewed,
+				None
+			),
+			("kennytv", day(5), ReviewState::Reviewed, Some("Approved")),
+		]
+	);
+	let latest = history.latest().unwrap();
+	assert_eq!(
+		latest.created_at,
+		OffsetDateTime::parse("2024-06-05T11:02:58.930714Z", &Rfc3339).unwrap()
+	);
+}
+
+#[test]
+fn staff() {
+	let staff: UsersResponse = parse("staff.json");
+	let names: Vec<_> = staff.iter().map(|user| user.name.as_str()).collect();
+	assert_eq!(names, ["kennytv", "MiniDigger", "Machine_Maker", "Emilia"]);
+	let admins: Vec<_> = staff
+		.iter()
+		.filter(|user| user.roles.iter().any(Role::is_admin))
+		.map(|user| user.name.as_str())
+		.collect();
+	assert_eq!(admins, ["kennytv", "MiniDigger"]);
+	assert!(staff
+		.iter()
+		.flat_map(|user| &user.roles)
+		.all(|role| !role.is_project_owner()));
+
+	let moderator = &staff.result[2].roles[1];
+	assert_eq!(moderator.title, "Hangar Moderator");
+	assert_eq!(moderator.color.to_string(), "#26A65B");
+	assert_eq!(
+		moderator.permissions,
+		[
+			NamedPermission::ModNotesAndFlags,
+			NamedPermission::SeeHidden,
+			NamedPermission::Reviewer
+		]
+	);
+	assert!(staff.result[0].roles[0].permissions.is_empty());
+
+	// categories added to Hangar after this crate don't break deserializing
+	let partner = &staff.result[3].roles[1];
+	assert_eq!(partner.category, RoleCategory::Unknown);
+	assert_eq!(partner.rank, None);
+	assert!(!partner.is_admin());
+
+	let mut roles: Vec<Role> = staff
+		.result
+		.iter()
+		.flat_map(|user| user.roles.clone())
+		.collect();
+	roles.sort_by(Role::by_rank);
+	let titles: Vec<_> = roles.iter().map(|role| role.title.as_str()).collect();
+	assert_eq!(
+		titles,
+		[
+			"Hangar Admin",
+			"Hangar Admin",
+			"Hangar Moderator",
+			"Hangar Support",
+			"Paper Developer",
+			"Paper Developer",
+			"Community Partner",
+		]
+	);
+	// the admin title alone doesn't make a project role an admin
+	let mut project_admin = roles[0].clone();
+	project_admin.category = RoleCategory::Project;
+	assert!(!project_admin.is_admin());
+}
@@ -0,0 +1,210 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use std::future::Future;
+
+use hangar_api::api::{MainPageRequest, ProjectRequest, VersionsRequest};
+use hangar_api::backend::MockBackend;
+use hangar_api::object::Pagination;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::MetricKind;
+
+use common::{client, API};
+
+/// A metric recorded while running a test, with its labels sorted.
+#[derive(Debug, PartialEq)]
+struct Metric {
+	kind: MetricKind,
+	name: String,
+	labels: Vec<(String, String)>,
+	value: DebugValue,
+}
+
+/// Runs a future with a recorder installed for the current thread, returning everything it recorded sorted by name
+/// and labels.
+fn record(future: impl Future<Output = ()>) -> Vec<Metric> {
+	let recorder = DebuggingRecorder::new();
+	let snapshotter = recorder.snapshotter();
+	let runtime = tokio::runtime::Builder::new_current_thread()
+		.enable_time()
+		.start_paused(true)
+		.build()
+		.expect("runtime");
+	metrics::with_local_recorder(&recorder, || runtime.block_on(future));
+	let mut metrics: Vec<Metric> = snapshotter
+		.snapshot()
+		.into_vec()
+		.into_iter()
+		.map(|(key, _, _, value)| {
+			let (kind, key) = key.into_parts();
+			let mut labels: Vec<(String, String)> = key
+				.labels()
+				.map(|label| (label.key().to_string(), label.value().to_string()))
+				.collect();
+			labels.sort();
+			Metric {
+				kind,
+				name: key.name().to_string(),
+				labels,
+				value,
+			}
+		})
+		.collect();
+	metrics.sort_by(|a, b| (&a.name, &a.labels).cmp(&(&b.name, &b.labels)));
+	metrics
+}
+
+fn labels(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+	pairs
+		.iter()
+		.map(|(key, value)| (key.to_string(), value.to_string()))
+		.collect()
+}
+
+fn counter(metrics: &[Metric], name: &str, pairs: &[(&str, &str)]) -> Option<u64> {
+	let labels = labels(pairs);
+	metrics
+		.iter()
+		.find(|metric| metric.name == name && metric.labels == labels)
+		.map(|metric| match metric.value {
+			DebugValue::Counter(value) => value,
+			ref value => panic!("{name} isn't a counter: {value:?}"),
+		})
+}
+
+fn samples(metrics: &[Metric], name: &str, pairs: &[(&str, &str)]) -> usize {
+	let labels = labels(pairs);
+	metrics
+		.iter()
+		.find(|metric| metric.name == name && metric.labels == labels)
+		.map_or(0, |metric| match &metric.value {
+			DebugValue::Histogram(values) => values.len(),
+			value => panic!("{name} isn't a histogram: {value:?}"),
+		})
+}
+
+#[test]
+fn successful_requests_are_counted_and_timed() {
+	let url = format!("{API}/projects/Maintenance");
+	let metrics = record(async {
+		let client = client(MockBackend::new().respond(&url, 200, "{}"));
+		let _ = client.send(&ProjectRequest::new("Maintenance")).await;
+	});
+	let names: Vec<(MetricKind, &str)> = metrics
+		.iter()
+		.map(|metric| (metric.kind, &*metric.name))
+		.collect();
+	assert_eq!(
+		names,
+		[
+			(MetricKind::Histogram, "hangar_request_duration_seconds"),
+			(MetricKind::Counter, "hangar_requests_total"),
+		]
+	);
+	let ok = [("endpoint", "projects"), ("status", "2xx")];
+	assert_eq!(counter(&metrics, "hangar_requests_total", &ok), Some(1));
+	assert_eq!(samples(&metrics, "hangar_request_duration_seconds", &ok), 1);
+}
+
+#[test]
+fn retries_are_counted_per_attempt() {
+	let url = format!("{API}/projects/Maintenance/versions");
+	let metrics = record(async {
+		let client = client(MockBackend::new().respond(&url, 503, "busy").respond(
+			&url,
+			200,
+			r#"{"pagination":{"limit":10,"offset":0,"count":0},"result":[]}"#,
+		));
+		let request = VersionsRequest::builder()
+			.slug("Maintenance")
+			.pagination(Pagination::default())
+			.build();
+		client.send(&request).await.expect("versions");
+	});
+	let endpoint = ("endpoint", "versions");
+	assert_eq!(
+		counter(
+			&metrics,
+			"hangar_requests_total",
+			&[endpoint, ("status", "5xx")]
+		),
+		Some(1)
+	);
+	assert_eq!(
+		counter(
+			&metrics,
+			"hangar_requests_total",
+			&[endpoint, ("status", "2xx")]
+		),
+		Some(1)
+	);
+	assert_eq!(
+		counter(&metrics, "hangar_retries_total", &[endpoint]),
+		Some(1)
+	);
+	assert_eq!(
+		counter(&metrics, "hangar_rate_limited_total", &[endpoint]),
+		None
+	);
+}
+
+#[test]
+fn rate_limited_requests_are_counted() {
+	let url = format!("{API}/pages/main/Maintenance");
+	let metrics = record(async {
+		let client = client(
+			MockBackend::new()
+				.respond_with_headers(&url, 429, &[("Retry-After", "1")], "")
+				.respond(&url, 200, "# Maintenance"),
+		);
+		let request = MainPageRequest::builder().slug("Maintenance").build();
+		client.send(&request).await.expect("page");
+	});
+	let endpoint = ("endpoint", "pages");
+	assert_eq!(
+		counter(&metrics, "hangar_rate_limited_total", &[endpoint]),
+		Some(1)
+	);
+	assert_eq!(
+		counter(&metrics, "hangar_retries_total", &[endpoint]),
+		Some(1)
+	);
+	assert_eq!(
+		counter(
+			&metrics,
+			"hangar_requests_total",
+			&[endpoint, ("status", "4xx")]
+		),
+		Some(1)
+	);
+}
+
+#[test]
+fn slugs_are_not_used_as_labels() {
+	let metrics = record(async {
+		let backend = MockBackend::new()
+			.respond(&format!("{API}/projects/Maintenance"), 200, "{}")
+			.respond(&format!("{API}/projects/ViaVersion"), 200, "{}");
+		let client = client(backend);
+		let _ = client.send(&ProjectRequest::new("Maintenance")).await;
+		let _ = client.send(&ProjectRequest::new("ViaVersion")).await;
+	});
+	assert_eq!(
+		counter(
+			&metrics,
+			"hangar_requests_total",
+			&[("endpoint", "projects"), ("status", "2xx")]
+		),
+		Some(2)
+	);
+	for metric in &metrics {
+		for (_, value) in &metric.labels {
+			assert!(
+				!value.contains("Maintenance") && !value.contains("ViaVersion"),
+				"{} is labelled with {value}",
+				metric.name
+			);
+		}
+	}
+}
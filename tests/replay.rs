@@ -0,0 +1,178 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use std::path::PathBuf;
+
+use hangar_api::api::{MainPageRequest, ProjectRequest, VersionsRequest};
+use hangar_api::backend::{MockBackend, ReqwestBackend};
+use hangar_api::object::Pagination;
+use hangar_api::replay::{CassetteBackend, RecordingBackend, ReplayBackend};
+use hangar_api::Error;
+
+use common::{builder, API};
+
+/// The recordings this crate's own tests replay, recorded with `HANGAR_RECORD=1`.
+const CASSETTES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cassettes");
+
+const JSON: &[(&str, &str)] = &[("Content-Type", "application/json")];
+
+/// Gets an empty directory to record to, unique to the test.
+fn scratch_dir(test: &str) -> PathBuf {
+	let dir = std::env::temp_dir().join(format!("hangar-api-{}-{test}", std::process::id()));
+	let _ = std::fs::remove_dir_all(&dir);
+	dir
+}
+
+fn versions() -> VersionsRequest {
+	VersionsRequest::builder()
+		.slug("Maintenance")
+		.pagination(Pagination::default())
+		.build()
+}
+
+/// A backend serving the captured responses in `tests/fixtures` for Maintenance.
+fn fixtures() -> MockBackend {
+	MockBackend::new()
+		.respond_with_headers(
+			&format!("{API}/authenticate"),
+			200,
+			JSON,
+			r#"{"token":"eyJhbGciOiJIUzI1NiJ9.e30.c2lnbmF0dXJl","expiresIn":3600}"#,
+		)
+		.respond_with_headers(
+			&format!("{API}/projects/Maintenance"),
+			200,
+			JSON,
+			include_str!("fixtures/project.json"),
+		)
+		.respond_with_headers(
+			&format!("{API}/projects/Maintenance/versions"),
+			200,
+			JSON,
+			include_str!("fixtures/versions.json"),
+		)
+		.respond_with_headers(
+			&format!("{API}/pages/main/Maintenance"),
+			200,
+			&[("Content-Type", "text/plain")],
+			include_str!("fixtures/page.md"),
+		)
+}
+
+#[tokio::test]
+async fn recorded_responses_are_replayed() {
+	let dir = scratch_dir("replayed");
+	let recording = builder()
+		.build_with_backend(RecordingBackend::new(fixtures(), &dir))
+		.unwrap();
+	let project = recording
+		.send(&ProjectRequest::new("Maintenance"))
+		.await
+		.unwrap();
+	let page = recording
+		.send(&MainPageRequest::builder().slug("Maintenance").build())
+		.await
+		.unwrap();
+
+	let replay = builder()
+		.build_with_backend(ReplayBackend::new(&dir))
+		.unwrap();
+	let replayed = replay
+		.send(&ProjectRequest::new("Maintenance"))
+		.await
+		.unwrap();
+	assert_eq!(replayed.namespace.key(), project.namespace.key());
+	assert_eq!(replayed.stats, project.stats);
+	let replayed_page = replay
+		.send(&MainPageRequest::builder().slug("Maintenance").build())
+		.await
+		.unwrap();
+	assert_eq!(replayed_page.contents, page.contents);
+	let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn requests_which_were_not_recorded_fail() {
+	let dir = scratch_dir("unrecorded");
+	let recording = builder()
+		.build_with_backend(RecordingBackend::new(fixtures(), &dir))
+		.unwrap();
+	recording
+		.send(&ProjectRequest::new("Maintenance"))
+		.await
+		.unwrap();
+
+	let replay = builder()
+		.build_with_backend(ReplayBackend::new(&dir))
+		.unwrap();
+	let other_page = VersionsRequest::builder()
+		.slug("Maintenance")
+		.pagination(Pagination::page(1, 25))
+		.build();
+	for err in [
+		replay.send(&versions()).await.unwrap_err(),
+		replay.send(&other_page).await.unwrap_err(),
+	] {
+		match err {
+			Error::Replay(message) => {
+				assert!(
+					message.contains("/projects/Maintenance/versions"),
+					"{message}"
+				)
+			}
+			err => panic!("expected Replay, got {err:?}"),
+		}
+	}
+	let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn recordings_leave_out_secrets() {
+	let dir = scratch_dir("secrets");
+	let api_key = "9b3e5c1a-0000-4000-8000-000000000000.hangar-secret";
+	let client = builder()
+		.api_key(api_key)
+		.build_with_backend(RecordingBackend::new(fixtures(), &dir))
+		.unwrap();
+	client
+		.send(&ProjectRequest::new("Maintenance"))
+		.await
+		.unwrap();
+	let requests = client.backend().inner().requests();
+	assert!(
+		requests.iter().any(|request| request
+			.headers
+			.iter()
+			.any(|(name, _)| name.eq_ignore_ascii_case("Authorization"))),
+		"the project should be requested with a token"
+	);
+	let recordings: Vec<String> = std::fs::read_dir(&dir)
+		.unwrap()
+		.map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+		.collect();
+	assert_eq!(recordings.len(), 2);
+	for recording in recordings {
+		assert!(!recording.contains(api_key), "{recording}");
+		assert!(!recording.contains("Authorization"), "{recording}");
+	}
+	let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn the_crates_cassettes_replay() {
+	let backend = CassetteBackend::from_env(ReqwestBackend::default(), CASSETTES);
+	let client = builder().build_with_backend(backend).unwrap();
+	let project = client
+		.send(&ProjectRequest::new("Maintenance"))
+		.await
+		.unwrap();
+	assert_eq!(project.namespace.key(), "kennytv/Maintenance");
+	let versions = client.send(&versions()).await.unwrap();
+	assert!(!versions.result.is_empty());
+	let page = client
+		.send(&MainPageRequest::builder().slug("Maintenance").build())
+		.await
+		.unwrap();
+	assert!(page.contents.contains("Maintenance"));
+}
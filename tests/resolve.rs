@@ -0,0 +1,308 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+mod common;
+
+use hangar_api::api::{HangarRequest, ProjectsRequest, VersionsRequest};
+use hangar_api::backend::MockBackend;
+use hangar_api::object::{Pagination, Platform, Version};
+use hangar_api::resolve::{DependencyReport, ResolveOptions, UnresolvedReason};
+use serde_json::{json, Value};
+
+use common::client;
+
+/// Reads a response captured from Hangar, from `tests/fixtures`.
+fn fixture(name: &str) -> Value {
+	let path = format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+	let json =
+		std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("failed to read {path}: {err}"));
+	serde_json::from_str(&json).unwrap()
+}
+
+/// Gets the url a request is sent to, including its query.
+fn url(request: &impl HangarRequest) -> String {
+	reqwest::Url::parse_with_params(&request.url(), request.query().unwrap())
+		.unwrap()
+		.to_string()
+}
+
+/// A dependency listed by a version on Paper: its name, whether it's required and its external url.
+type Dependency<'a> = (&'a str, bool, Option<&'a str>);
+
+/// Creates a version with downloads for the given platforms, depending on the given plugins on Paper.
+fn version(name: &str, platforms: &[&str], dependencies: &[Dependency]) -> Value {
+	let mut version = fixture("version_internal.json");
+	let download = version["downloads"]["PAPER"].clone();
+	version["name"] = json!(name);
+	version["downloads"] = platforms
+		.iter()
+		.map(|platform| (platform.to_string(), download.clone()))
+		.collect::<serde_json::Map<_, _>>()
+		.into();
+	version["pluginDependencies"] = json!({
+		"PAPER": dependencies
+			.iter()
+			.map(|(name, required, external_url)| json!({
+				"name": name,
+				"required": required,
+				"externalUrl": external_url,
+				"platform": "PAPER",
+			}))
+			.collect::<Vec<_>>(),
+	});
+	version
+}
+
+fn page(result: Vec<Value>) -> String {
+	json!({
+		"pagination": {"limit": 1, "offset": 0, "count": result.len()},
+		"result": result,
+	})
+	.to_string()
+}
+
+/// A mocked copy of Hangar with some projects depending on each other.
+struct Api {
+	backend: MockBackend,
+}
+
+impl Api {
+	fn new() -> Self {
+		Self {
+			backend: MockBackend::new(),
+		}
+	}
+
+	/// Adds a project, with its versions listed newest first.
+	fn project(self, name: &str, versions: Vec<Value>) -> Self {
+		let mut project = fixture("project.json");
+		project["name"] = json!(name);
+		project["namespace"]["slug"] = json!(name);
+		let backend = self
+			.backend
+			.respond(&url(&search(name)), 200, page(vec![project]))
+			.respond(&url(&versions_of(name)), 200, page(versions));
+		Self { backend }
+	}
+
+	/// Adds a name nothing on Hangar is called.
+	fn missing(self, name: &str) -> Self {
+		let backend = self
+			.backend
+			.respond(&url(&search(name)), 200, page(Vec::new()));
+		Self { backend }
+	}
+
+	async fn resolve(self, dependencies: &[Dependency<'_>], options: ResolveOptions) -> Resolved {
+		let root: Version =
+			serde_json::from_value(version("1.0.0", &["PAPER"], dependencies)).unwrap();
+		let client = client(self.backend);
+		let report = client
+			.resolve_dependencies(&root, Platform::Paper, options)
+			.await
+			.expect("resolved");
+		let requests = client
+			.backend()
+			.requests()
+			.into_iter()
+			.map(|request| request.url)
+			.collect();
+		Resolved { report, requests }
+	}
+}
+
+fn search(name: &str) -> ProjectsRequest {
+	ProjectsRequest::builder()
+		.query(name.to_string())
+		.prioritize_exact_match(true)
+		.pagination(Pagination::page(0, 1))
+		.build()
+}
+
+fn versions_of(slug: &str) -> VersionsRequest {
+	VersionsRequest::builder()
+		.slug(slug)
+		.pagination(Pagination::page(0, Pagination::MAX_LIMIT))
+		.platform(Platform::Paper)
+		.build()
+}
+
+struct Resolved {
+	report: DependencyReport,
+	/// The urls of every request sent, without their queries
+	requests: Vec<String>,
+}
+
+impl Resolved {
+	fn hangar(&self) -> Vec<(&str, &str, usize)> {
+		self.report
+			.hangar
+			.iter()
+			.map(|dependency| {
+				(
+					&*dependency.name,
+					&*dependency.version.name,
+					dependency.depth,
+				)
+			})
+			.collect()
+	}
+
+	fn unresolved(&self) -> Vec<(&str, UnresolvedReason)> {
+		self.report
+			.unresolved
+			.iter()
+			.map(|dependency| (&*dependency.name, dependency.reason))
+			.collect()
+	}
+
+	fn external(&self) -> Vec<(&str, &str)> {
+		self.report
+			.external
+			.iter()
+			.map(|dependency| (&*dependency.name, &*dependency.url))
+			.collect()
+	}
+}
+
+/// A library which depends on another library depending on it again.
+fn libraries() -> Api {
+	Api::new()
+		.project(
+			"LibA",
+			vec![version("1.2.0", &["PAPER"], &[("LibB", true, None)])],
+		)
+		.project(
+			"LibB",
+			vec![version("0.3.0", &["PAPER"], &[("LibA", true, None)])],
+		)
+}
+
+#[tokio::test]
+async fn transitive_dependencies_are_resolved() {
+	let resolved = libraries()
+		.resolve(&[("LibA", true, None)], ResolveOptions::default())
+		.await;
+	assert_eq!(
+		resolved.hangar(),
+		[("LibA", "1.2.0", 1), ("LibB", "0.3.0", 2)]
+	);
+	assert!(resolved.report.is_complete());
+	assert_eq!(resolved.report.dependencies_of(None), ["LibA"]);
+	assert_eq!(resolved.report.dependencies_of(Some("LibB")), ["LibA"]);
+}
+
+#[tokio::test]
+async fn cycles_are_only_resolved_once() {
+	let resolved = libraries()
+		.resolve(&[("LibA", true, None)], ResolveOptions::default())
+		.await;
+	assert_eq!(resolved.report.cycles(), [vec!["LibA", "LibB"]]);
+	let version_listings = resolved
+		.requests
+		.iter()
+		.filter(|url| url.ends_with("/versions"))
+		.count();
+	assert_eq!(version_listings, 2);
+}
+
+#[tokio::test]
+async fn dependencies_past_the_max_depth_are_unresolved() {
+	let resolved = libraries()
+		.resolve(
+			&[("LibA", true, None)],
+			ResolveOptions::builder().max_depth(1_usize).build(),
+		)
+		.await;
+	assert_eq!(resolved.hangar(), [("LibA", "1.2.0", 1)]);
+	assert_eq!(
+		resolved.unresolved(),
+		[("LibB", UnresolvedReason::MaxDepth)]
+	);
+	assert!(!resolved.report.is_complete());
+}
+
+#[tokio::test]
+async fn unknown_and_incompatible_dependencies_are_unresolved() {
+	let resolved = Api::new()
+		.missing("NotOnHangar")
+		.project("VelocityOnly", vec![version("2.0.0", &["VELOCITY"], &[])])
+		.resolve(
+			&[("NotOnHangar", true, None), ("VelocityOnly", true, None)],
+			ResolveOptions::default(),
+		)
+		.await;
+	assert!(resolved.hangar().is_empty());
+	assert_eq!(
+		resolved.unresolved(),
+		[
+			("NotOnHangar", UnresolvedReason::NotFound),
+			("VelocityOnly", UnresolvedReason::NoCompatibleVersion),
+		]
+	);
+	assert!(!resolved.report.is_complete());
+}
+
+#[tokio::test]
+async fn the_newest_version_for_the_platform_is_picked() {
+	let resolved = Api::new()
+		.project(
+			"LibA",
+			vec![
+				version("1.3.0", &["VELOCITY"], &[]),
+				version("1.2.0", &["PAPER", "VELOCITY"], &[]),
+				version("1.1.0", &["PAPER"], &[]),
+			],
+		)
+		.resolve(&[("LibA", true, None)], ResolveOptions::default())
+		.await;
+	assert_eq!(resolved.hangar(), [("LibA", "1.2.0", 1)]);
+}
+
+const PROTOCOL_LIB: &str = "https://www.spigotmc.org/resources/protocollib.1997/";
+
+#[tokio::test]
+async fn optional_dependencies_are_skipped_by_default() {
+	let resolved = libraries()
+		.resolve(
+			&[
+				("LibA", false, None),
+				("ProtocolLib", false, Some(PROTOCOL_LIB)),
+			],
+			ResolveOptions::default(),
+		)
+		.await;
+	assert!(resolved.hangar().is_empty());
+	assert!(resolved.external().is_empty());
+	assert!(resolved.requests.is_empty());
+}
+
+#[tokio::test]
+async fn optional_dependencies_can_be_included() {
+	let resolved = libraries()
+		.resolve(
+			&[
+				("LibA", false, None),
+				("ProtocolLib", false, Some(PROTOCOL_LIB)),
+			],
+			ResolveOptions::builder().include_optional(true).build(),
+		)
+		.await;
+	assert_eq!(
+		resolved.hangar(),
+		[("LibA", "1.2.0", 1), ("LibB", "0.3.0", 2)]
+	);
+	assert_eq!(resolved.external(), [("ProtocolLib", PROTOCOL_LIB)]);
+}
+
+#[tokio::test]
+async fn external_dependencies_are_not_looked_up() {
+	let resolved = Api::new()
+		.resolve(
+			&[("ProtocolLib", true, Some(PROTOCOL_LIB))],
+			ResolveOptions::default(),
+		)
+		.await;
+	assert_eq!(resolved.external(), [("ProtocolLib", PROTOCOL_LIB)]);
+	assert!(resolved.report.is_complete());
+	assert!(resolved.requests.is_empty());
+}